@@ -4,19 +4,34 @@ use std::{
     path::PathBuf,
 };
 
-use clap::Parser;
-use lazy_static::lazy_static;
+use clap::ValueEnum;
 
-use crate::Format;
+/// Possible output formats
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum Format {
+    Apx,
+    #[default]
+    Tgf,
+}
 
-lazy_static! {
-    /// Global command line arguments
-    pub static ref ARGS: Args = Args::parse();
+impl Format {
+    const fn as_initial_file_ending(&self) -> &'static str {
+        match self {
+            Format::Apx => "apx",
+            Format::Tgf => "tgf",
+        }
+    }
+    const fn as_update_file_ending(&self) -> &'static str {
+        match self {
+            Format::Apx => "apxm",
+            Format::Tgf => "tgfm",
+        }
+    }
 }
 
 /// Generate AFs and optional updates for the dynamic context.
-#[derive(Debug, clap::Parser)]
-pub struct Args {
+#[derive(Debug, Clone, clap::Args)]
+pub struct GenerateArgs {
     /// Size of the initial AF.
     #[arg(
         short = 'n',
@@ -32,7 +47,7 @@ pub struct Args {
     /// The main file will be written to PATH-initial.EXT.
     /// The update file will be written to PATH-updates.EXTm.
     #[arg(short, long, value_name = "PATH")]
-    output: PathBuf,
+    pub output: PathBuf,
     /// Format for written files.
     #[arg(short, long, value_name = "EXT")]
     pub format: Format,
@@ -61,7 +76,7 @@ pub struct Args {
     pub output_intermediates: bool,
 }
 
-impl Args {
+impl GenerateArgs {
     pub fn get_initial_output_path(&self) -> PathBuf {
         let mut file_name = self.output_file_name();
         write!(
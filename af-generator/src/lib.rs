@@ -1,37 +1,13 @@
-//! Tool to generate random argumentation frameworks
+//! Generate random argumentation frameworks and updates for the dynamic context
 use std::{fmt::Write, fs::File, io::BufWriter, io::Write as IoWrite};
 
-use clap::ValueEnum;
 use rand::{rngs::SmallRng, seq::SliceRandom, Rng, SeedableRng};
 use types::{Argument, ArgumentWithState, Attack, AttackWithState, State};
 
 mod args;
 mod types;
 
-use args::ARGS;
-
-/// Possible output formats
-#[derive(Debug, Clone, Copy, Default, ValueEnum)]
-pub enum Format {
-    Apx,
-    #[default]
-    Tgf,
-}
-
-impl Format {
-    const fn as_initial_file_ending(&self) -> &'static str {
-        match self {
-            Format::Apx => "apx",
-            Format::Tgf => "tgf",
-        }
-    }
-    const fn as_update_file_ending(&self) -> &'static str {
-        match self {
-            Format::Apx => "apxm",
-            Format::Tgf => "tgfm",
-        }
-    }
-}
+pub use args::{Format, GenerateArgs};
 
 /// Possible update lines
 enum UpdateLine {
@@ -45,6 +21,7 @@ impl UpdateLine {
     /// Generate a single new update line.
     pub fn generate(
         rng: &mut impl Rng,
+        config: &GenerateArgs,
         args: &[ArgumentWithState],
         attacks: &[AttackWithState],
     ) -> Option<Self> {
@@ -92,7 +69,7 @@ impl UpdateLine {
                 let attacks: Vec<_> = dead_attacks
                     .into_iter()
                     .filter(|(attack, _)| attack.contains(arg))
-                    .filter(|_| rng.gen_bool(ARGS.edge_prop_when_adding_arg))
+                    .filter(|_| rng.gen_bool(config.edge_prop_when_adding_arg))
                     .map(|(attack, _)| *attack)
                     .collect();
                 Some(UpdateLine::EnableArgument(*arg, attacks))
@@ -116,8 +93,8 @@ impl UpdateLine {
     }
 
     /// Format this update line respecting the requested output format.
-    fn format(&self) -> String {
-        match ARGS.format {
+    fn format(&self, format: Format) -> String {
+        match format {
             Format::Apx => match self {
                 Self::EnableArgument(arg, atts) => {
                     let mut formatted = format!("+arg({})", arg.name());
@@ -159,9 +136,9 @@ struct AF {
 
 impl AF {
     /// Generate a new argumentation framework
-    fn generate(rng: &mut impl Rng) -> Self {
+    fn generate(rng: &mut impl Rng, config: &GenerateArgs) -> Self {
         // Generate af arguments and attacks
-        let args = generate_arguments(rng)
+        let args = generate_arguments(rng, config)
             .map(|arg| {
                 (
                     arg,
@@ -173,7 +150,7 @@ impl AF {
                 )
             })
             .collect();
-        let atts = generate_attacks(rng)
+        let atts = generate_attacks(rng, config)
             .map(|attack| {
                 (
                     attack,
@@ -190,9 +167,10 @@ impl AF {
     fn write_framework_to_file(
         &self,
         output: &mut BufWriter<File>,
+        format: Format,
         alive_only: bool,
     ) -> ::std::io::Result<()> {
-        match ARGS.format {
+        match format {
             Format::Apx => {
                 self.args
                     .iter()
@@ -253,32 +231,36 @@ impl AF {
         Ok(())
     }
     /// Write the initial file
-    fn write_initial_file(&self) -> ::std::io::Result<()> {
-        let initial_file_path = ARGS.get_initial_output_path();
+    fn write_initial_file(&self, config: &GenerateArgs) -> ::std::io::Result<()> {
+        let initial_file_path = config.get_initial_output_path();
         let mut output = BufWriter::new(File::create(initial_file_path)?);
-        self.write_framework_to_file(&mut output, false)
+        self.write_framework_to_file(&mut output, config.format, false)
     }
-    fn write_intermediate_file(&self, nr: usize) -> ::std::io::Result<()> {
-        let initial_file_path = ARGS.get_intermediate_output_path(nr);
+    fn write_intermediate_file(&self, config: &GenerateArgs, nr: usize) -> ::std::io::Result<()> {
+        let initial_file_path = config.get_intermediate_output_path(nr);
         let mut output = BufWriter::new(File::create(initial_file_path)?);
-        self.write_framework_to_file(&mut output, true)
+        self.write_framework_to_file(&mut output, config.format, true)
     }
     /// Generate and apply updates
-    fn generate_apply_updates(&mut self, rng: &mut impl Rng) -> Vec<UpdateLine> {
+    fn generate_apply_updates(
+        &mut self,
+        rng: &mut impl Rng,
+        config: &GenerateArgs,
+    ) -> Vec<UpdateLine> {
         let mut updates = vec![];
         // Initial intermediate, without `opt`s
-        if ARGS.output_intermediates {
-            if let Err(why) = self.write_intermediate_file(0) {
+        if config.output_intermediates {
+            if let Err(why) = self.write_intermediate_file(config, 0) {
                 log::warn!("Failed to write intermediate number 0: {why}");
             }
         }
-        for update_nr in 1..=ARGS.nr_of_updates {
-            let update = UpdateLine::generate(rng, &self.args, &self.atts);
+        for update_nr in 1..=config.nr_of_updates {
+            let update = UpdateLine::generate(rng, config, &self.args, &self.atts);
             match update {
                 Some(update) => {
                     self.apply_update(&update);
-                    if ARGS.output_intermediates {
-                        if let Err(why) = self.write_intermediate_file(update_nr) {
+                    if config.output_intermediates {
+                        if let Err(why) = self.write_intermediate_file(config, update_nr) {
                             log::warn!("Failed to write intermediate number {update_nr}: {why}");
                         }
                     }
@@ -330,19 +312,26 @@ impl AF {
     }
 }
 
-fn generate_arguments<R: Rng>(rng: &'_ mut R) -> impl Iterator<Item = Argument> + '_ {
-    (0..ARGS.arg_count).map(|id| {
-        let optional = rng.gen_bool(ARGS.arg_optional_prop);
+fn generate_arguments<'a, R: Rng>(
+    rng: &'a mut R,
+    config: &'a GenerateArgs,
+) -> impl Iterator<Item = Argument> + 'a {
+    (0..config.arg_count).map(|id| {
+        let optional = rng.gen_bool(config.arg_optional_prop);
         Argument::new(id, optional)
     })
 }
 
-fn generate_attacks<R: Rng>(rng: &'_ mut R) -> impl Iterator<Item = Attack> + '_ {
-    (0..ARGS.arg_count)
-        .flat_map(|from| (0..ARGS.arg_count).map(move |to| (from, to)))
+fn generate_attacks<'a, R: Rng>(
+    rng: &'a mut R,
+    config: &'a GenerateArgs,
+) -> impl Iterator<Item = Attack> + 'a {
+    let arg_count = config.arg_count;
+    (0..arg_count)
+        .flat_map(move |from| (0..arg_count).map(move |to| (from, to)))
         .filter_map(|(from, to)| {
-            if rng.gen_bool(ARGS.edge_prop) {
-                let optional = rng.gen_bool(ARGS.attack_optional_prop);
+            if rng.gen_bool(config.edge_prop) {
+                let optional = rng.gen_bool(config.attack_optional_prop);
                 Some(Attack::from_raw(from, to, optional))
             } else {
                 None
@@ -350,26 +339,30 @@ fn generate_attacks<R: Rng>(rng: &'_ mut R) -> impl Iterator<Item = Attack> + '_
         })
 }
 
-fn write_update_file(updates: &[UpdateLine]) -> ::std::io::Result<()> {
-    let update_file_path = ARGS.get_update_output_path();
+fn write_update_file(config: &GenerateArgs, updates: &[UpdateLine]) -> ::std::io::Result<()> {
+    let update_file_path = config.get_update_output_path();
     let mut output = BufWriter::new(File::create(update_file_path)?);
     updates
         .iter()
-        .map(|update| update.format())
+        .map(|update| update.format(config.format))
         .try_for_each(|line| writeln!(output, "{line}"))
 }
 
-fn main() {
+/// Generate a random framework and updates as configured and write them to disk.
+///
+/// See [`GenerateArgs`] for the paths used.
+pub fn generate(config: &GenerateArgs) -> ::std::io::Result<()> {
     // Initialize the PRNG
     let mut rng = SmallRng::from_rng(rand::thread_rng()).expect("Initializing RNG");
     // Generate AF
-    let mut af = AF::generate(&mut rng);
+    let mut af = AF::generate(&mut rng, config);
     // Write the initial file
-    af.write_initial_file().expect("Writing intial file");
+    af.write_initial_file(config)?;
     // Write update file
-    let updates = af.generate_apply_updates(&mut rng);
+    let updates = af.generate_apply_updates(&mut rng, config);
     if !updates.is_empty() {
         // Only write the file if we actually have updates to write
-        write_update_file(&updates).expect("Writing update file");
+        write_update_file(config, &updates)?;
     }
+    Ok(())
 }
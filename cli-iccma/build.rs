@@ -0,0 +1,36 @@
+//! Generate the man page at build time.
+//!
+//! The page is written to `$MAN_DIR` if set, otherwise to `$OUT_DIR`.
+use std::{env, fs, path::PathBuf};
+
+use clap::CommandFactory;
+
+#[allow(dead_code)]
+#[path = "src/args.rs"]
+mod args;
+
+/// Stand-in for the crate's error type, required by the included modules
+#[allow(dead_code)]
+#[derive(Debug)]
+struct Error(std::io::Error);
+
+type Result<T = (), E = Error> = std::result::Result<T, E>;
+
+impl From<std::io::Error> for Error {
+    fn from(why: std::io::Error) -> Self {
+        Self(why)
+    }
+}
+
+fn main() -> std::io::Result<()> {
+    println!("cargo:rerun-if-changed=src/args.rs");
+    println!("cargo:rerun-if-env-changed=MAN_DIR");
+    let man_dir = env::var_os("MAN_DIR")
+        .or_else(|| env::var_os("OUT_DIR"))
+        .map(PathBuf::from)
+        .expect("OUT_DIR is set by cargo");
+    let cmd = args::Args::command().name("cli-iccma");
+    let mut page = vec![];
+    clap_mangen::Man::new(cmd).render(&mut page)?;
+    fs::write(man_dir.join("cli-iccma.1"), page)
+}
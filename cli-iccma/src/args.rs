@@ -0,0 +1,229 @@
+use clap::{Parser, Subcommand, ValueEnum};
+use driver::{output::SampleFormat, PathOrStdin, Problem};
+use lazy_static::lazy_static;
+
+use std::{
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use crate::{Error, Result};
+
+lazy_static! {
+    /// Command line arguments
+    static ref ARGS: Args = Args::parse();
+}
+
+/// Enumeration of all possible tasks
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CliTask {
+    CeAd,
+    CeAdD,
+    CeCo,
+    CeCoD,
+    CePr,
+    CePrD,
+    CeSt,
+    CeStD,
+    EeAd,
+    EeAdD,
+    EeCo,
+    EeCoD,
+    EePr,
+    EePrD,
+    EeSt,
+    EeStD,
+    SeAd,
+    SeAdD,
+    SeCo,
+    SeCoD,
+    SeGr,
+    SeGrD,
+    SePr,
+    SePrD,
+    SeSt,
+    SeStD,
+}
+
+impl CliTask {
+    /// Whether this task reads updates after solving the initial framework.
+    pub const fn is_dynamic(&self) -> bool {
+        matches!(
+            self,
+            CliTask::CeAdD
+                | CliTask::CeCoD
+                | CliTask::CePrD
+                | CliTask::CeStD
+                | CliTask::EeAdD
+                | CliTask::EeCoD
+                | CliTask::EePrD
+                | CliTask::EeStD
+                | CliTask::SeAdD
+                | CliTask::SeCoD
+                | CliTask::SeGrD
+                | CliTask::SePrD
+                | CliTask::SeStD
+        )
+    }
+
+    /// The problem to solve, independent of semantics and dynamics.
+    pub const fn problem(&self) -> Problem {
+        match self {
+            CliTask::CeAd
+            | CliTask::CeAdD
+            | CliTask::CeCo
+            | CliTask::CeCoD
+            | CliTask::CePr
+            | CliTask::CePrD
+            | CliTask::CeSt
+            | CliTask::CeStD => Problem::Count,
+            CliTask::EeAd
+            | CliTask::EeAdD
+            | CliTask::EeCo
+            | CliTask::EeCoD
+            | CliTask::EePr
+            | CliTask::EePrD
+            | CliTask::EeSt
+            | CliTask::EeStD => Problem::Enumerate,
+            CliTask::SeAd
+            | CliTask::SeAdD
+            | CliTask::SeCo
+            | CliTask::SeCoD
+            | CliTask::SeGr
+            | CliTask::SeGrD
+            | CliTask::SePr
+            | CliTask::SePrD
+            | CliTask::SeSt
+            | CliTask::SeStD => Problem::Sample,
+        }
+    }
+}
+
+/// Invocation contract the solver was called with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Contract {
+    /// `--fo` given, answers are framed as in ICCMA'19 and '21
+    Iccma21,
+    /// No `--fo`, instance may be given positionally or on stdin,
+    /// witnesses are printed as `w a1 a2`
+    Iccma23,
+}
+
+/// Possible file formats
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum FileFormat {
+    Tgf,
+    Apx,
+}
+
+/// Helper commands, hidden from the help output
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Print shell completions to stdout
+    #[command(hide = true)]
+    Completions {
+        /// Shell to generate completions for
+        shell: clap_complete::Shell,
+    },
+}
+
+/// Modulear ASP solver FOr Dynamics
+#[derive(Debug, Parser)]
+#[command(version, about, args_conflicts_with_subcommands = true)]
+pub struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// File to load
+    #[arg(short, long)]
+    file: Option<PathBuf>,
+    /// File to load, as an alternative to `--file`. Without either, the instance is read from stdin
+    #[arg(value_name = "INSTANCE", conflicts_with = "file")]
+    instance: Option<PathBuf>,
+    /// Task to execute
+    #[arg(short = 'p', long, ignore_case = true)]
+    task: Option<CliTask>,
+    /// Show solver name, version, supported formats and problems
+    #[arg(long)]
+    about: bool,
+    /// Show supprted formats
+    #[arg(long)]
+    formats: bool,
+    /// Show supported problems
+    #[arg(long)]
+    problems: bool,
+    /// File format for `--file`
+    #[arg(long = "fo")]
+    file_format: Option<FileFormat>,
+    /// Argument to query by acceptance problems
+    #[arg(long, short, visible_alias = "additional-parameter", value_name = "ID")]
+    argument: Option<String>,
+    /// File to read updates from. Use '-' for stdin
+    #[arg(long, short, short_alias = 'm', default_value_t = PathOrStdin::Stdin)]
+    update_file: PathOrStdin,
+}
+
+impl Args {
+    /// Read the instance from `--file`, the positional path or stdin, in this order.
+    pub fn file_content(&self) -> Result<String> {
+        match self.file.as_ref().or(self.instance.as_ref()) {
+            Some(path) => ::std::fs::read_to_string(path).map_err(Error::from),
+            None if self.task.is_some_and(|task| task.is_dynamic())
+                && matches!(self.update_file, PathOrStdin::Stdin) =>
+            {
+                Err(Error::from(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Instance and updates cannot both be read from stdin, use --update-file",
+                )))
+            }
+            None => {
+                let mut content = String::new();
+                io::stdin().read_to_string(&mut content)?;
+                Ok(content)
+            }
+        }
+    }
+
+    pub fn contract(&self) -> Contract {
+        if self.file_format.is_some() {
+            Contract::Iccma21
+        } else {
+            Contract::Iccma23
+        }
+    }
+
+    /// How extensions of SE tasks are written under the contract.
+    pub fn sample_format(&self) -> SampleFormat {
+        match self.contract() {
+            Contract::Iccma21 => SampleFormat::List,
+            Contract::Iccma23 => SampleFormat::Witness,
+        }
+    }
+
+    pub fn should_show_problems(&self) -> bool {
+        self.problems
+    }
+
+    pub fn should_show_about(&self) -> bool {
+        self.about
+    }
+
+    pub fn should_show_formats(&self) -> bool {
+        self.formats
+    }
+
+    pub fn subcommand(&self) -> Option<&Command> {
+        self.command.as_ref()
+    }
+
+    pub fn task(&self) -> Option<CliTask> {
+        self.task
+    }
+
+    pub fn argument(&self) -> Option<&str> {
+        self.argument.as_deref()
+    }
+
+    pub fn update_file(&self) -> &PathOrStdin {
+        &self.update_file
+    }
+}
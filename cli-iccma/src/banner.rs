@@ -0,0 +1,43 @@
+//! Solver identification as expected by the ICCMA probo harness
+use clap::ValueEnum;
+
+use crate::args::{CliTask, FileFormat};
+
+/// Name used to identify the solver.
+const SOLVER_NAME: &str = "dasp";
+
+/// Supported problems, i.e. `[CE-AD,CE-AD-D,...]`.
+pub fn problems() -> String {
+    let names = CliTask::value_variants()
+        .iter()
+        .map(|task| value_name(task).to_uppercase());
+    bracketed(names)
+}
+
+/// Supported file formats, i.e. `[tgf,apx]`.
+pub fn formats() -> String {
+    bracketed(FileFormat::value_variants().iter().map(value_name))
+}
+
+/// Full banner printed without arguments or with `--about`.
+pub fn banner() -> String {
+    format!(
+        "{SOLVER_NAME} {}\n{}\n{}\n{}",
+        env!("CARGO_PKG_VERSION"),
+        env!("CARGO_PKG_AUTHORS"),
+        formats(),
+        problems()
+    )
+}
+
+fn value_name(value: &impl ValueEnum) -> String {
+    value
+        .to_possible_value()
+        .expect("No value is skipped")
+        .get_name()
+        .to_owned()
+}
+
+fn bracketed(names: impl Iterator<Item = String>) -> String {
+    format!("[{}]", names.collect::<Vec<_>>().join(","))
+}
@@ -1,145 +1,93 @@
 //! Dynamic Argumentation Solved using ASP
-use args::{Args, CliTask};
-use clap::Parser;
+use args::{Args, CliTask, Command};
+use clap::{CommandFactory, Parser};
 use fallible_iterator::FallibleIterator;
 use lib::{
-    argumentation_framework::{semantics::ArgumentationFrameworkSemantic, ArgumentationFramework},
-    framework::GenericExtension,
-    semantics, Error, Result,
+    argumentation_framework::{
+        semantics::ArgumentationFrameworkSemantic, symbols, ArgumentationFramework,
+    },
+    semantics, Error, Framework, Result,
 };
 
-use crate::context::Context;
-
 mod args;
-mod context;
-
-pub enum Dynamics {
-    No,
-    Yes,
-}
+mod banner;
 
 fn main() -> Result {
     pretty_env_logger::init();
 
     let args = Args::parse();
-    if args.should_show_problems() {
-        println!("[CE-CO,CE-ST,SE-CO,SE-ST]");
+    if let Some(Command::Completions { shell }) = args.subcommand() {
+        let mut cmd = Args::command();
+        clap_complete::generate(
+            *shell,
+            &mut cmd,
+            env!("CARGO_BIN_NAME"),
+            &mut std::io::stdout(),
+        );
+        Ok(())
+    } else if args.should_show_about() {
+        println!("{}", banner::banner());
+        Ok(())
+    } else if args.should_show_problems() {
+        println!("{}", banner::problems());
         Ok(())
     } else if args.should_show_formats() {
-        println!("[apx,tgf]");
+        println!("{}", banner::formats());
         Ok(())
     } else if let Some(task) = args.task() {
-        // Decide task to execute
+        // Decide the semantics, the problem is handled by the driver
         match task {
-            CliTask::CeCoD => {
-                run_task_count_extensions::<semantics::Complete>(&args, Dynamics::Yes)
-            }
-            CliTask::CeCo => run_task_count_extensions::<semantics::Complete>(&args, Dynamics::No),
-            CliTask::CeSt => run_task_count_extensions::<semantics::Stable>(&args, Dynamics::No),
-            CliTask::EeCoD => {
-                run_task_enumerate_extensions::<semantics::Complete>(&args, Dynamics::Yes)
-            }
-            CliTask::EeCo => {
-                run_task_enumerate_extensions::<semantics::Complete>(&args, Dynamics::No)
-            }
-            CliTask::SeCoD => {
-                run_task_sample_extension::<semantics::Complete>(&args, Dynamics::Yes)
-            }
-            CliTask::SeCo => run_task_sample_extension::<semantics::Complete>(&args, Dynamics::No),
-            CliTask::SeSt => run_task_sample_extension::<semantics::Stable>(&args, Dynamics::No),
-            CliTask::EeStD => {
-                run_task_enumerate_extensions::<semantics::Stable>(&args, Dynamics::Yes)
-            }
-            CliTask::EeSt => {
-                run_task_enumerate_extensions::<semantics::Stable>(&args, Dynamics::No)
-            }
-            CliTask::CeAd => {
-                run_task_count_extensions::<semantics::Admissible>(&args, Dynamics::No)
-            }
-            CliTask::SeAd => {
-                run_task_sample_extension::<semantics::Admissible>(&args, Dynamics::No)
-            }
-            CliTask::EeAd => {
-                run_task_enumerate_extensions::<semantics::Admissible>(&args, Dynamics::No)
-            }
-            CliTask::CeAdD => {
-                run_task_count_extensions::<semantics::Admissible>(&args, Dynamics::Yes)
-            }
-            CliTask::CeStD => run_task_count_extensions::<semantics::Stable>(&args, Dynamics::Yes),
-            CliTask::EeAdD => {
-                run_task_enumerate_extensions::<semantics::Admissible>(&args, Dynamics::Yes)
-            }
-            CliTask::SeAdD => {
-                run_task_sample_extension::<semantics::Admissible>(&args, Dynamics::Yes)
-            }
-            CliTask::SeStD => run_task_sample_extension::<semantics::Stable>(&args, Dynamics::Yes),
+            CliTask::CeAd
+            | CliTask::CeAdD
+            | CliTask::EeAd
+            | CliTask::EeAdD
+            | CliTask::SeAd
+            | CliTask::SeAdD => run_task::<semantics::Admissible>(&args, task),
+            CliTask::CeCo
+            | CliTask::CeCoD
+            | CliTask::EeCo
+            | CliTask::EeCoD
+            | CliTask::SeCo
+            | CliTask::SeCoD => run_task::<semantics::Complete>(&args, task),
+            CliTask::SeGr | CliTask::SeGrD => run_task::<semantics::Ground>(&args, task),
+            CliTask::CePr
+            | CliTask::CePrD
+            | CliTask::EePr
+            | CliTask::EePrD
+            | CliTask::SePr
+            | CliTask::SePrD => run_task::<semantics::Preferred>(&args, task),
+            CliTask::CeSt
+            | CliTask::CeStD
+            | CliTask::EeSt
+            | CliTask::EeStD
+            | CliTask::SeSt
+            | CliTask::SeStD => run_task::<semantics::Stable>(&args, task),
         }
     } else {
-        println!("dasp v0.1");
-        println!("Malte Tammena, malte.tammena@pm.me");
+        println!("{}", banner::banner());
         Ok(())
     }
 }
 
-fn run_task_enumerate_extensions<P: ArgumentationFrameworkSemantic>(
-    args: &Args,
-    dynamics: Dynamics,
-) -> Result {
-    let mut ctx = Context::<ArgumentationFramework<P>>::new(args)?;
-    ctx.enumerate_extensions()?.by_ref().for_each(|ext| {
-        println!("{}", ext.format());
-        Ok(())
-    })?;
-    if matches!(dynamics, Dynamics::Yes) {
-        let mut update_iter = args.update_file().lines()?;
-        while let Some(update) = update_iter.next()? {
-            ctx.update(&update)?;
-            log::trace!("Found update: {:?}", update);
-            ctx.enumerate_extensions()?.by_ref().for_each(|ext| {
-                println!("{}", ext.format());
-                Ok(())
-            })?;
-        }
-    }
-    Ok(())
-}
-
-fn run_task_count_extensions<P: ArgumentationFrameworkSemantic>(
-    args: &Args,
-    dynamics: Dynamics,
-) -> Result {
-    let mut ctx = Context::<ArgumentationFramework<P>>::new(args)?;
-    println!("{}", ctx.count_extensions()?);
-    if matches!(dynamics, Dynamics::Yes) {
-        let mut update_iter = args.update_file().lines()?;
-        while let Some(update) = update_iter.next()? {
-            ctx.update(&update)?;
-            println!("{}", ctx.count_extensions()?);
-        }
-    }
-    Ok(())
-}
-
-fn run_task_sample_extension<P: ArgumentationFrameworkSemantic>(
-    args: &Args,
-    dynamics: Dynamics,
-) -> Result {
-    let mut ctx = Context::<ArgumentationFramework<P>>::new(args)?;
-    match ctx.sample_extension()? {
-        Some(ext) => println!("{}", ext.format()),
-        None => println!("NO"),
-    }
-    if matches!(dynamics, Dynamics::Yes) {
-        let mut update_iter = args.update_file().lines()?;
-        while let Some(update) = update_iter.next()? {
-            ctx.update(&update)?;
-            match ctx.sample_extension()? {
-                Some(ext) => println!("{}", ext.format()),
-                None => println!("NO"),
-            }
-        }
-    }
-    Ok(())
+fn run_task<S: ArgumentationFrameworkSemantic>(args: &Args, task: CliTask) -> Result {
+    let mut af = ArgumentationFramework::<S>::new(&args.file_content()?)?;
+    let argument = args
+        .argument()
+        .map(|id| symbols::find(af.arguments(), id).cloned())
+        .transpose()?;
+    let updates: Box<dyn FallibleIterator<Item = String, Error = Error>> = if task.is_dynamic() {
+        Box::new(args.update_file().lines(false)?)
+    } else {
+        Box::new(fallible_iterator::convert(std::iter::empty()))
+    };
+    driver::task::run(
+        &mut af,
+        task.problem(),
+        args.sample_format(),
+        argument.as_ref(),
+        updates,
+        &mut std::io::stdout().lock(),
+    )
 }
 
 #[cfg(test)]
@@ -240,6 +188,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn problems_are_listed_from_the_task_registry() {
+        let problems = crate::banner::problems();
+        assert!(problems.starts_with("[CE-AD,CE-AD-D,"));
+        assert!(problems.ends_with(",SE-ST,SE-ST-D]"));
+        assert_eq!(crate::banner::formats(), "[tgf,apx]");
+    }
+
+    #[test]
+    fn iccma23_invocation_is_accepted() {
+        use crate::args::{Args, Contract};
+        use clap::Parser;
+
+        let args = Args::try_parse_from(["", "-p", "SE-AD", "instance.tgf"]).unwrap();
+        assert_eq!(args.contract(), Contract::Iccma23);
+        let args =
+            Args::try_parse_from(["", "-p", "se-ad", "-f", "af.tgf", "--fo", "tgf"]).unwrap();
+        assert_eq!(args.contract(), Contract::Iccma21);
+        assert!(Args::try_parse_from(["", "-p", "se-ad", "-f", "af.tgf", "af.tgf"]).is_err());
+    }
+
     #[ignore = "complete not supported yet"]
     #[test]
     fn enumerate_complete_extensions() {
@@ -1,13 +1,5 @@
 use criterion::{criterion_group, criterion_main, Criterion};
-use tempfile::NamedTempFile;
-
-// TODO: Currently broken
-
-use std::{
-    fs::File,
-    io::{BufReader, BufWriter},
-    path::Path,
-};
+use lib::instances::InstanceCache;
 
 // {b,be,e}_{5..=10}_{1..=10}_{6..=10}.{apx,apx_arg,tgf,tgf_arg}
 
@@ -121,33 +113,18 @@ const ICCMA_2019_INSTANCE_NAMES_SHUFFLED: &[&str] = &[
     "A-3-afinput_exp_cycles_indvary2_step1_batch_yyy04",
 ];
 
-fn create_extracted_temp_file<P: AsRef<Path>>(path: &P) -> NamedTempFile {
-    let mut reader = BufReader::new(File::open(path).unwrap());
-    let mut target = NamedTempFile::new().unwrap();
-    {
-        let mut writer = BufWriter::new(&mut target);
-        lzma_rs::lzma_decompress(&mut reader, &mut writer).unwrap();
-    }
-    target
-}
-
+/// Runs `dasp` on the first instances, fetched via [`InstanceCache::from_env`].
 fn run_5_argumentation_instances(c: &mut Criterion) {
+    let cache = InstanceCache::from_env().expect("Opening instance cache");
     for instance in ICCMA_2019_INSTANCE_NAMES_SHUFFLED.iter().take(5) {
-        eprintln!("{:?}", ::std::env::current_dir());
-        let orig_path = format!("./benches/argumentation-frameworks/{instance}.tgf.lzma");
-        let file = create_extracted_temp_file(&orig_path);
-        c.bench_function(&format!("ee-st '{instance}'"), |b| {
+        let file = cache.get(instance, "tgf").expect("Fetching instance");
+        c.bench_function(&format!("ee-ad '{instance}'"), |b| {
             b.iter(|| {
-                assert_cmd::Command::cargo_bin("cli")
+                assert_cmd::Command::cargo_bin("dasp")
                     .expect("Cargo binary found")
-                    .args(&[
-                        "--file",
-                        &file.path().to_str().unwrap(), // Load file
-                        "--fo",
-                        "tgf", // TGF format
-                        "--task",
-                        "ee-st", // Execute task
-                    ])
+                    .args(["solve", "--file"])
+                    .arg(&file) // Load file
+                    .args(["--task", "ee-ad"]) // Execute task
                     .unwrap()
             })
         });
@@ -1,10 +1,13 @@
-use std::path::PathBuf;
+use std::{
+    num::{NonZeroU64, NonZeroUsize},
+    path::PathBuf,
+};
 
-use clap::{Parser, ValueEnum};
+use af_generator::{Format, GenerateArgs};
+use clap::{Parser, Subcommand, ValueEnum};
+use driver::PathOrStdin;
 use lazy_static::lazy_static;
 
-use crate::path_or_stdin::PathOrStdin;
-
 lazy_static! {
     /// Command line arguments
     pub static ref ARGS: Args = Args::parse();
@@ -19,19 +22,544 @@ pub enum CliTask {
     EeAdD,
     SeAd,
     SeAdD,
+    MaxAd,
+    MaxAdD,
+    MinAd,
+    MinAdD,
+    DcAd,
+    DcAdD,
+    DcCo,
+    DcCoD,
+    DcSt,
+    DcStD,
+    DsAd,
+    DsAdD,
+    DsCo,
+    DsCoD,
+    DsPr,
+    DsPrD,
+    DsSt,
+    DsStD,
+    EcAd,
+    EcAdD,
+    EcCo,
+    EcCoD,
+    EcPr,
+    EcPrD,
+    EcSt,
+    EcStD,
+    EsAd,
+    EsAdD,
+    EsCo,
+    EsCoD,
+    EsPr,
+    EsPrD,
+    EsSt,
+    EsStD,
+    ExSt,
+    ExStD,
+}
+
+impl CliTask {
+    /// Whether this task reads updates after solving the initial framework.
+    pub const fn is_dynamic(&self) -> bool {
+        matches!(
+            self,
+            CliTask::CeAdD
+                | CliTask::EeAdD
+                | CliTask::SeAdD
+                | CliTask::MaxAdD
+                | CliTask::MinAdD
+                | CliTask::DcAdD
+                | CliTask::DcCoD
+                | CliTask::DcStD
+                | CliTask::DsAdD
+                | CliTask::DsCoD
+                | CliTask::DsPrD
+                | CliTask::DsStD
+                | CliTask::EcAdD
+                | CliTask::EcCoD
+                | CliTask::EcPrD
+                | CliTask::EcStD
+                | CliTask::EsAdD
+                | CliTask::EsCoD
+                | CliTask::EsPrD
+                | CliTask::EsStD
+                | CliTask::ExStD
+        )
+    }
+}
+
+/// Reaction to update lines that cannot be applied
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum OnUpdateError {
+    /// Log the error and continue with the next update
+    Skip,
+    /// Abort the run
+    #[default]
+    Stop,
+    /// Ask on the terminal whether to skip the update
+    Prompt,
+}
+
+/// Format of error reports
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ErrorFormat {
+    /// Human-readable text
+    Text,
+    /// JSON object with kind, message, file, line and span
+    Json,
+}
+
+/// Domain heuristics selectable on the command line
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CliHeuristic {
+    /// Default heuristic of clingo
+    Default,
+    /// Prefer leaving arguments out of the extension
+    PreferOut,
+    /// Decide on arguments attacking many others first
+    AttackDegree,
+}
+
+/// Flavors of parallel solving selectable on the command line
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CliParallelMode {
+    /// Threads solve the whole problem with different configurations
+    Compete,
+    /// Threads split the search space
+    Split,
+}
+
+/// Policies for attacks on undeclared arguments selectable on the command line
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CliUndeclared {
+    /// Reject the framework
+    Reject,
+    /// Warn and drop the attack
+    Ignore,
+    /// Declare the missing argument
+    Declare,
+}
+
+/// Semantics selectable on the command line
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CliSemantics {
+    /// Admissible
+    Ad,
+    /// Complete
+    Co,
+    /// Grounded
+    Gr,
+    /// Stable
+    St,
+}
+
+/// Available subcommands
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Solve a task on a (dynamic) argumentation framework
+    Solve(SolveArgs),
+    /// Generate random frameworks and optional updates
+    Generate(GenerateArgs),
+    /// Convert a framework between APX and TGF
+    Convert(ConvertArgs),
+    /// Check that a framework and its updates are well-formed
+    Validate(ValidateArgs),
+    /// Measure the time needed to solve a task
+    Bench(BenchArgs),
+    /// Solve a recorded session again and compare the results
+    Replay(ReplayArgs),
+    /// Compare the extensions of two frameworks
+    Diff(DiffArgs),
+    /// Check whether two frameworks are equivalent under a semantics
+    Equivalent(EquivalentArgs),
+    /// Print statistics about a framework
+    Stats(StatsArgs),
+    /// Apply one update stream to several frameworks and compare their answers
+    Lockstep(LockstepArgs),
+    /// Keep a framework loaded and answer commands on a Unix socket
+    #[cfg(unix)]
+    Serve(ServeArgs),
+    /// Expose frameworks over an HTTP API speaking JSON
+    #[cfg(feature = "http")]
+    Http(HttpArgs),
+    /// Step through the updates of a framework in an interactive terminal UI
+    #[cfg(feature = "tui")]
+    Explore(ExploreArgs),
+    /// Check the known relations between the semantics on a framework
+    Verify(VerifyArgs),
+    /// Explain why an argument is rejected by the attacks that would need to be removed
+    Explain(ExplainArgs),
+    /// Answer an acceptance query with a checkable certificate, or check a certificate
+    Certify(CertifyArgs),
+    /// Check the clingo installation and solve built-in instances
+    Doctor,
+    /// Print shell completions to stdout
+    #[command(hide = true)]
+    Completions {
+        /// Shell to generate completions for
+        shell: clap_complete::Shell,
+    },
 }
 
 /// Modulear ASP solver FOr Dynamics
 #[derive(Debug, Parser)]
-#[command(version, about)]
+#[command(name = "dasp", version, about)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Command,
+    /// Format of errors printed on failure.
+    #[arg(long, global = true, value_name = "FORMAT", default_value = "text")]
+    pub error_format: ErrorFormat,
+}
+
+/// Arguments for solving a task.
+#[derive(Debug, clap::Args)]
+pub struct SolveArgs {
     /// File to load.
     #[arg(short, long)]
     pub file: PathBuf,
+    /// Memory-map the file instead of reading it into memory first. Saves a copy of
+    /// multi-GB instances. The file must not change while solving.
+    #[cfg(unix)]
+    #[arg(long)]
+    pub mmap: bool,
     /// Task to execute
-    #[arg(short = 'p', long, requires = "file")]
+    #[arg(short = 'p', long)]
+    pub task: CliTask,
+    /// File to read updates from. Use '-' for stdin
+    #[arg(long, short, default_value_t = PathOrStdin::Stdin)]
+    pub update_file: PathOrStdin,
+    /// Argument to query by acceptance tasks, i.e. DC and DS.
+    #[arg(long, short, visible_alias = "additional-parameter", value_name = "ID")]
+    pub argument: Option<String>,
+    /// Solve under the semantics encoded by this ASP program instead of the one of the task.
+    /// It has to use `argument/1` and `attack/2` and define the extensions by `in/1`.
+    #[arg(long, value_name = "PATH")]
+    pub semantics_file: Option<PathBuf>,
+    /// Only check that every update line parses and changes optional arguments or attacks
+    /// of the initial framework, reporting all problems without solving.
+    #[arg(long)]
+    pub check_updates: bool,
+    /// Only check every update line against the loaded framework, applying the lines
+    /// without problems, and report all problems without solving. Patches that do not
+    /// change the framework are warned about.
+    #[arg(long, conflicts_with = "check_updates")]
+    pub validate_updates: bool,
+    /// What to do with update lines that cannot be applied.
+    #[arg(long, value_name = "ACTION", default_value = "stop")]
+    pub on_update_error: OnUpdateError,
+    /// Only compute and print the first N extensions per solve of EE tasks.
+    #[arg(long, value_name = "N")]
+    pub first: Option<NonZeroUsize>,
+    /// Print N distinct extensions per solve of SE tasks, drawn with randomized solver
+    /// heuristics instead of a single one.
+    #[arg(long, value_name = "N")]
+    pub sample_k: Option<NonZeroUsize>,
+    /// Enclose every answer in `BEGIN ANSWER <N>` and `END ANSWER` lines, where answer N
+    /// belongs to the framework after the first N update lines.
+    #[arg(long)]
+    pub framed: bool,
+    /// Wait for lines appended to the update file instead of stopping at its end, like `tail -f`,
+    /// and skip empty lines. Stdin is read until it is closed.
+    #[arg(long)]
+    pub follow: bool,
+    /// Print the difference to the previous and the initial count after every update.
+    /// Only used by dynamic counting tasks.
+    #[arg(long)]
+    pub deltas: bool,
+    /// After every update, only print extensions that appeared (`+`) or disappeared (`-`).
+    /// Only used by dynamic enumeration tasks.
+    #[arg(long)]
+    pub delta_output: bool,
+    #[command(flatten)]
+    pub solver: SolverArgs,
+    /// Stop solving after the given wall-clock time (e.g. `30s`, `5min`) and print `LIMIT time`.
+    #[arg(long, value_name = "DURATION")]
+    pub max_time: Option<humantime::Duration>,
+    /// Stop solving once the resident memory exceeds the given MiB and print `LIMIT memory`.
+    #[arg(long, value_name = "MIB")]
+    pub max_memory: Option<u64>,
+    /// Stop every solve after the given number of conflicts and print `LIMIT conflicts`,
+    /// unless it has decided whether there is an extension by then.
+    #[arg(long, value_name = "N")]
+    pub max_conflicts: Option<NonZeroU64>,
+    /// Periodically save the progress of dynamic tasks to PATH and resume from it if it exists.
+    /// The file is removed once all updates have been processed.
+    #[arg(long, value_name = "PATH")]
+    pub checkpoint: Option<PathBuf>,
+    /// Number of updates between two checkpoints.
+    #[arg(long, value_name = "N", default_value_t = NonZeroUsize::new(100).unwrap(), requires = "checkpoint")]
+    pub checkpoint_every: NonZeroUsize,
+    /// Write a CSV row per update to PATH with the update number, patch, extension count,
+    /// solve time and number of credulously accepted arguments.
+    #[arg(long, value_name = "PATH")]
+    pub summary: Option<PathBuf>,
+    /// Record the inputs, arguments and output of this run to DIR, to be replayed later
+    /// with `dasp replay`.
+    #[arg(long, value_name = "DIR")]
+    pub record: Option<PathBuf>,
+    /// Print the time spent parsing, grounding, solving and patching to stderr when done.
+    #[cfg(feature = "profiling")]
+    #[arg(long)]
+    pub profile: bool,
+}
+
+/// Arguments configuring the solver backend.
+#[derive(Debug, clap::Args)]
+pub struct SolverArgs {
+    /// Number of threads to use for solving. Defaults to the number of available cores.
+    #[arg(long, value_name = "N")]
+    pub threads: Option<NonZeroUsize>,
+    /// How multiple threads work together.
+    #[arg(long, value_name = "MODE", default_value = "compete")]
+    pub parallel_mode: CliParallelMode,
+    /// Additional option passed verbatim to clingo. Can be given multiple times.
+    #[arg(long = "clingo-arg", value_name = "OPT", allow_hyphen_values = true)]
+    pub clingo_args: Vec<String>,
+    /// Level on which warnings of clingo are logged, `off` to hide them.
+    #[arg(long, value_name = "LEVEL", default_value = "info")]
+    pub clingo_log_level: log::LevelFilter,
+    /// Domain heuristic guiding the solver.
+    #[arg(long, value_name = "HEURISTIC", default_value = "default")]
+    pub heuristic: CliHeuristic,
+    /// Solve complete, grounded and stable semantics SCC by SCC.
+    #[arg(long)]
+    pub decompose: bool,
+    /// Skip duplicate extensions within a solve, remembering up to N of them. Only needed
+    /// if projection is disabled with `--clingo-arg=--project=no`.
+    #[arg(long, value_name = "N")]
+    pub deduplicate: Option<NonZeroUsize>,
+    /// Keep learnt clauses and heuristic state between the solves of dynamic tasks.
+    #[arg(long)]
+    pub warm_start: bool,
+    /// Reject duplicate or conflicting declarations and attacks on undeclared arguments
+    /// instead of warning about them.
+    #[arg(long)]
+    pub strict: bool,
+    /// Reject optional arguments and attacks, accepting plain ICCMA files only.
+    #[arg(long)]
+    pub iccma_compliant: bool,
+    /// How to handle attacks on arguments that were never declared.
+    #[arg(long, value_name = "POLICY", default_value = "ignore")]
+    pub undeclared: CliUndeclared,
+    /// Remember results per framework state, answering revisited states without solving.
+    #[arg(long)]
+    pub cache: bool,
+}
+
+/// Arguments for serving a framework.
+#[cfg(unix)]
+#[derive(Debug, clap::Args)]
+pub struct ServeArgs {
+    /// File to load.
+    #[arg(short, long)]
+    pub file: PathBuf,
+    /// Unix socket to listen on.
+    #[arg(long, value_name = "PATH")]
+    pub socket: PathBuf,
+    /// Semantics to answer the requests under.
+    #[arg(short, long, default_value = "ad")]
+    pub semantics: CliSemantics,
+    #[command(flatten)]
+    pub solver: SolverArgs,
+}
+
+/// Arguments for the interactive explorer.
+#[cfg(feature = "tui")]
+#[derive(Debug, clap::Args)]
+pub struct ExploreArgs {
+    /// Framework to explore.
+    #[arg(short, long)]
+    pub file: PathBuf,
+    /// Updates to step through, in APXM or TGFM format.
+    #[arg(long, short)]
+    pub update_file: Option<PathBuf>,
+    /// Semantics to enumerate the extensions of.
+    #[arg(short, long, default_value = "ad")]
+    pub semantics: CliSemantics,
+    #[command(flatten)]
+    pub solver: SolverArgs,
+}
+
+/// Arguments for the HTTP API.
+#[cfg(feature = "http")]
+#[derive(Debug, clap::Args)]
+pub struct HttpArgs {
+    /// File to load on startup. Frameworks can be uploaded later on as well.
+    #[arg(short, long)]
+    pub file: Option<PathBuf>,
+    /// Address to listen on.
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:8080")]
+    pub listen: String,
+    /// Semantics to solve the loaded frameworks under.
+    #[arg(short, long, default_value = "ad")]
+    pub semantics: CliSemantics,
+    #[command(flatten)]
+    pub solver: SolverArgs,
+}
+
+/// Arguments for converting a framework.
+#[derive(Debug, clap::Args)]
+pub struct ConvertArgs {
+    /// File to convert.
+    #[arg(short, long)]
+    pub file: PathBuf,
+    /// Format to convert to.
+    #[arg(long, value_name = "EXT")]
+    pub to: Format,
+    /// File to write to. Defaults to stdout.
+    #[arg(short, long, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+}
+
+/// Arguments for validating input files.
+#[derive(Debug, clap::Args)]
+pub struct ValidateArgs {
+    /// File to validate.
+    #[arg(short, long)]
+    pub file: PathBuf,
+    /// Update file to validate as well. Use '-' for stdin
+    #[arg(long, short)]
+    pub update_file: Option<PathOrStdin>,
+    /// Count duplicate or conflicting declarations and attacks on undeclared arguments
+    /// as problems instead of warnings.
+    #[arg(long)]
+    pub strict: bool,
+    /// Reject optional arguments and attacks, accepting plain ICCMA files only.
+    #[arg(long)]
+    pub iccma_compliant: bool,
+}
+
+/// Arguments for replaying a recorded session.
+#[derive(Debug, clap::Args)]
+pub struct ReplayArgs {
+    /// Directory created by `dasp solve --record`.
+    pub dir: PathBuf,
+}
+
+/// Arguments for comparing two frameworks.
+#[derive(Debug, clap::Args)]
+pub struct DiffArgs {
+    /// First framework.
+    pub file_a: PathBuf,
+    /// Second framework.
+    pub file_b: PathBuf,
+    /// Semantics to compare the frameworks under.
+    #[arg(short, long, default_value = "ad")]
+    pub semantics: CliSemantics,
+    #[command(flatten)]
+    pub solver: SolverArgs,
+}
+
+/// Arguments for checking the equivalence of two frameworks.
+#[derive(Debug, clap::Args)]
+pub struct EquivalentArgs {
+    /// First framework.
+    pub file_a: PathBuf,
+    /// Second framework.
+    pub file_b: PathBuf,
+    /// Semantics to compare the frameworks under.
+    #[arg(short, long, default_value = "ad")]
+    pub semantics: CliSemantics,
+    /// Check strong expansion equivalence, i.e. whether the frameworks stay equivalent
+    /// after adding the same arguments and attacks to both.
+    #[arg(long)]
+    pub strong: bool,
+    #[command(flatten)]
+    pub solver: SolverArgs,
+}
+
+/// Arguments for applying one update stream to several frameworks.
+#[derive(Debug, clap::Args)]
+pub struct LockstepArgs {
+    /// Frameworks to load.
+    #[arg(required = true)]
+    pub files: Vec<PathBuf>,
+    /// Task to execute on every framework. Its semantics are replaced by `--semantics`.
+    #[arg(short = 'p', long)]
     pub task: CliTask,
+    /// Semantics to load every framework under. Can be given multiple times.
+    #[arg(short, long, default_value = "ad")]
+    pub semantics: Vec<CliSemantics>,
     /// File to read updates from. Use '-' for stdin
     #[arg(long, short, default_value_t = PathOrStdin::Stdin)]
     pub update_file: PathOrStdin,
+    #[command(flatten)]
+    pub solver: SolverArgs,
+}
+
+/// Arguments for framework statistics.
+#[derive(Debug, clap::Args)]
+pub struct StatsArgs {
+    /// Framework to analyze.
+    pub file: PathBuf,
+    /// Print the statistics as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Arguments for checking the relations between the semantics.
+#[derive(Debug, clap::Args)]
+pub struct VerifyArgs {
+    /// Framework to check.
+    pub file: PathBuf,
+    /// Check again after every update read from this file. Use '-' for stdin
+    #[arg(long, short)]
+    pub update_file: Option<PathOrStdin>,
+    #[command(flatten)]
+    pub solver: SolverArgs,
+}
+
+/// Arguments for explaining the rejection of an argument.
+#[derive(Debug, clap::Args)]
+pub struct ExplainArgs {
+    /// Framework containing the argument.
+    pub file: PathBuf,
+    /// Argument to explain.
+    #[arg(short, long)]
+    pub argument: String,
+    /// Semantics under which the argument is rejected.
+    #[arg(short, long, default_value = "ad")]
+    pub semantics: CliSemantics,
+    /// Apply all updates from this file before explaining. Use '-' for stdin
+    #[arg(long, short)]
+    pub update_file: Option<PathOrStdin>,
+    #[command(flatten)]
+    pub solver: SolverArgs,
+}
+
+/// Arguments for certifying an acceptance answer.
+#[derive(Debug, clap::Args)]
+pub struct CertifyArgs {
+    /// Framework containing the argument.
+    pub file: PathBuf,
+    /// Argument to certify the answer for.
+    #[arg(short, long)]
+    pub argument: String,
+    /// Semantics of the query. Certificates exist for admissible, complete and grounded.
+    #[arg(short, long, default_value = "co")]
+    pub semantics: CliSemantics,
+    /// Certify skeptical instead of credulous acceptance.
+    #[arg(long)]
+    pub skeptical: bool,
+    /// Check the certificate in this file instead of solving.
+    #[arg(long, value_name = "CERTIFICATE")]
+    pub check: Option<PathBuf>,
+    #[command(flatten)]
+    pub solver: SolverArgs,
+}
+
+/// Arguments for benchmarking a task.
+#[derive(Debug, clap::Args)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    pub solve: SolveArgs,
+    /// Number of measured runs.
+    #[arg(long, value_name = "NUM", default_value_t = NonZeroUsize::new(10).unwrap())]
+    pub runs: NonZeroUsize,
+    /// Number of unmeasured runs before measuring.
+    #[arg(long, value_name = "NUM", default_value_t = 1)]
+    pub warmup: usize,
 }
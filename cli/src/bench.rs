@@ -0,0 +1,43 @@
+//! Benchmark solving a task
+use std::time::{Duration, Instant};
+
+use fallible_iterator::FallibleIterator;
+use humantime::format_duration;
+
+use crate::{
+    args::BenchArgs,
+    limits,
+    solve::{run_task, Updates},
+    Result,
+};
+
+/// Run the task repeatedly, discarding all answers, and print timing statistics.
+pub fn run(args: &BenchArgs) -> Result {
+    // Updates are read once, so that every run sees the same ones
+    let updates: Vec<String> = args.solve.updates()?.collect()?;
+    let replay = || -> Updates {
+        Box::new(fallible_iterator::convert(
+            updates.clone().into_iter().map(Ok),
+        ))
+    };
+    for _ in 0..args.warmup {
+        run_task(&args.solve, replay(), &mut std::io::sink())?;
+        limits::check()?;
+    }
+    let mut timings = Vec::with_capacity(args.runs.get());
+    for run in 1..=args.runs.get() {
+        let before = Instant::now();
+        run_task(&args.solve, replay(), &mut std::io::sink())?;
+        let elapsed = before.elapsed();
+        log::info!("Run #{run} took {}", format_duration(elapsed));
+        timings.push(elapsed);
+        limits::check()?;
+    }
+    let total: Duration = timings.iter().sum();
+    let mean = total / timings.len() as u32;
+    println!("runs {}", timings.len());
+    println!("min  {}", format_duration(*timings.iter().min().unwrap()));
+    println!("mean {}", format_duration(mean));
+    println!("max  {}", format_duration(*timings.iter().max().unwrap()));
+    Ok(())
+}
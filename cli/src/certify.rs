@@ -0,0 +1,197 @@
+//! Answer acceptance queries with checkable certificates
+//!
+//! Certificates are printed as JSON, either
+//! `{"argument": "a", "extension": ["a"], "defenses": [{"attacker": "b", "defender": "a"}]}`
+//! or `{"argument": "b", "labelling": {"a": "in", "b": "out"}}`.
+use lib::argumentation_framework::{
+    certify::{Certificate, Defense},
+    decomposition::Label,
+    parse_initial_file,
+    semantics::ArgumentationFrameworkSemantic,
+    symbols, ArgumentationFramework,
+};
+use lib::semantics;
+use serde_json::{json, Value};
+
+use crate::{
+    args::{CertifyArgs, CliSemantics},
+    Error, Result,
+};
+
+/// Print the answer followed by its certificate, or check the given certificate.
+pub fn run(args: &CertifyArgs) -> Result {
+    if let Some(path) = &args.check {
+        return check(args, path);
+    }
+    let argument = symbols::Argument::new(args.argument.as_str(), false);
+    let (accepted, certificate) = match (args.semantics, args.skeptical) {
+        (CliSemantics::Ad, false) => credulous::<semantics::Admissible>(args, &argument)?,
+        (CliSemantics::Co, false) => credulous::<semantics::Complete>(args, &argument)?,
+        (CliSemantics::Co, true) => grounded::<semantics::Complete>(args, &argument)?,
+        (CliSemantics::Gr, _) => grounded::<semantics::Ground>(args, &argument)?,
+        (CliSemantics::Ad | CliSemantics::St, _) => return Err(lib::Error::Logic(String::from(
+            "Certificates exist for credulous admissible, complete and grounded acceptance only",
+        ))
+        .into()),
+    };
+    println!("{}", if accepted { "YES" } else { "NO" });
+    match certificate {
+        Some(certificate) => println!("{}", to_json(&certificate)),
+        None => println!("// No certificate, {} is undecided", argument.id),
+    }
+    Ok(())
+}
+
+fn load<S: ArgumentationFrameworkSemantic>(
+    args: &CertifyArgs,
+) -> Result<ArgumentationFramework<S>> {
+    let content = std::fs::read_to_string(&args.file)?;
+    let af =
+        ArgumentationFramework::<S>::new_with_config(&content, args.solver.framework_config())?;
+    Ok(af)
+}
+
+fn credulous<S: ArgumentationFrameworkSemantic>(
+    args: &CertifyArgs,
+    argument: &symbols::Argument,
+) -> Result<(bool, Option<Certificate>)> {
+    let certificate = load::<S>(args)?.certify_credulous(argument)?;
+    let accepted = matches!(certificate, Some(Certificate::Defended { .. }));
+    Ok((accepted, certificate))
+}
+
+fn grounded<S: ArgumentationFrameworkSemantic>(
+    args: &CertifyArgs,
+    argument: &symbols::Argument,
+) -> Result<(bool, Option<Certificate>)> {
+    let certificate = load::<S>(args)?.certify_grounded(argument)?;
+    Ok((certificate.label() == Some(Label::In), Some(certificate)))
+}
+
+/// Validate the certificate against the framework as loaded, without optional elements.
+fn check(args: &CertifyArgs, path: &std::path::Path) -> Result {
+    let content = std::fs::read_to_string(&args.file)?;
+    let (mut arguments, mut attacks) = parse_initial_file(&content)?;
+    arguments.retain(|arg| !arg.optional);
+    attacks.retain(|attack| !attack.optional);
+    let value: Value = serde_json::from_str(&std::fs::read_to_string(path)?)
+        .map_err(|why| Error::Certificate(why.to_string()))?;
+    let certificate = from_json(&value).map_err(Error::Certificate)?;
+    certificate
+        .validate(&arguments, &attacks)
+        .map_err(Error::Certificate)?;
+    match certificate.label() {
+        None => println!("// {} is credulously accepted", certificate.argument()),
+        Some(label) => println!(
+            "// {} is labelled {label:?} by the grounded labelling",
+            certificate.argument()
+        ),
+    }
+    Ok(())
+}
+
+fn to_json(certificate: &Certificate) -> Value {
+    match certificate {
+        Certificate::Defended {
+            argument,
+            extension,
+            defenses,
+        } => {
+            let defenses: Vec<_> = defenses
+                .iter()
+                .map(
+                    |defense| json!({ "attacker": defense.attacker, "defender": defense.defender }),
+                )
+                .collect();
+            json!({ "argument": argument, "extension": extension, "defenses": defenses })
+        }
+        Certificate::Labelling { argument, labels } => {
+            let labels: serde_json::Map<_, _> = labels
+                .iter()
+                .map(|(id, label)| {
+                    let label = match label {
+                        Label::In => "in",
+                        Label::Out => "out",
+                        Label::Undec => "undec",
+                    };
+                    (id.clone(), Value::from(label))
+                })
+                .collect();
+            json!({ "argument": argument, "labelling": labels })
+        }
+    }
+}
+
+fn from_json(value: &Value) -> Result<Certificate, String> {
+    let string = |value: &Value, what: &str| {
+        value
+            .as_str()
+            .map(str::to_owned)
+            .ok_or_else(|| format!("{what} is not a string"))
+    };
+    let argument = string(&value["argument"], "argument")?;
+    if let Some(labelling) = value.get("labelling") {
+        let labels = labelling
+            .as_object()
+            .ok_or("labelling is not an object")?
+            .iter()
+            .map(|(id, label)| {
+                let label = match label.as_str() {
+                    Some("in") => Label::In,
+                    Some("out") => Label::Out,
+                    Some("undec") => Label::Undec,
+                    _ => return Err(format!("label of {id} is none of in, out and undec")),
+                };
+                Ok((id.clone(), label))
+            })
+            .collect::<Result<_, String>>()?;
+        return Ok(Certificate::Labelling { argument, labels });
+    }
+    let extension = value["extension"]
+        .as_array()
+        .ok_or("extension is not an array")?
+        .iter()
+        .map(|id| string(id, "extension member"))
+        .collect::<Result<_, _>>()?;
+    let defenses = value["defenses"]
+        .as_array()
+        .ok_or("defenses is not an array")?
+        .iter()
+        .map(|defense| {
+            Ok(Defense {
+                attacker: string(&defense["attacker"], "attacker")?,
+                defender: string(&defense["defender"], "defender")?,
+            })
+        })
+        .collect::<Result<_, String>>()?;
+    Ok(Certificate::Defended {
+        argument,
+        extension,
+        defenses,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn certificates_survive_json() {
+        let (arguments, attacks) =
+            parse_initial_file("arg(a). arg(b). arg(c). att(a,b). att(b,c).").unwrap();
+        let labelling = Certificate::grounded(&arguments, &attacks, "b").unwrap();
+        let defended = Certificate::Defended {
+            argument: String::from("c"),
+            extension: vec![String::from("a"), String::from("c")],
+            defenses: vec![Defense {
+                attacker: String::from("b"),
+                defender: String::from("a"),
+            }],
+        };
+        for certificate in [labelling, defended] {
+            let parsed = from_json(&to_json(&certificate)).unwrap();
+            assert_eq!(parsed, certificate);
+            assert_eq!(parsed.validate(&arguments, &attacks), Ok(()));
+        }
+    }
+}
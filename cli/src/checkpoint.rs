@@ -0,0 +1,155 @@
+//! Persisting the progress of long dynamic runs
+use std::{
+    collections::BTreeMap,
+    fs,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+};
+
+use lib::argumentation_framework::Patch;
+
+use crate::{Error, Result};
+
+/// First line of every checkpoint file.
+const HEADER: &str = "// dasp checkpoint";
+
+/// Progress of a dynamic run.
+///
+/// Stores the number of applied update lines and the latest patch for every
+/// argument and attack touched so far, which is enough to restore the framework
+/// without replaying the updates.
+#[derive(Debug)]
+pub struct Checkpoint {
+    path: PathBuf,
+    every: NonZeroUsize,
+    /// Number of update lines applied so far
+    applied: usize,
+    /// Latest patch, keyed by the patched argument or attack
+    state: BTreeMap<String, Patch>,
+}
+
+impl Checkpoint {
+    /// Load the checkpoint at `path` or start a fresh one if there is none.
+    ///
+    /// The checkpoint is written to disk after every `every` recorded updates.
+    pub fn load_or_new(path: &Path, every: NonZeroUsize) -> Result<Self> {
+        let mut checkpoint = Checkpoint {
+            path: path.to_owned(),
+            every,
+            applied: 0,
+            state: BTreeMap::new(),
+        };
+        if !path.exists() {
+            return Ok(checkpoint);
+        }
+        let content = fs::read_to_string(path)?;
+        let mut lines = content.lines();
+        if lines.next() != Some(HEADER) {
+            return Err(Error::Checkpoint(String::from("Missing header")));
+        }
+        checkpoint.applied = lines
+            .next()
+            .and_then(|line| line.strip_prefix("applied "))
+            .and_then(|applied| applied.parse().ok())
+            .ok_or_else(|| Error::Checkpoint(String::from("Missing number of applied updates")))?;
+        for line in lines {
+            Patch::parse_line(line)?
+                .into_iter()
+                .for_each(|patch| checkpoint.insert(patch));
+        }
+        log::info!(
+            "Resuming from checkpoint {} after {} updates",
+            path.display(),
+            checkpoint.applied
+        );
+        Ok(checkpoint)
+    }
+
+    /// Number of update lines applied so far.
+    pub fn applied(&self) -> usize {
+        self.applied
+    }
+
+    /// Patches restoring the checkpointed framework state.
+    pub fn patches(&self) -> impl Iterator<Item = &Patch> {
+        self.state.values()
+    }
+
+    /// Record a completed update line, saving the checkpoint if it is due.
+    pub fn record(&mut self, update: &str) -> Result {
+        Patch::parse_line(update)?
+            .into_iter()
+            .for_each(|patch| self.insert(patch));
+        self.advance()
+    }
+
+    /// Record a skipped update line, saving the checkpoint if it is due.
+    pub fn skip(&mut self) -> Result {
+        self.advance()
+    }
+
+    fn advance(&mut self) -> Result {
+        self.applied += 1;
+        if self.applied % self.every == 0 {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// Remove the checkpoint once the run is complete.
+    pub fn remove(self) -> Result {
+        if self.path.exists() {
+            fs::remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+
+    fn insert(&mut self, patch: Patch) {
+        let key = match &patch {
+            Patch::EnableArgument(argument) | Patch::DisableArgument(argument) => {
+                argument.to_string()
+            }
+            Patch::EnableAttack(attack) | Patch::DisableAttack(attack) => attack.to_string(),
+        };
+        self.state.insert(key, patch);
+    }
+
+    /// Write the checkpoint, replacing the previous one atomically.
+    fn save(&self) -> Result {
+        let mut content = format!("{HEADER}\napplied {}\n", self.applied);
+        for patch in self.state.values() {
+            content += &format!("{patch}\n");
+        }
+        let temporary = self.path.with_extension("tmp");
+        fs::write(&temporary, content)?;
+        fs::rename(&temporary, &self.path)?;
+        log::debug!("Saved checkpoint after {} updates", self.applied);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkpoints_keep_the_latest_patch_and_survive_reloading() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run.checkpoint");
+        let every = NonZeroUsize::new(2).unwrap();
+        let mut checkpoint = Checkpoint::load_or_new(&path, every).unwrap();
+        checkpoint.record("+arg(c).").unwrap();
+        assert!(!path.exists());
+        checkpoint.record("-arg(c).").unwrap();
+        checkpoint.record("+att(a,b).").unwrap();
+
+        let restored = Checkpoint::load_or_new(&path, every).unwrap();
+        assert_eq!(restored.applied(), 2);
+        assert_eq!(
+            restored.patches().map(Patch::to_string).collect::<Vec<_>>(),
+            vec!["-arg(c)."]
+        );
+        restored.remove().unwrap();
+        assert!(!path.exists());
+    }
+}
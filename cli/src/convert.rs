@@ -0,0 +1,22 @@
+//! Convert frameworks between file formats
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+};
+
+use lib::argumentation_framework::parse_initial_file;
+
+use crate::{args::ConvertArgs, output::write_framework, Result};
+
+/// Convert the requested file, writing the result to the output file or stdout.
+pub fn run(args: &ConvertArgs) -> Result {
+    let content = std::fs::read_to_string(&args.file)?;
+    let (arguments, attacks) = parse_initial_file(&content)?;
+    let mut out: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(std::io::stdout()),
+    };
+    write_framework(&mut out, args.to, &arguments, &attacks)?;
+    out.flush()?;
+    Ok(())
+}
@@ -0,0 +1,130 @@
+//! Compare the extensions of two frameworks
+use std::{
+    collections::{BTreeSet, HashSet},
+    path::Path,
+};
+
+use fallible_iterator::FallibleIterator;
+use lib::{
+    argumentation_framework::{
+        parse_initial_file, semantics::ArgumentationFrameworkSemantic, ArgumentationFramework,
+        Extension,
+    },
+    semantics, Framework, GenericExtension,
+};
+
+use crate::{
+    args::{CliSemantics, DiffArgs, SolverArgs},
+    Result,
+};
+
+/// Print the extensions unique to either framework and all arguments whose acceptance differs.
+pub fn run(args: &DiffArgs) -> Result {
+    match args.semantics {
+        CliSemantics::Ad => diff::<semantics::Admissible>(args),
+        CliSemantics::Co => diff::<semantics::Complete>(args),
+        CliSemantics::Gr => diff::<semantics::Ground>(args),
+        CliSemantics::St => diff::<semantics::Stable>(args),
+    }
+}
+
+fn diff<S: ArgumentationFrameworkSemantic>(args: &DiffArgs) -> Result {
+    let (arguments_a, extensions_a) = enumerate::<S>(&args.file_a, &args.solver)?;
+    let (arguments_b, extensions_b) = enumerate::<S>(&args.file_b, &args.solver)?;
+    println!("// Only in {}", args.file_a.display());
+    for ext in extensions_a.difference(&extensions_b) {
+        println!("{}", ext.format());
+    }
+    println!("// Only in {}", args.file_b.display());
+    for ext in extensions_b.difference(&extensions_a) {
+        println!("{}", ext.format());
+    }
+    println!("// Acceptance differs");
+    for argument in arguments_a.union(&arguments_b).collect::<BTreeSet<_>>() {
+        let a = Acceptance::of(argument, &extensions_a);
+        let b = Acceptance::of(argument, &extensions_b);
+        if a != b {
+            println!(
+                "{argument}: credulous {} -> {}, skeptical {} -> {}",
+                yes_no(a.credulous),
+                yes_no(b.credulous),
+                yes_no(a.skeptical),
+                yes_no(b.skeptical)
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Acceptance status of a single argument.
+#[derive(Debug, PartialEq, Eq)]
+struct Acceptance {
+    credulous: bool,
+    skeptical: bool,
+}
+
+impl Acceptance {
+    fn of(id: &str, extensions: &BTreeSet<Extension>) -> Self {
+        let contains = |ext: &Extension| ext.arguments().any(|arg| arg.id == id);
+        Acceptance {
+            credulous: extensions.iter().any(contains),
+            skeptical: extensions.iter().all(contains),
+        }
+    }
+}
+
+/// All argument names and extensions of the framework in `file`.
+fn enumerate<S: ArgumentationFrameworkSemantic>(
+    file: &Path,
+    solver: &SolverArgs,
+) -> Result<(HashSet<String>, BTreeSet<Extension>)> {
+    let content = std::fs::read_to_string(file)?;
+    let (arguments, _) = parse_initial_file(&content)?;
+    let mut af = ArgumentationFramework::<S>::new_with_config(&content, solver.framework_config())?;
+    let extensions = af.enumerate_extensions()?.by_ref().collect()?;
+    Ok((
+        arguments.into_iter().map(|arg| arg.id).collect(),
+        extensions,
+    ))
+}
+
+const fn yes_no(value: bool) -> &'static str {
+    if value {
+        "YES"
+    } else {
+        "NO"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn acceptance_is_derived_from_extensions() {
+        let ext = |ids: &[&str]| -> Extension { ids.iter().map(|id| id.to_string()).collect() };
+        let extensions = BTreeSet::from([ext(&["a"]), ext(&["a", "b"])]);
+        let acceptance = |id| Acceptance::of(id, &extensions);
+        assert_eq!(
+            acceptance("a"),
+            Acceptance {
+                credulous: true,
+                skeptical: true
+            }
+        );
+        assert_eq!(
+            acceptance("b"),
+            Acceptance {
+                credulous: true,
+                skeptical: false
+            }
+        );
+        assert_eq!(
+            acceptance("c"),
+            Acceptance {
+                credulous: false,
+                skeptical: false
+            }
+        );
+    }
+}
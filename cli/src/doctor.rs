@@ -0,0 +1,151 @@
+//! Check the installation by solving built-in instances
+use std::collections::BTreeSet;
+
+use fallible_iterator::FallibleIterator;
+use lib::{
+    argumentation_framework::{
+        clingo_version, semantics::ArgumentationFrameworkSemantic, ArgumentationFramework,
+    },
+    semantics, Framework,
+};
+
+use crate::{args::CliSemantics, Error, Result};
+
+/// Oldest clingo version supported by the bindings.
+const MIN_CLINGO_VERSION: (i32, i32, i32) = (5, 5, 0);
+
+/// Built-in instance with its known extensions.
+struct Check {
+    name: &'static str,
+    semantics: CliSemantics,
+    framework: &'static str,
+    /// Update lines applied before solving
+    updates: &'static [&'static str],
+    expected: &'static [&'static [&'static str]],
+}
+
+/// Instances for every semantics whose encoding is complete.
+const CHECKS: &[Check] = &[
+    Check {
+        name: "empty framework",
+        semantics: CliSemantics::Ad,
+        framework: "",
+        updates: &[],
+        expected: &[&[]],
+    },
+    Check {
+        name: "unattacked arguments",
+        semantics: CliSemantics::Ad,
+        framework: "arg(a). arg(b).",
+        updates: &[],
+        expected: &[&[], &["a"], &["b"], &["a", "b"]],
+    },
+    Check {
+        name: "chain",
+        semantics: CliSemantics::Ad,
+        framework: "arg(a). arg(b). arg(c). att(a,b). att(b,c).",
+        updates: &[],
+        expected: &[&[], &["a"], &["a", "c"]],
+    },
+    Check {
+        name: "mutual attack",
+        semantics: CliSemantics::Ad,
+        framework: "arg(a). arg(b). att(a,b). att(b,a).",
+        updates: &[],
+        expected: &[&[], &["a"], &["b"]],
+    },
+    Check {
+        name: "self attack",
+        semantics: CliSemantics::Ad,
+        framework: "arg(a). att(a,a).",
+        updates: &[],
+        expected: &[&[]],
+    },
+    Check {
+        name: "optional elements after updates",
+        semantics: CliSemantics::Ad,
+        framework: "arg(a). arg(b). arg(c). att(a,b). att(c,a). opt(arg(c)). opt(att(c,a)).",
+        updates: &["+arg(c).", "+att(c,a)."],
+        expected: &[&[], &["c"], &["b", "c"]],
+    },
+];
+
+/// Report the clingo version and the result of every check.
+pub fn run() -> Result {
+    let mut failed = 0;
+    let (major, minor, revision) = clingo_version();
+    if (major, minor, revision) >= MIN_CLINGO_VERSION {
+        println!("ok    clingo {major}.{minor}.{revision}");
+    } else {
+        let (min_major, min_minor, min_revision) = MIN_CLINGO_VERSION;
+        println!(
+            "FAIL  clingo {major}.{minor}.{revision} is older than {min_major}.{min_minor}.{min_revision}"
+        );
+        failed += 1;
+    }
+    for check in CHECKS {
+        let label = format!("{:?} {}", check.semantics, check.name).to_lowercase();
+        match check.run() {
+            Ok(()) => println!("ok    {label}"),
+            Err(why) => {
+                println!("FAIL  {label}: {why}");
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(Error::Doctor(failed));
+    }
+    println!("All {} checks passed", CHECKS.len() + 1);
+    Ok(())
+}
+
+type ExtensionSet = BTreeSet<BTreeSet<String>>;
+
+impl Check {
+    /// Solve the instance, describing the difference if the extensions are not the expected ones.
+    fn run(&self) -> Result<(), String> {
+        let found = match self.semantics {
+            CliSemantics::Ad => self.extensions::<semantics::Admissible>(),
+            CliSemantics::Co => self.extensions::<semantics::Complete>(),
+            CliSemantics::Gr => self.extensions::<semantics::Ground>(),
+            CliSemantics::St => self.extensions::<semantics::Stable>(),
+        }
+        .map_err(|why| why.to_string())?;
+        let expected: ExtensionSet = self
+            .expected
+            .iter()
+            .map(|ext| ext.iter().map(|id| id.to_string()).collect())
+            .collect();
+        if found == expected {
+            Ok(())
+        } else {
+            Err(format!("expected {expected:?}, found {found:?}"))
+        }
+    }
+
+    fn extensions<S: ArgumentationFrameworkSemantic>(&self) -> Result<ExtensionSet> {
+        let mut af = ArgumentationFramework::<S>::new(self.framework)?;
+        for update in self.updates {
+            af.update(update)?;
+        }
+        let extensions = af
+            .enumerate_extensions()?
+            .by_ref()
+            .map(|ext| Ok(ext.arguments().map(|arg| arg.id.clone()).collect()))
+            .collect()?;
+        Ok(extensions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_in_checks_pass() {
+        for check in CHECKS {
+            assert_eq!(check.run(), Ok(()), "{}", check.name);
+        }
+    }
+}
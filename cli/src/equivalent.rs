@@ -0,0 +1,83 @@
+//! Check the equivalence of two frameworks
+use std::path::Path;
+
+use lib::{
+    argumentation_framework::{
+        equivalence::{self, Kernel, Side, Witness},
+        parse_initial_file,
+        semantics::ArgumentationFrameworkSemantic,
+        symbols,
+    },
+    semantics, GenericExtension,
+};
+
+use crate::{
+    args::{CliSemantics, EquivalentArgs},
+    Result,
+};
+
+/// Print `YES` if the frameworks are equivalent, otherwise `NO` and the witness.
+pub fn run(args: &EquivalentArgs) -> Result {
+    let first = load(&args.file_a)?;
+    let second = load(&args.file_b)?;
+    let (first, second) = ((&first.0[..], &first.1[..]), (&second.0[..], &second.1[..]));
+    let witness = if args.strong {
+        let kernel = match args.semantics {
+            CliSemantics::Ad => Kernel::Admissible,
+            CliSemantics::Co => Kernel::Complete,
+            CliSemantics::Gr => Kernel::Grounded,
+            CliSemantics::St => Kernel::Stable,
+        };
+        equivalence::strong(first, second, kernel)
+    } else {
+        match args.semantics {
+            CliSemantics::Ad => standard::<semantics::Admissible>(first, second, args)?,
+            CliSemantics::Co => standard::<semantics::Complete>(first, second, args)?,
+            CliSemantics::Gr => standard::<semantics::Ground>(first, second, args)?,
+            CliSemantics::St => standard::<semantics::Stable>(first, second, args)?,
+        }
+    };
+    let Some(witness) = witness else {
+        println!("YES");
+        return Ok(());
+    };
+    println!("NO");
+    let file = |side| match side {
+        Side::First => args.file_a.display(),
+        Side::Second => args.file_b.display(),
+    };
+    match witness {
+        Witness::Extension { extension, only_in } => {
+            println!(
+                "// Extension only in {}: {}",
+                file(only_in),
+                extension.format()
+            )
+        }
+        Witness::Argument { id, only_in } => {
+            println!("// Argument only in {}: {id}", file(only_in))
+        }
+        Witness::Attack { attack, only_in } => {
+            println!("// Kernel attack only in {}: {attack}", file(only_in))
+        }
+    }
+    Ok(())
+}
+
+type Elements = (Vec<symbols::Argument>, Vec<symbols::Attack>);
+
+fn load(file: &Path) -> Result<Elements> {
+    Ok(parse_initial_file(&std::fs::read_to_string(file)?)?)
+}
+
+fn standard<S: ArgumentationFrameworkSemantic>(
+    first: (&[symbols::Argument], &[symbols::Attack]),
+    second: (&[symbols::Argument], &[symbols::Attack]),
+    args: &EquivalentArgs,
+) -> Result<Option<Witness>> {
+    Ok(equivalence::standard::<S>(
+        first,
+        second,
+        &args.solver.framework_config(),
+    )?)
+}
@@ -0,0 +1,57 @@
+//! Explain the rejection of an argument
+use fallible_iterator::FallibleIterator;
+use lib::{
+    argumentation_framework::{
+        semantics::ArgumentationFrameworkSemantic, symbols, ArgumentationFramework,
+    },
+    semantics, Framework,
+};
+
+use crate::{
+    args::{CliSemantics, ExplainArgs},
+    Result,
+};
+
+/// Print the update lines removing the fewest enabled optional attacks that make the
+/// argument credulously accepted.
+pub fn run(args: &ExplainArgs) -> Result {
+    match args.semantics {
+        CliSemantics::Ad => explain::<semantics::Admissible>(args),
+        CliSemantics::Co => explain::<semantics::Complete>(args),
+        CliSemantics::Gr => explain::<semantics::Ground>(args),
+        CliSemantics::St => explain::<semantics::Stable>(args),
+    }
+}
+
+fn explain<S: ArgumentationFrameworkSemantic>(args: &ExplainArgs) -> Result {
+    let content = std::fs::read_to_string(&args.file)?;
+    let mut af =
+        ArgumentationFramework::<S>::new_with_config(&content, args.solver.framework_config())?;
+    if let Some(update_file) = &args.update_file {
+        let updates: Vec<String> = update_file.lines(false)?.collect()?;
+        let updates: Vec<&str> = updates.iter().map(String::as_str).collect();
+        af.update_many(&updates)?;
+    }
+    let argument = symbols::Argument::new(args.argument.as_str(), false);
+    let diagnoses = af.explain_rejection(&argument)?;
+    match diagnoses.first().map(|diagnosis| diagnosis.update_line()) {
+        None => println!(
+            "// {} stays rejected when removing all enabled optional attacks",
+            argument.id
+        ),
+        Some(None) => println!("// {} is credulously accepted", argument.id),
+        Some(Some(_)) => {
+            println!(
+                "// {} is accepted after any of the following updates",
+                argument.id
+            );
+            for line in diagnoses
+                .iter()
+                .filter_map(|diagnosis| diagnosis.update_line())
+            {
+                println!("{line}");
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,263 @@
+//! Interactive terminal explorer stepping through the updates of a framework
+use std::{
+    collections::HashSet,
+    io::{self, Stdout},
+};
+
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use driver::PathOrStdin;
+use fallible_iterator::FallibleIterator;
+use lib::{
+    argumentation_framework::{
+        semantics::ArgumentationFrameworkSemantic, symbols, ArgumentationFramework, Extension,
+        Patch,
+    },
+    semantics, Framework, GenericExtension,
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame, Terminal,
+};
+
+use crate::{
+    args::{CliSemantics, ExploreArgs},
+    Result,
+};
+
+const HELP: &str = "q: quit | n: apply next update | up/down: select extension";
+
+pub fn run(args: &ExploreArgs) -> Result {
+    match args.semantics {
+        CliSemantics::Ad => explore::<semantics::Admissible>(args),
+        CliSemantics::Co => explore::<semantics::Complete>(args),
+        CliSemantics::Gr => explore::<semantics::Ground>(args),
+        CliSemantics::St => explore::<semantics::Stable>(args),
+    }
+}
+
+fn explore<S: ArgumentationFrameworkSemantic>(args: &ExploreArgs) -> Result {
+    let content = std::fs::read_to_string(&args.file)?;
+    let af =
+        ArgumentationFramework::<S>::new_with_config(&content, args.solver.framework_config())?;
+    let updates = match &args.update_file {
+        Some(path) => PathOrStdin::Path(path.clone()).lines(false)?.collect()?,
+        None => vec![],
+    };
+    let mut explorer = Explorer::new(af, updates)?;
+    let mut terminal = TerminalGuard::enter()?;
+    loop {
+        terminal.0.draw(|frame| explorer.draw(frame))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Char('n') | KeyCode::Right | KeyCode::Enter => explorer.step()?,
+            KeyCode::Down | KeyCode::Char('j') => explorer.select(1),
+            KeyCode::Up | KeyCode::Char('k') => explorer.select(-1),
+            _ => {}
+        }
+    }
+}
+
+/// Terminal in raw mode on the alternate screen, restored when dropped.
+struct TerminalGuard(Terminal<CrosstermBackend<Stdout>>);
+
+impl TerminalGuard {
+    fn enter() -> Result<Self> {
+        enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen)?;
+        Ok(TerminalGuard(Terminal::new(CrosstermBackend::new(
+            io::stdout(),
+        ))?))
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        disable_raw_mode().ok();
+        execute!(self.0.backend_mut(), LeaveAlternateScreen).ok();
+        self.0.show_cursor().ok();
+    }
+}
+
+/// State of the explorer, independent of the terminal.
+struct Explorer<S: ArgumentationFrameworkSemantic> {
+    af: ArgumentationFramework<S>,
+    updates: Vec<String>,
+    /// Number of updates stepped over, including failed ones
+    applied: usize,
+    /// Optional arguments enabled by the applied updates
+    enabled: HashSet<String>,
+    extensions: Vec<Extension>,
+    selected: ListState,
+    /// Outcome of the last step
+    status: Option<String>,
+}
+
+impl<S: ArgumentationFrameworkSemantic> Explorer<S> {
+    fn new(af: ArgumentationFramework<S>, updates: Vec<String>) -> Result<Self> {
+        let mut explorer = Explorer {
+            af,
+            updates,
+            applied: 0,
+            enabled: HashSet::new(),
+            extensions: vec![],
+            selected: ListState::default(),
+            status: None,
+        };
+        explorer.solve()?;
+        Ok(explorer)
+    }
+
+    /// Enumerate the extensions of the current framework.
+    fn solve(&mut self) -> Result {
+        self.extensions = self.af.enumerate_extensions()?.by_ref().collect()?;
+        self.extensions.sort();
+        self.selected
+            .select((!self.extensions.is_empty()).then_some(0));
+        Ok(())
+    }
+
+    /// Apply the next update, if any. Updates that cannot be applied are skipped.
+    fn step(&mut self) -> Result {
+        let Some(update) = self.updates.get(self.applied) else {
+            self.status = Some(String::from("No updates left"));
+            return Ok(());
+        };
+        let nr = self.applied;
+        self.applied += 1;
+        if let Err(why) = self.af.update(update) {
+            self.status = Some(format!("Skipped update #{nr}: {why}"));
+            return Ok(());
+        }
+        for patch in Patch::parse_line(update)? {
+            match patch {
+                Patch::EnableArgument(argument) => self.enabled.insert(argument.id),
+                Patch::DisableArgument(argument) => self.enabled.remove(&argument.id),
+                Patch::EnableAttack(_) | Patch::DisableAttack(_) => continue,
+            };
+        }
+        self.status = Some(format!("Applied update #{nr}"));
+        self.solve()
+    }
+
+    /// Move the selection by `offset` extensions, staying within the list.
+    fn select(&mut self, offset: isize) {
+        if let Some(selected) = self.selected.selected() {
+            let last = self.extensions.len() - 1;
+            self.selected
+                .select(Some(selected.saturating_add_signed(offset).min(last)));
+        }
+    }
+
+    fn is_active(&self, argument: &symbols::Argument) -> bool {
+        !argument.optional || self.enabled.contains(&argument.id)
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, status] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.size());
+        let [arguments, updates, extensions] = Layout::horizontal([
+            Constraint::Percentage(25),
+            Constraint::Percentage(35),
+            Constraint::Percentage(40),
+        ])
+        .areas(main);
+        frame.render_widget(self.arguments_list(), arguments);
+        frame.render_widget(self.updates_list(), updates);
+        let list = List::new(
+            self.extensions
+                .iter()
+                .map(|ext| ListItem::new(ext.format())),
+        )
+        .block(titled(format!("Extensions ({})", self.extensions.len())))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+        frame.render_stateful_widget(list, extensions, &mut self.selected);
+        let status_line = self.status.as_deref().unwrap_or(HELP);
+        frame.render_widget(Paragraph::new(status_line), status);
+    }
+
+    /// Arguments colored by acceptance, those in the selected extension in bold.
+    fn arguments_list(&self) -> List<'static> {
+        let selected = self
+            .selected
+            .selected()
+            .and_then(|idx| self.extensions.get(idx));
+        let items = self.af.arguments().iter().map(|argument| {
+            let mut style = Style::default();
+            if !self.is_active(argument) {
+                style = style.fg(Color::DarkGray);
+            } else if self.extensions.iter().all(|ext| ext.contains(argument)) {
+                style = style.fg(Color::Green);
+            } else if self.extensions.iter().any(|ext| ext.contains(argument)) {
+                style = style.fg(Color::Yellow);
+            } else {
+                style = style.fg(Color::Red);
+            }
+            if selected.is_some_and(|ext| ext.contains(argument)) {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            ListItem::new(Span::styled(argument.id.clone(), style))
+        });
+        List::new(items).block(titled(String::from(
+            "Arguments (green: skeptical, yellow: credulous)",
+        )))
+    }
+
+    /// Applied updates and the ones still to come, the next one highlighted.
+    fn updates_list(&self) -> List<'static> {
+        let items = self.updates.iter().enumerate().map(|(nr, update)| {
+            let style = match nr.cmp(&self.applied) {
+                std::cmp::Ordering::Less => Style::default(),
+                std::cmp::Ordering::Equal => Style::default().fg(Color::Cyan),
+                std::cmp::Ordering::Greater => Style::default().fg(Color::DarkGray),
+            };
+            ListItem::new(Line::styled(format!("#{nr} {update}"), style))
+        });
+        List::new(items).block(titled(format!(
+            "Updates ({}/{})",
+            self.applied,
+            self.updates.len()
+        )))
+    }
+}
+
+fn titled(title: String) -> Block<'static> {
+    Block::default().title(title).borders(Borders::ALL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stepping_applies_updates_in_order() {
+        let af =
+            ArgumentationFramework::<semantics::Stable>::new("a\nb\nc?\n#\na b\nc a\n").unwrap();
+        let updates = vec![String::from("+arg(c)."), String::from("-arg(d).")];
+        let mut explorer = Explorer::new(af, updates).unwrap();
+        assert_eq!(explorer.extensions.len(), 1);
+        explorer.step().unwrap();
+        assert!(explorer.enabled.contains("c"));
+        assert_eq!(explorer.extensions[0].format(), "[b,c]");
+        explorer.step().unwrap();
+        assert_eq!(explorer.applied, 2);
+        assert!(explorer
+            .status
+            .as_deref()
+            .is_some_and(|status| status.starts_with("Skipped update #1")));
+    }
+}
@@ -0,0 +1,216 @@
+//! HTTP API exposing a framework as JSON
+//!
+//! Endpoints:
+//!
+//! - `PUT /af` loads the APX or TGF framework in the request body
+//! - `POST /updates` applies the APXM/TGFM update lines in the request body
+//! - `GET /extensions` lists all extensions
+//! - `GET /extensions/count` counts all extensions
+//! - `GET /acceptance/credulous/<ARG>` and `GET /acceptance/skeptical/<ARG>` decide acceptance,
+//!   with `<ARG>` percent-encoded
+//!
+//! Frameworks are solved under the semantics given by `--semantics`. Failing requests are answered by `{"error": <REASON>}` with a matching status code.
+use fallible_iterator::FallibleIterator;
+use lib::argumentation_framework::{
+    parse_initial_file,
+    registry::DynFramework,
+    symbols::Argument,
+    updates::{Follow, UpdateLines},
+};
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Response, Server};
+
+use crate::{args::HttpArgs, Error, Result};
+
+/// Framework of the semantics given on the command line.
+type HttpFramework = Box<dyn DynFramework>;
+
+/// Framework served by the API, if one has been loaded yet.
+struct State<'a> {
+    args: &'a HttpArgs,
+    af: Option<HttpFramework>,
+}
+
+/// Load the optional initial framework and answer requests forever.
+pub fn run(args: &HttpArgs) -> Result {
+    let mut state = State { args, af: None };
+    if let Some(file) = &args.file {
+        state.load(&std::fs::read_to_string(file)?)?;
+    }
+    let server = Server::http(&args.listen).map_err(|why| Error::Io(std::io::Error::other(why)))?;
+    log::info!("Listening on http://{}", args.listen);
+    let content_type =
+        Header::from_bytes("Content-Type", "application/json").expect("Valid header");
+    for mut request in server.incoming_requests() {
+        let mut body = String::new();
+        let (status, answer) = match request.as_reader().read_to_string(&mut body) {
+            Ok(_) => state.handle(request.method(), request.url(), &body),
+            Err(why) => (400, json!({ "error": why.to_string() })),
+        };
+        log::debug!("{} {} -> {status}", request.method(), request.url());
+        let response = Response::from_string(answer.to_string())
+            .with_status_code(status)
+            .with_header(content_type.clone());
+        if let Err(why) = request.respond(response) {
+            log::warn!("Failed to respond: {why}");
+        }
+    }
+    Ok(())
+}
+
+impl State<'_> {
+    fn load(&mut self, content: &str) -> Result<Value> {
+        let (arguments, attacks) = parse_initial_file(content)?;
+        self.af = Some(
+            self.args
+                .semantics
+                .id()
+                .framework(content, self.args.solver.framework_config())?,
+        );
+        log::info!("Successfully populated AF");
+        Ok(json!({ "arguments": arguments.len(), "attacks": attacks.len() }))
+    }
+
+    /// Route the request, returning the status code and the JSON answer.
+    fn handle(&mut self, method: &Method, url: &str, body: &str) -> (u16, Value) {
+        let path = url.trim_end_matches('/');
+        let answer = match (method, path) {
+            (Method::Put, "/af") => self.load(body),
+            (Method::Post, "/updates") => self.with_af(|af| {
+                let mut applied = 0;
+                let mut lines = UpdateLines::new(body.as_bytes(), Follow::No);
+                while let Some(line) = lines.next()? {
+                    af.update(&line)?;
+                    applied += 1;
+                }
+                Ok(json!({ "applied": applied }))
+            }),
+            (Method::Get, "/extensions") => self.with_af(|af| {
+                let extensions: Vec<Vec<_>> = af
+                    .extensions()?
+                    .iter()
+                    .map(|ext| ext.arguments().map(|arg| arg.id.clone()).collect())
+                    .collect();
+                Ok(json!({ "extensions": extensions }))
+            }),
+            (Method::Get, "/extensions/count") => {
+                self.with_af(|af| Ok(json!({ "count": af.count_extensions()? })))
+            }
+            (Method::Get, _) if path.starts_with("/acceptance/") => {
+                let Some((mode, encoded)) = path["/acceptance/".len()..].split_once('/') else {
+                    return not_found(path);
+                };
+                let Some(id) = percent_decode(encoded) else {
+                    let why = format!("Invalid percent-encoding in {encoded:?}");
+                    return (400, json!({ "error": why }));
+                };
+                let argument = Argument::new(id.as_str(), false);
+                let accepted = match mode {
+                    "credulous" => self.with_af(|af| Ok(af.is_credulous_accepted(&argument)?)),
+                    "skeptical" => self.with_af(|af| Ok(af.is_skeptical_accepted(&argument)?)),
+                    _ => return not_found(path),
+                };
+                accepted.map(|accepted| json!({ "argument": id, "accepted": accepted }))
+            }
+            _ => return not_found(path),
+        };
+        match answer {
+            Ok(answer) => (200, answer),
+            Err(why @ Error::NoFramework) => (409, json!({ "error": why.to_string() })),
+            Err(why) => (400, json!({ "error": why.to_string() })),
+        }
+    }
+
+    fn with_af<T>(&mut self, f: impl FnOnce(&mut HttpFramework) -> Result<T>) -> Result<T> {
+        match &mut self.af {
+            Some(af) => f(af),
+            None => Err(Error::NoFramework),
+        }
+    }
+}
+
+fn not_found(path: &str) -> (u16, Value) {
+    (404, json!({ "error": format!("Unknown endpoint {path}") }))
+}
+
+/// Decode the `%XX` escapes of a path segment, `None` if they are malformed or not UTF-8.
+fn percent_decode(segment: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(segment.len());
+    let mut rest = segment.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = tail
+                .get(..2)
+                .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))?;
+            bytes.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn requests_are_answered_with_json() {
+        let args = crate::args::Args::try_parse_from(["", "http"]).unwrap();
+        let crate::args::Command::Http(args) = args.command else {
+            unreachable!("Parsed http subcommand");
+        };
+        let mut state = State {
+            args: &args,
+            af: None,
+        };
+        assert_eq!(state.handle(&Method::Get, "/extensions/count", "").0, 409);
+        assert_eq!(
+            state.handle(&Method::Put, "/af", "a\nb?\n#\na b\n"),
+            (200, json!({ "arguments": 2, "attacks": 1 }))
+        );
+        assert_eq!(
+            state.handle(&Method::Get, "/extensions", ""),
+            (200, json!({ "extensions": [["a"], []] }))
+        );
+        assert_eq!(
+            state.handle(&Method::Post, "/updates", "+b\n"),
+            (200, json!({ "applied": 1 }))
+        );
+        assert_eq!(
+            state.handle(&Method::Get, "/acceptance/credulous/a", ""),
+            (200, json!({ "argument": "a", "accepted": true }))
+        );
+        assert_eq!(state.handle(&Method::Get, "/nope", "").0, 404);
+    }
+
+    #[test]
+    fn acceptance_is_decided_under_the_given_semantics() {
+        let args = crate::args::Args::try_parse_from(["", "http", "-s", "st"]).unwrap();
+        let crate::args::Command::Http(args) = args.command else {
+            unreachable!("Parsed http subcommand");
+        };
+        let mut state = State {
+            args: &args,
+            af: None,
+        };
+        state.handle(&Method::Put, "/af", "arg(a). arg(größe). att(a, größe).");
+        assert_eq!(
+            state.handle(&Method::Get, "/acceptance/skeptical/a", ""),
+            (200, json!({ "argument": "a", "accepted": true }))
+        );
+        assert_eq!(
+            state.handle(&Method::Get, "/acceptance/credulous/gr%C3%B6%C3%9Fe", ""),
+            (200, json!({ "argument": "größe", "accepted": false }))
+        );
+        assert_eq!(
+            state
+                .handle(&Method::Get, "/acceptance/credulous/%C3", "")
+                .0,
+            400
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Resource limits enforced by a watchdog thread and by clasp
+use std::{
+    io::Write,
+    num::NonZeroU64,
+    sync::OnceLock,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{args::SolveArgs, Error, Result};
+
+/// Exit code used when a resource limit has been exceeded.
+pub const LIMIT_EXIT_CODE: i32 = 3;
+
+/// Interval in which the watchdog checks the limits.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Time given to the solve loop to stop by itself once a limit is exceeded.
+///
+/// Only a single solve taking longer than this is cut short by the watchdog. Not given
+/// for the memory limit, as the process keeps allocating in the meantime.
+const GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+/// Limit found exceeded by the watchdog, if any.
+static EXCEEDED: OnceLock<Limit> = OnceLock::new();
+
+/// Resource limits for the whole solving process.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Limits {
+    /// Maximum wall-clock time.
+    pub max_time: Option<Duration>,
+    /// Maximum resident memory in MiB.
+    pub max_memory: Option<u64>,
+    /// Maximum number of conflicts per solve, enforced by clasp.
+    pub max_conflicts: Option<NonZeroU64>,
+}
+
+impl SolveArgs {
+    /// Resource limits requested on the command line.
+    pub fn limits(&self) -> Limits {
+        Limits {
+            max_time: self.max_time.map(Into::into),
+            max_memory: self.max_memory,
+            max_conflicts: self.max_conflicts,
+        }
+    }
+}
+
+/// Exceeded limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    Time,
+    Memory,
+    Conflicts,
+}
+
+impl Limits {
+    /// Spawn a watchdog thread enforcing the time and memory limits.
+    ///
+    /// Once a limit is exceeded, the solve loop stops before the next update, see
+    /// [`check`]. If it does not stop within [`GRACE_PERIOD`], e.g. as a single solve
+    /// takes too long, the watchdog stops the process, see [`exit`]. The memory limit
+    /// stops the process right away. Does nothing if no limit is set.
+    pub fn spawn_watchdog(self) -> std::io::Result<()> {
+        if self.max_time.is_none() && self.max_memory.is_none() {
+            return Ok(());
+        }
+        if self.max_memory.is_some() && resident_memory_mib().is_none() {
+            log::warn!("Memory usage cannot be determined on this platform, ignoring --max-memory");
+        }
+        let start = Instant::now();
+        thread::Builder::new()
+            .name(String::from("watchdog"))
+            .spawn(move || loop {
+                if let Some(limit) = self.exceeded(start.elapsed()) {
+                    log::warn!("Limit exceeded: {limit:?}, stopping");
+                    EXCEEDED.set(limit).ok();
+                    if limit != Limit::Memory {
+                        thread::sleep(GRACE_PERIOD);
+                        log::warn!("Solving did not stop in time, exiting");
+                    }
+                    exit(limit);
+                }
+                thread::sleep(POLL_INTERVAL);
+            })?;
+        Ok(())
+    }
+
+    fn exceeded(&self, elapsed: Duration) -> Option<Limit> {
+        if matches!(self.max_time, Some(max_time) if elapsed >= max_time) {
+            return Some(Limit::Time);
+        }
+        match (self.max_memory, resident_memory_mib()) {
+            (Some(max_memory), Some(used)) if used >= max_memory => Some(Limit::Memory),
+            _ => None,
+        }
+    }
+}
+
+impl Limit {
+    const fn as_str(&self) -> &'static str {
+        match self {
+            Limit::Time => "time",
+            Limit::Memory => "memory",
+            Limit::Conflicts => "conflicts",
+        }
+    }
+
+    /// The limit that stopped solving, if the error was caused by one.
+    pub fn causing(error: &Error) -> Option<Limit> {
+        match error {
+            Error::Limit(limit) => Some(*limit),
+            Error::Lib(lib::Error::LimitExceeded) => Some(Limit::Conflicts),
+            Error::InFile { source, .. } => Limit::causing(source),
+            _ => None,
+        }
+    }
+}
+
+/// Fail with [`Error::Limit`] once the watchdog found a limit exceeded.
+pub fn check() -> Result {
+    match EXCEEDED.get() {
+        Some(&limit) => Err(Error::Limit(limit)),
+        None => Ok(()),
+    }
+}
+
+/// Print `LIMIT <time|memory|conflicts>` to stdout and exit with [`LIMIT_EXIT_CODE`].
+///
+/// Called by `main` once the answers written so far have been flushed.
+pub fn exit(limit: Limit) -> ! {
+    let mut stdout = std::io::stdout().lock();
+    writeln!(stdout, "LIMIT {}", limit.as_str()).ok();
+    stdout.flush().ok();
+    std::process::exit(LIMIT_EXIT_CODE);
+}
+
+/// Resident memory of this process in MiB, if it can be determined.
+fn resident_memory_mib() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let kib = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kib / 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_limit_is_detected() {
+        let limits = Limits {
+            max_time: Some(Duration::from_secs(1)),
+            ..Default::default()
+        };
+        assert_eq!(limits.exceeded(Duration::from_millis(10)), None);
+        assert_eq!(limits.exceeded(Duration::from_secs(2)), Some(Limit::Time));
+    }
+
+    #[test]
+    fn limits_are_found_behind_the_file() {
+        let error = Error::in_file("updates", Some(3), lib::Error::LimitExceeded.into());
+        assert_eq!(Limit::causing(&error), Some(Limit::Conflicts));
+        assert_eq!(Limit::causing(&Error::Invalid(1)), None);
+    }
+}
@@ -0,0 +1,132 @@
+//! Apply one update stream to several frameworks in lockstep
+use std::io::Write;
+
+use driver::Problem;
+use fallible_iterator::FallibleIterator;
+use lib::{argumentation_framework::registry::DynFramework, GenericExtension};
+
+use crate::{
+    args::{CliSemantics, CliTask, LockstepArgs},
+    Result,
+};
+
+/// Framework of any semantics, answering the task after every update.
+type Instance = Box<dyn DynFramework>;
+
+/// Write the answer to the task, like `dasp solve` does.
+fn answer(instance: &mut Instance, task: CliTask, out: &mut impl Write) -> Result {
+    match task.problem() {
+        Problem::Count => writeln!(out, "{}", instance.count_extensions()?)?,
+        Problem::Enumerate => {
+            for extension in instance.extensions()? {
+                writeln!(out, "{}", extension.format())?;
+            }
+        }
+        Problem::Sample => match instance.sample_extension()? {
+            Some(extension) => writeln!(out, "{}", extension.format())?,
+            None => writeln!(out, "NO")?,
+        },
+        Problem::ExistsExtension => {
+            let exists = instance.has_extension()?;
+            writeln!(out, "{}", if exists { "YES" } else { "NO" })?
+        }
+        Problem::Optimal(objective) => match instance.optimal_extension(objective)? {
+            Some(extension) => writeln!(out, "{}", extension.format())?,
+            None => writeln!(out, "NO")?,
+        },
+        Problem::CredulousArguments => match instance.credulously_accepted_arguments()? {
+            Some(arguments) => writeln!(out, "{}", arguments.format())?,
+            None => writeln!(out, "NO")?,
+        },
+        Problem::SkepticalArguments => match instance.skeptically_accepted_arguments()? {
+            Some(arguments) => writeln!(out, "{}", arguments.format())?,
+            None => writeln!(out, "NO")?,
+        },
+        Problem::Credulous | Problem::Skeptical => {
+            return Err(lib::Error::Logic(String::from(
+                "Acceptance tasks are not supported in lockstep",
+            ))
+            .into())
+        }
+    }
+    Ok(())
+}
+
+/// Load every framework under every semantics, then answer the task for all of them
+/// after every update, grouped by update.
+pub fn run(args: &LockstepArgs) -> Result {
+    let mut instances = vec![];
+    for file in &args.files {
+        let content = std::fs::read_to_string(file)?;
+        for &semantics in &args.semantics {
+            let mut label = file.display().to_string();
+            if args.semantics.len() > 1 {
+                label += &format!(" ({semantics:?})").to_lowercase();
+            }
+            instances.push((label, load(&content, semantics, args)?));
+        }
+    }
+    log::info!("Loaded {} instances", instances.len());
+    let out = &mut std::io::stdout();
+    writeln!(out, "// Initial")?;
+    answer_all(&mut instances, args.task, out)?;
+    if !args.task.is_dynamic() {
+        return Ok(());
+    }
+    let mut updates = args.update_file.lines(false)?.enumerate();
+    while let Some((nr, update)) = updates.next()? {
+        for (label, instance) in &mut instances {
+            instance
+                .update(&update)
+                .inspect_err(|_| log::error!("Update #{nr} failed for {label}"))?;
+        }
+        writeln!(out, "// Update #{nr} -- {update}")?;
+        answer_all(&mut instances, args.task, out)?;
+    }
+    Ok(())
+}
+
+fn load(content: &str, semantics: CliSemantics, args: &LockstepArgs) -> Result<Instance> {
+    Ok(semantics
+        .id()
+        .framework(content, args.solver.framework_config())?)
+}
+
+fn answer_all(instances: &mut [(String, Instance)], task: CliTask, out: &mut impl Write) -> Result {
+    for (label, instance) in instances {
+        writeln!(out, "// {label}")?;
+        answer(instance, task, out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn instances_follow_the_same_updates() {
+        let args = crate::args::Args::try_parse_from(["", "lockstep", "af.apx", "-p", "ce-ad-d"]);
+        let crate::args::Command::Lockstep(args) = args.unwrap().command else {
+            unreachable!("Parsed lockstep subcommand");
+        };
+        let content = "arg(a). arg(b). att(a,b). opt(att(a,b)).";
+        let mut instances = vec![
+            (
+                "ad".to_owned(),
+                load(content, CliSemantics::Ad, &args).unwrap(),
+            ),
+            (
+                "st".to_owned(),
+                load(content, CliSemantics::St, &args).unwrap(),
+            ),
+        ];
+        let mut out = vec![];
+        for (_, instance) in &mut instances {
+            instance.update("+att(a,b).").unwrap();
+        }
+        answer_all(&mut instances, args.task, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "// ad\n2\n// st\n1\n");
+    }
+}
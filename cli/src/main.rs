@@ -1,18 +1,37 @@
 //! Main CLI for DASP
 mod args;
-mod path_or_stdin;
+mod bench;
+mod certify;
+mod checkpoint;
+mod convert;
+mod diff;
+mod doctor;
+mod equivalent;
+mod explain;
+#[cfg(feature = "tui")]
+mod explore;
+#[cfg(feature = "http")]
+mod http;
+mod limits;
+mod lockstep;
+#[cfg(unix)]
+mod mmap;
+mod output;
+mod record;
+mod report;
+#[cfg(unix)]
+mod serve;
+mod solve;
+mod stats;
+mod summary;
+mod validate;
+mod verify;
 
 use std::time::Instant;
 
-use args::ARGS;
-use fallible_iterator::FallibleIterator;
+use args::{Args, Command, ErrorFormat, ARGS};
+use clap::CommandFactory;
 use humantime::format_duration;
-use lib::{
-    argumentation_framework::{semantics::ArgumentationFrameworkSemantic, ArgumentationFramework},
-    semantics, Framework, GenericExtension,
-};
-
-use crate::args::CliTask;
 
 pub type Result<T = (), E = Error> = ::std::result::Result<T, E>;
 
@@ -22,11 +41,40 @@ pub enum Error {
     Lib(#[from] lib::Error),
     #[error("IO: {_0}")]
     Io(#[from] std::io::Error),
+    #[error("Found {_0} invalid line(s)")]
+    Invalid(usize),
+    #[error("Invalid checkpoint: {_0}")]
+    Checkpoint(String),
+    #[error("{_0} check(s) failed")]
+    Doctor(usize),
+    #[error("{_0} relation(s) between the semantics violated")]
+    Verify(usize),
+    #[error("Invalid certificate: {_0}")]
+    Certificate(String),
+    #[error("Replay failed: {_0}")]
+    Replay(String),
+    #[error("{_0:?} limit exceeded")]
+    Limit(limits::Limit),
+    #[error("{file}{}: {source}", line.map(|line| format!(":{line}")).unwrap_or_default())]
+    InFile {
+        file: String,
+        line: Option<usize>,
+        source: Box<Error>,
+    },
+    #[cfg(feature = "http")]
+    #[error("No framework loaded")]
+    NoFramework,
 }
 
-pub enum Dynamics {
-    No,
-    Yes,
+impl Error {
+    /// Attach the file, and the line if known, that caused the error.
+    pub fn in_file(file: impl std::fmt::Display, line: Option<usize>, source: Error) -> Self {
+        Error::InFile {
+            file: file.to_string(),
+            line,
+            source: Box::new(source),
+        }
+    }
 }
 
 fn main() -> Result {
@@ -34,91 +82,234 @@ fn main() -> Result {
 
     log::trace!("Parsed arguments: {:#?}", *ARGS);
 
-    let before = Instant::now();
-    let res = match ARGS.task {
-        CliTask::CeAd => run_task_count_extensions::<semantics::Admissible>(Dynamics::No),
-        CliTask::EeAd => run_task_enumerate_extensions::<semantics::Admissible>(Dynamics::No),
-        CliTask::SeAd => run_task_sample_extension::<semantics::Admissible>(Dynamics::No),
-        CliTask::CeAdD => run_task_count_extensions::<semantics::Admissible>(Dynamics::Yes),
-        CliTask::EeAdD => run_task_enumerate_extensions::<semantics::Admissible>(Dynamics::Yes),
-        CliTask::SeAdD => run_task_sample_extension::<semantics::Admissible>(Dynamics::Yes),
-    };
-    log::info!("Entire solving took {}", format_duration(before.elapsed()));
-    res
-}
-
-fn load_initial_file_into_af<S: ArgumentationFrameworkSemantic>(
-) -> Result<ArgumentationFramework<S>> {
-    let content = std::fs::read_to_string(&ARGS.file)?;
-    let af = ArgumentationFramework::new(&content)?;
-    log::info!("Successfully populated AF from initial file");
-    Ok(af)
-}
-
-fn run_task_count_extensions<S: ArgumentationFrameworkSemantic>(dynamics: Dynamics) -> Result {
-    let mut af = load_initial_file_into_af::<S>()?;
-    println!("// Initial count");
-    println!("{}", af.count_extensions()?);
-    if matches!(dynamics, Dynamics::Yes) {
-        let mut update_iter = ARGS.update_file.lines()?.enumerate();
-        while let Some((nr, update)) = update_iter.next()? {
-            af.update(&update)?;
-            println!("// Update #{nr} -- {update}");
-            println!("{}", af.count_extensions()?);
-        }
+    let res = run(&ARGS.command);
+    // All writers have been dropped, and thus flushed, once `run` returns
+    if let Some(limit) = res.as_ref().err().and_then(limits::Limit::causing) {
+        limits::exit(limit);
     }
-    Ok(())
-}
-
-fn run_task_enumerate_extensions<S: ArgumentationFrameworkSemantic>(dynamics: Dynamics) -> Result {
-    let mut af = load_initial_file_into_af::<S>()?;
-    println!("// Initial extensions");
-    af.enumerate_extensions()?.by_ref().for_each(|ext| {
-        println!("{}", ext.format());
-        Ok(())
-    })?;
-    if matches!(dynamics, Dynamics::Yes) {
-        let mut update_iter = ARGS.update_file.lines()?.enumerate();
-        while let Some((nr, update)) = update_iter.next()? {
-            af.update(&update)?;
-            println!("// Update #{nr} -- {update}");
-            af.enumerate_extensions()?.by_ref().for_each(|ext| {
-                println!("{}", ext.format());
-                Ok(())
-            })?;
-        }
+    if let (Err(why), ErrorFormat::Json) = (&res, ARGS.error_format) {
+        eprintln!("{}", why.to_json());
+        std::process::exit(1);
     }
-    Ok(())
+    res
 }
 
-fn run_task_sample_extension<P: ArgumentationFrameworkSemantic>(dynamics: Dynamics) -> Result {
-    let mut ctx = load_initial_file_into_af::<P>()?;
-    match ctx.sample_extension()? {
-        Some(ext) => println!("{}", ext.format()),
-        None => println!("NO"),
-    }
-    if matches!(dynamics, Dynamics::Yes) {
-        let mut update_iter = ARGS.update_file.lines()?;
-        while let Some(update) = update_iter.next()? {
-            ctx.update(&update)?;
-            match ctx.sample_extension()? {
-                Some(ext) => println!("{}", ext.format()),
-                None => println!("NO"),
+fn run(command: &Command) -> Result {
+    match command {
+        Command::Solve(args) => {
+            if args.check_updates {
+                return validate::check_updates(
+                    &args.file,
+                    &args.update_file,
+                    args.solver.strict,
+                    args.solver.iccma_compliant,
+                );
+            }
+            if args.validate_updates {
+                return validate::validate_updates(args);
             }
+            args.limits().spawn_watchdog()?;
+            let before = Instant::now();
+            let res = match &args.record {
+                Some(dir) => record::run(args, dir),
+                None => solve::run_task(args, args.updates()?, &mut std::io::stdout()),
+            };
+            log::info!("Entire solving took {}", format_duration(before.elapsed()));
+            res
+        }
+        Command::Generate(args) => Ok(af_generator::generate(args)?),
+        Command::Convert(args) => convert::run(args),
+        Command::Validate(args) => validate::run(args),
+        Command::Diff(args) => diff::run(args),
+        Command::Equivalent(args) => equivalent::run(args),
+        Command::Stats(args) => stats::run(args),
+        Command::Lockstep(args) => lockstep::run(args),
+        Command::Replay(args) => record::replay(args),
+        Command::Bench(args) => {
+            args.solve.limits().spawn_watchdog()?;
+            bench::run(args)
+        }
+        #[cfg(unix)]
+        Command::Serve(args) => serve::run(args),
+        #[cfg(feature = "http")]
+        Command::Http(args) => http::run(args),
+        #[cfg(feature = "tui")]
+        Command::Explore(args) => explore::run(args),
+        Command::Verify(args) => verify::run(args),
+        Command::Explain(args) => explain::run(args),
+        Command::Certify(args) => certify::run(args),
+        Command::Doctor => doctor::run(),
+        Command::Completions { shell } => {
+            let mut cmd = Args::command();
+            clap_complete::generate(
+                *shell,
+                &mut cmd,
+                env!("CARGO_BIN_NAME"),
+                &mut std::io::stdout(),
+            );
+            Ok(())
         }
     }
-    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use clap::Parser;
 
+    use crate::args::{Args, Command, SolveArgs};
+
+    fn parse_solve<'a>(extra: impl IntoIterator<Item = &'a str>) -> clap::error::Result<SolveArgs> {
+        let args = ["", "solve", "--file", "af.apx", "--task", "ee-ad"];
+        match Args::try_parse_from(args.into_iter().chain(extra))?.command {
+            Command::Solve(args) => Ok(args),
+            _ => unreachable!("Parsed solve subcommand"),
+        }
+    }
+
     #[test]
     fn argument_parser_works() {
         assert_eq!(
-            crate::args::Args::try_parse_from([""]).unwrap_err().kind(),
+            Args::try_parse_from([""]).unwrap_err().kind(),
+            clap::error::ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
+        );
+        assert_eq!(
+            Args::try_parse_from(["", "solve"]).unwrap_err().kind(),
             clap::error::ErrorKind::MissingRequiredArgument
         );
+        assert!(parse_solve([]).is_ok());
+    }
+
+    #[test]
+    fn completions_do_not_require_a_task() {
+        let args = Args::try_parse_from(["", "completions", "bash"]).unwrap();
+        assert!(matches!(args.command, Command::Completions { .. }));
+    }
+
+    #[test]
+    fn clingo_args_are_collected() {
+        let args =
+            parse_solve(["--clingo-arg", "--sign-def=rnd", "--clingo-arg=--seed=42"]).unwrap();
+        assert_eq!(args.solver.clingo_args, vec!["--sign-def=rnd", "--seed=42"]);
+    }
+
+    #[test]
+    fn thread_count_must_be_positive() {
+        let parse = |threads: &str| parse_solve(["--threads", threads]);
+        assert_eq!(parse("4").unwrap().solver.threads.map(usize::from), Some(4));
+        assert_eq!(
+            parse("0").unwrap_err().kind(),
+            clap::error::ErrorKind::ValueValidation
+        );
+    }
+
+    #[test]
+    fn semantics_file_is_optional() {
+        assert_eq!(parse_solve([]).unwrap().semantics_file, None);
+        let args = parse_solve(["--semantics-file", "naive.dl"]).unwrap();
+        assert_eq!(args.semantics_file, Some("naive.dl".into()));
+    }
+
+    #[test]
+    fn acceptance_tasks_take_an_argument() {
+        use crate::args::CliTask;
+        let parse = |extra: &[&str]| {
+            let args = ["", "solve", "--file", "af.apx"];
+            match Args::try_parse_from(args.iter().chain(extra))
+                .unwrap()
+                .command
+            {
+                Command::Solve(args) => args,
+                _ => unreachable!("Parsed solve subcommand"),
+            }
+        };
+        let args = parse(&["--task", "dc-co-d", "-a", "a1"]);
+        assert!(matches!(args.task, CliTask::DcCoD) && args.task.is_dynamic());
+        assert_eq!(args.argument.as_deref(), Some("a1"));
+        assert_eq!(parse(&["--task", "dc-st"]).argument, None);
+        let args = parse(&["--task", "ds-pr", "--additional-parameter", "a2"]);
+        assert!(matches!(args.task, CliTask::DsPr) && !args.task.is_dynamic());
+        assert_eq!(args.argument.as_deref(), Some("a2"));
+    }
+
+    #[test]
+    fn threads_compete_by_default() {
+        use crate::args::CliParallelMode;
+        let parse = |extra: &[&str]| {
+            parse_solve(extra.iter().copied()).map(|args| args.solver.parallel_mode)
+        };
+        assert!(matches!(parse(&[]).unwrap(), CliParallelMode::Compete));
+        assert!(matches!(
+            parse(&["--parallel-mode", "split"]).unwrap(),
+            CliParallelMode::Split
+        ));
+    }
+
+    #[test]
+    fn update_errors_stop_the_run_by_default() {
+        use crate::args::OnUpdateError;
+        let parse =
+            |extra: &[&str]| parse_solve(extra.iter().copied()).map(|args| args.on_update_error);
+        assert!(matches!(parse(&[]).unwrap(), OnUpdateError::Stop));
+        assert!(matches!(
+            parse(&["--on-update-error", "skip"]).unwrap(),
+            OnUpdateError::Skip
+        ));
+        assert!(parse(&["--on-update-error", "ignore"]).is_err());
+    }
+
+    #[test]
+    fn updates_are_checked_in_one_way_only() {
+        let parse = |extra: &[&str]| parse_solve(extra.iter().copied());
+        assert!(parse(&["--validate-updates"]).unwrap().validate_updates);
+        assert!(parse(&["--validate-updates", "--check-updates"]).is_err());
+    }
+
+    #[test]
+    fn bench_shares_the_solve_arguments() {
+        let args = Args::try_parse_from([
+            "",
+            "bench",
+            "--file",
+            "af.apx",
+            "--task",
+            "ce-ad",
+            "--threads",
+            "2",
+            "--runs",
+            "3",
+        ])
+        .unwrap();
+        let Command::Bench(args) = args.command else {
+            unreachable!("Parsed bench subcommand");
+        };
+        assert_eq!(args.runs.get(), 3);
+        assert_eq!(args.solve.solver.threads.map(usize::from), Some(2));
+    }
+
+    #[test]
+    fn signed_differences_are_formatted_with_sign() {
+        assert_eq!(crate::solve::signed_difference(5, 3), "+2");
+        assert_eq!(crate::solve::signed_difference(3, 5), "-2");
+        assert_eq!(crate::solve::signed_difference(4, 4), "+0");
+    }
+
+    #[test]
+    fn answers_are_framed() {
+        use std::io::Write;
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "arg(a). arg(b). att(a,b). opt(att(a,b)).").unwrap();
+        let path = file.path().to_str().unwrap();
+        let args = ["", "solve", "--file", path, "--task", "ce-ad-d", "--framed"];
+        let Command::Solve(args) = Args::try_parse_from(args).unwrap().command else {
+            unreachable!("Parsed solve subcommand");
+        };
+        let updates = fallible_iterator::convert(vec![Ok("+att(a,b).".to_owned())].into_iter());
+        let mut out = vec![];
+        crate::solve::run_task(&args, Box::new(updates), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "BEGIN ANSWER 0\n// Initial count\n4\nEND ANSWER\n\
+             BEGIN ANSWER 1\n// Update #0 -- +att(a,b).\n2\nEND ANSWER\n"
+        );
     }
 }
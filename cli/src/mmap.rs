@@ -0,0 +1,96 @@
+//! Read-only memory mapping of input files
+//!
+//! The kernel loads the pages of the file lazily, so multi-GB instances are parsed
+//! without copying the whole file into memory first.
+use std::{
+    fs::File,
+    io,
+    os::fd::AsRawFd,
+    path::Path,
+    ptr::{self, NonNull},
+};
+
+/// A file mapped into memory.
+///
+/// The file must not be modified while it is mapped, as the mapped content would
+/// change underneath the parser.
+pub struct Mmap {
+    ptr: NonNull<libc::c_void>,
+    len: usize,
+}
+
+impl Mmap {
+    /// Map the whole file read-only.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|why| io::Error::new(io::ErrorKind::InvalidData, why))?;
+        if len == 0 {
+            // Empty mappings are rejected by mmap
+            return Ok(Mmap {
+                ptr: NonNull::dangling(),
+                len,
+            });
+        }
+        // SAFETY: A private read-only mapping of an open file, checked for failure below.
+        // The mapping stays valid after closing the file.
+        let ptr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        let ptr = NonNull::new(ptr).ok_or_else(io::Error::last_os_error)?;
+        Ok(Mmap { ptr, len })
+    }
+
+    /// The mapped content.
+    pub fn as_bytes(&self) -> &[u8] {
+        // SAFETY: The mapping spans `len` readable bytes until dropped
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr().cast(), self.len) }
+    }
+
+    /// The mapped content, if it is valid UTF-8.
+    pub fn as_str(&self) -> io::Result<&str> {
+        std::str::from_utf8(self.as_bytes())
+            .map_err(|why| io::Error::new(io::ErrorKind::InvalidData, why))
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        if self.len > 0 {
+            // SAFETY: Unmapping exactly the region mapped in `open`
+            unsafe { libc::munmap(self.ptr.as_ptr(), self.len) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn mapped_files_match_their_content() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        assert_eq!(Mmap::open(file.path()).unwrap().as_str().unwrap(), "");
+        write!(file, "arg(a).\narg(b).\n").unwrap();
+        file.flush().unwrap();
+        assert_eq!(
+            Mmap::open(file.path()).unwrap().as_str().unwrap(),
+            "arg(a).\narg(b).\n"
+        );
+        file.write_all(&[0xff]).unwrap();
+        file.flush().unwrap();
+        assert!(Mmap::open(file.path()).unwrap().as_str().is_err());
+    }
+}
@@ -0,0 +1,58 @@
+//! Writing frameworks in the supported file formats
+use std::io::Write;
+
+use af_generator::Format;
+use lib::argumentation_framework::symbols::{Argument, Attack};
+
+/// Write the framework to `out` using the given format, keeping optional markers.
+pub fn write_framework(
+    out: &mut impl Write,
+    format: Format,
+    args: &[Argument],
+    attacks: &[Attack],
+) -> std::io::Result<()> {
+    match format {
+        Format::Apx => {
+            for arg in args {
+                let arg_string = format!("arg({})", arg.id);
+                if arg.optional {
+                    writeln!(out, "{arg_string}. opt({arg_string}).")?;
+                } else {
+                    writeln!(out, "{arg_string}.")?;
+                }
+            }
+            for attack in attacks {
+                let attack_string = format!("att({}, {})", attack.from, attack.to);
+                if attack.optional {
+                    writeln!(out, "{attack_string}. opt({attack_string}).")?;
+                } else {
+                    writeln!(out, "{attack_string}.")?;
+                }
+            }
+        }
+        Format::Tgf => {
+            for arg in args {
+                writeln!(out, "{}{}", arg.id, optional_marker(arg.optional))?;
+            }
+            writeln!(out, "#")?;
+            for attack in attacks {
+                writeln!(
+                    out,
+                    "{} {}{}",
+                    attack.from,
+                    attack.to,
+                    optional_marker(attack.optional)
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+const fn optional_marker(optional: bool) -> &'static str {
+    if optional {
+        "?"
+    } else {
+        ""
+    }
+}
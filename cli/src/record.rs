@@ -0,0 +1,198 @@
+//! Record solving sessions and replay them for regression testing
+//!
+//! A recording is a directory containing
+//!
+//! - `framework`, the initial framework
+//! - `updates`, all update lines read
+//! - `semantics.dl`, the program given by `--semantics-file`, if any
+//! - `args`, the arguments passed to `dasp solve`, one per line
+//! - `output`, everything written to stdout
+use std::{
+    fs::{self, File},
+    io::{self, LineWriter, Write},
+    path::Path,
+};
+
+use clap::Parser;
+use driver::PathOrStdin;
+use fallible_iterator::FallibleIterator;
+
+use crate::{
+    args::{Args, Command, ReplayArgs, SolveArgs},
+    solve, Error, Result,
+};
+
+const FRAMEWORK_FILE: &str = "framework";
+const UPDATES_FILE: &str = "updates";
+const SEMANTICS_FILE: &str = "semantics.dl";
+const ARGS_FILE: &str = "args";
+const OUTPUT_FILE: &str = "output";
+
+/// Solve the task while recording inputs and outputs to `dir`.
+pub fn run(args: &SolveArgs, dir: &Path) -> Result {
+    fs::create_dir_all(dir)?;
+    fs::copy(&args.file, dir.join(FRAMEWORK_FILE))?;
+    if let Some(semantics_file) = &args.semantics_file {
+        fs::copy(semantics_file, dir.join(SEMANTICS_FILE))?;
+    }
+    let recorded_args: Vec<_> = std::env::args()
+        .skip_while(|arg| arg != "solve")
+        .skip(1)
+        .collect();
+    fs::write(dir.join(ARGS_FILE), recorded_args.join("\n") + "\n")?;
+    let mut updates_file = LineWriter::new(File::create(dir.join(UPDATES_FILE))?);
+    let updates = args.updates()?.map(move |update| {
+        writeln!(updates_file, "{update}")?;
+        Ok(update)
+    });
+    let mut out = Tee {
+        first: io::stdout(),
+        second: LineWriter::new(File::create(dir.join(OUTPUT_FILE))?),
+    };
+    solve::run_task(args, Box::new(updates), &mut out)?;
+    out.flush()?;
+    log::info!("Recorded session to {}", dir.display());
+    Ok(())
+}
+
+/// Solve the recorded session again and compare the outputs.
+pub fn replay(args: &ReplayArgs) -> Result {
+    let recorded_args = fs::read_to_string(args.dir.join(ARGS_FILE))?;
+    let parsed = Args::try_parse_from(
+        ["dasp", "solve"]
+            .into_iter()
+            .chain(recorded_args.lines().filter(|arg| !arg.is_empty())),
+    )
+    .map_err(|why| Error::Replay(why.to_string()))?;
+    let Command::Solve(mut solve_args) = parsed.command else {
+        unreachable!("Parsed solve subcommand");
+    };
+    // Read the recorded inputs and keep no state besides the output
+    solve_args.file = args.dir.join(FRAMEWORK_FILE);
+    solve_args.update_file = PathOrStdin::Path(args.dir.join(UPDATES_FILE));
+    if solve_args.semantics_file.is_some() {
+        solve_args.semantics_file = Some(args.dir.join(SEMANTICS_FILE));
+    }
+    solve_args.follow = false;
+    solve_args.check_updates = false;
+    solve_args.validate_updates = false;
+    solve_args.checkpoint = None;
+    solve_args.summary = None;
+    solve_args.record = None;
+    let mut output = vec![];
+    solve::run_task(&solve_args, solve_args.updates()?, &mut output)?;
+    let expected = fs::read_to_string(args.dir.join(OUTPUT_FILE))?;
+    let actual = String::from_utf8_lossy(&output);
+    let differences = differing_sections(&expected, &actual);
+    for (header, expected, actual) in &differences {
+        println!("{header}");
+        expected.iter().for_each(|line| println!("-{line}"));
+        actual.iter().for_each(|line| println!("+{line}"));
+    }
+    if differences.is_empty() {
+        println!("{}: replay matches the recording", args.dir.display());
+        Ok(())
+    } else {
+        Err(Error::Replay(format!(
+            "{} section(s) differ from the recording",
+            differences.len()
+        )))
+    }
+}
+
+/// Writer duplicating everything into two writers.
+struct Tee<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A: Write, B: Write> Write for Tee<A, B> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.first.write_all(buf)?;
+        self.second.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.first.flush()?;
+        self.second.flush()
+    }
+}
+
+type Section<'a> = (&'a str, Vec<&'a str>);
+
+/// Compare both outputs section by section, where every `//` comment starts a new section.
+///
+/// Lines within a section are compared regardless of their order, as the order of
+/// extensions depends on the solver. Returns the header and the lines only found in
+/// the expected and the actual section, respectively.
+fn differing_sections<'a>(
+    expected: &'a str,
+    actual: &'a str,
+) -> Vec<(&'a str, Vec<&'a str>, Vec<&'a str>)> {
+    let expected = sections(expected);
+    let actual = sections(actual);
+    let mut differences = vec![];
+    for idx in 0..expected.len().max(actual.len()) {
+        let (header, mut expected) = expected.get(idx).cloned().unwrap_or_default();
+        let (actual_header, mut actual) = actual.get(idx).cloned().unwrap_or_default();
+        if header != actual_header {
+            expected.insert(0, header);
+            actual.insert(0, actual_header);
+        }
+        expected.sort_unstable();
+        actual.sort_unstable();
+        if expected != actual {
+            let only_expected = expected
+                .iter()
+                .filter(|line| !actual.contains(line))
+                .copied()
+                .collect();
+            let only_actual = actual
+                .iter()
+                .filter(|line| !expected.contains(line))
+                .copied()
+                .collect();
+            differences.push((header, only_expected, only_actual));
+        }
+    }
+    differences
+}
+
+fn sections(output: &str) -> Vec<Section<'_>> {
+    let mut sections: Vec<Section> = vec![];
+    for line in output.lines() {
+        match sections.last_mut() {
+            Some((_, lines)) if !line.starts_with("//") => lines.push(line),
+            _ if line.starts_with("//") => sections.push((line, vec![])),
+            _ => sections.push(("", vec![line])),
+        }
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sections_are_compared_regardless_of_order() {
+        let expected = "// Initial extensions\n[a]\n[]\n// Update #0 -- +b.\n[a]\n";
+        assert!(differing_sections(
+            expected,
+            "// Initial extensions\n[]\n[a]\n// Update #0 -- +b.\n[a]\n"
+        )
+        .is_empty());
+        assert_eq!(
+            differing_sections(
+                expected,
+                "// Initial extensions\n[]\n[a]\n// Update #0 -- +b.\n[b]\n"
+            ),
+            vec![("// Update #0 -- +b.", vec!["[a]"], vec!["[b]"])]
+        );
+        assert_eq!(
+            differing_sections(expected, "// Initial extensions\n[]\n[a]\n").len(),
+            1
+        );
+    }
+}
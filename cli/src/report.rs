@@ -0,0 +1,84 @@
+//! Machine-readable error reports
+use lib::framework::ParserError;
+use serde_json::{json, Value};
+
+use crate::Error;
+
+impl Error {
+    /// Describe the error as JSON object with its `kind`, `message`, `file`, `line` and `span`.
+    ///
+    /// Unknown locations are `null`.
+    pub fn to_json(&self) -> Value {
+        let (file, line, error) = match self {
+            Error::InFile { file, line, source } => (Some(file), *line, &**source),
+            error => (None, None, error),
+        };
+        let span = match error {
+            Error::Lib(lib::Error::Parser(ParserError::UnexpectedToken {
+                position, text, ..
+            })) => json!({ "start": position.start, "end": position.end, "text": text }),
+            _ => Value::Null,
+        };
+        json!({
+            "kind": error.kind(),
+            "message": most_specific_message(error),
+            "file": file,
+            "line": line,
+            "span": span,
+        })
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Error::Lib(lib::Error::Io(_)) | Error::Io(_) => "io",
+            Error::Lib(lib::Error::Clingo(_)) => "clingo",
+            Error::Lib(lib::Error::Parser(_)) => "parser",
+            Error::Lib(lib::Error::Logic(_)) => "logic",
+            Error::Lib(lib::Error::ClingoNotInitialized) => "internal",
+            Error::Lib(lib::Error::LimitExceeded) | Error::Limit(_) => "limit",
+            Error::Invalid(_) => "invalid",
+            Error::Checkpoint(_) => "checkpoint",
+            Error::Doctor(_) => "doctor",
+            Error::Verify(_) => "verify",
+            Error::Certificate(_) => "certificate",
+            Error::Replay(_) => "replay",
+            Error::InFile { source, .. } => source.kind(),
+            #[cfg(feature = "http")]
+            Error::NoFramework => "no-framework",
+        }
+    }
+}
+
+/// Message of the innermost error, as outer errors only add generic context.
+fn most_specific_message(error: &dyn std::error::Error) -> String {
+    let mut error = error;
+    while let Some(source) = error.source() {
+        error = source;
+    }
+    error.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lib::argumentation_framework::Patch;
+
+    #[test]
+    fn errors_are_reported_with_location() {
+        let parse_error = Patch::parse_line("+zz(").unwrap_err();
+        let error = Error::InFile {
+            file: "updates.apxm".to_owned(),
+            line: Some(3),
+            source: Box::new(parse_error.into()),
+        };
+        let report = error.to_json();
+        assert_eq!(report["kind"], "parser");
+        assert_eq!(report["file"], "updates.apxm");
+        assert_eq!(report["line"], 3);
+        assert_eq!(report["span"]["start"], 3);
+        assert!(report["message"].as_str().unwrap().contains("Expected"));
+        let report = Error::Invalid(2).to_json();
+        assert_eq!(report["message"], "Found 2 invalid line(s)");
+        assert_eq!(report["file"], Value::Null);
+    }
+}
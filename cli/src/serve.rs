@@ -0,0 +1,175 @@
+//! Serve a loaded framework over a Unix socket
+//!
+//! Clients are handled one after another. Every request is a single line,
+//! answered by a single line unless noted otherwise:
+//!
+//! - `update <LINE>` applies an APXM/TGFM update line, answers `OK`
+//! - `query <ARG>` checks credulous acceptance, answers `YES` or `NO`
+//! - `query skeptical <ARG>` checks skeptical acceptance, answers `YES` or `NO`
+//! - `count` answers the number of extensions
+//! - `enumerate` answers every extension on its own line, followed by `END`
+//! - `stats` answers `updates <N> queries <N> clients <N>`
+//! - `quit` closes the connection, `shutdown` stops the server
+//!
+//! Queries are answered under the semantics given by `--semantics`. Failing requests
+//! are answered by `ERROR <REASON>`.
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    os::unix::net::{UnixListener, UnixStream},
+};
+
+use lib::{
+    argumentation_framework::{registry::DynFramework, symbols::Argument},
+    GenericExtension,
+};
+
+use crate::{args::ServeArgs, Result};
+
+/// Framework of the semantics given on the command line.
+type ServedFramework = Box<dyn DynFramework>;
+
+/// Counters reported by `stats`.
+#[derive(Debug, Default)]
+struct Stats {
+    updates: usize,
+    queries: usize,
+    clients: usize,
+}
+
+/// What to do after handling a request.
+enum Flow {
+    Continue,
+    Disconnect,
+    Shutdown,
+}
+
+/// Load the framework and answer requests until a client asks for shutdown.
+pub fn run(args: &ServeArgs) -> Result {
+    let content = fs::read_to_string(&args.file)?;
+    let mut af = args
+        .semantics
+        .id()
+        .framework(&content, args.solver.framework_config())?;
+    log::info!("Successfully populated AF from initial file");
+    let listener = UnixListener::bind(&args.socket)?;
+    log::info!("Listening on {}", args.socket.display());
+    let mut stats = Stats::default();
+    for stream in listener.incoming() {
+        stats.clients += 1;
+        match serve_client(&mut af, &mut stats, stream?) {
+            Ok(Flow::Shutdown) => break,
+            Ok(_) => log::info!("Client disconnected"),
+            Err(why) => log::warn!("Client connection failed: {why}"),
+        }
+    }
+    fs::remove_file(&args.socket)?;
+    Ok(())
+}
+
+fn serve_client(af: &mut ServedFramework, stats: &mut Stats, stream: UnixStream) -> Result<Flow> {
+    let mut out = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        log::debug!("Received request {line:?}");
+        let flow = match handle_request(af, stats, line.trim(), &mut out) {
+            Ok(flow) => flow,
+            Err(why) => {
+                writeln!(out, "ERROR {why}")?;
+                Flow::Continue
+            }
+        };
+        if !matches!(flow, Flow::Continue) {
+            return Ok(flow);
+        }
+    }
+    Ok(Flow::Disconnect)
+}
+
+fn handle_request(
+    af: &mut ServedFramework,
+    stats: &mut Stats,
+    request: &str,
+    out: &mut impl Write,
+) -> Result<Flow> {
+    let (command, argument) = request.split_once(' ').unwrap_or((request, ""));
+    match command {
+        "update" => {
+            af.update(argument)?;
+            stats.updates += 1;
+            writeln!(out, "OK")?;
+        }
+        "query" => {
+            let accepted = match argument.split_once(' ') {
+                Some(("skeptical", id)) => af.is_skeptical_accepted(&Argument::new(id, false))?,
+                _ => af.is_credulous_accepted(&Argument::new(argument, false))?,
+            };
+            stats.queries += 1;
+            writeln!(out, "{}", if accepted { "YES" } else { "NO" })?;
+        }
+        "count" => {
+            let count = af.count_extensions()?;
+            stats.queries += 1;
+            writeln!(out, "{count}")?;
+        }
+        "enumerate" => {
+            let extensions = af.extensions()?;
+            stats.queries += 1;
+            for ext in extensions {
+                writeln!(out, "{}", ext.format())?;
+            }
+            writeln!(out, "END")?;
+        }
+        "stats" => writeln!(
+            out,
+            "updates {} queries {} clients {}",
+            stats.updates, stats.queries, stats.clients
+        )?,
+        "quit" => return Ok(Flow::Disconnect),
+        "shutdown" => {
+            writeln!(out, "OK")?;
+            return Ok(Flow::Shutdown);
+        }
+        _ => writeln!(out, "ERROR Unknown command {command:?}")?,
+    }
+    Ok(Flow::Continue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::CliSemantics;
+
+    fn served(semantics: CliSemantics) -> ServedFramework {
+        let content = "arg(a). arg(b). att(a,b). opt(att(a,b)).";
+        semantics
+            .id()
+            .framework(content, Default::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn requests_are_answered_line_by_line() {
+        let mut af = served(CliSemantics::Ad);
+        let mut stats = Stats::default();
+        let mut out = vec![];
+        for request in ["count", "update +att(a,b).", "query b", "stats", "nope"] {
+            handle_request(&mut af, &mut stats, request, &mut out).unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "4\nOK\nNO\nupdates 1 queries 2 clients 0\nERROR Unknown command \"nope\"\n"
+        );
+    }
+
+    #[test]
+    fn requests_are_answered_under_the_given_semantics() {
+        let mut af = served(CliSemantics::St);
+        let mut stats = Stats::default();
+        let mut out = vec![];
+        for request in ["query skeptical a", "count", "update +att(a,b).", "query b"] {
+            handle_request(&mut af, &mut stats, request, &mut out).unwrap();
+        }
+        assert_eq!(String::from_utf8(out).unwrap(), "YES\n1\nOK\nNO\n");
+    }
+}
@@ -0,0 +1,617 @@
+//! Solving tasks, writing their answers to an output
+use std::{
+    collections::BTreeSet,
+    fs::File,
+    io::{BufRead, BufWriter, Write},
+};
+
+use driver::{
+    output::{write_extensions, write_optimal, write_sample, write_samples, SampleFormat},
+    Problem,
+};
+use fallible_iterator::FallibleIterator;
+use lib::{
+    argumentation_framework::{
+        registry::SemanticsId,
+        semantics::{custom, ArgumentationFrameworkSemantic},
+        symbols, ArgumentationFramework, FrameworkConfig, Heuristic, ParallelMode,
+        UndeclaredArguments,
+    },
+    semantics, Framework, GenericExtension, Objective,
+};
+
+use crate::{
+    args::{
+        CliHeuristic, CliParallelMode, CliSemantics, CliTask, CliUndeclared, OnUpdateError,
+        SolveArgs, SolverArgs,
+    },
+    checkpoint::Checkpoint,
+    limits,
+    summary::Summary,
+    Error, Result,
+};
+
+impl SolverArgs {
+    /// Framework configuration requested on the command line.
+    pub fn framework_config(&self) -> FrameworkConfig {
+        let mut config = FrameworkConfig {
+            parallel_mode: match self.parallel_mode {
+                CliParallelMode::Compete => ParallelMode::Compete,
+                CliParallelMode::Split => ParallelMode::Split,
+            },
+            clingo_args: self.clingo_args.clone(),
+            clingo_log_level: self.clingo_log_level.to_level(),
+            heuristic: match self.heuristic {
+                CliHeuristic::Default => Heuristic::Default,
+                CliHeuristic::PreferOut => Heuristic::PreferOut,
+                CliHeuristic::AttackDegree => Heuristic::AttackDegree,
+            },
+            decompose: self.decompose,
+            deduplicate: self.deduplicate,
+            warm_start: self.warm_start,
+            strict: self.strict,
+            iccma_compliant: self.iccma_compliant,
+            undeclared: match self.undeclared {
+                CliUndeclared::Reject => UndeclaredArguments::Reject,
+                CliUndeclared::Ignore => UndeclaredArguments::Ignore,
+                CliUndeclared::Declare => UndeclaredArguments::Declare,
+            },
+            cache: self.cache,
+            ..Default::default()
+        };
+        if let Some(threads) = self.threads {
+            config.threads = threads;
+        }
+        config
+    }
+}
+
+/// Seed derived from the current time, differing between runs.
+fn random_seed() -> u32 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.subsec_nanos())
+}
+
+/// Update lines applied after solving the initial framework.
+pub type Updates<'a> = Box<dyn FallibleIterator<Item = String, Error = Error> + 'a>;
+
+impl CliTask {
+    /// The problem to solve, independent of semantics and dynamics.
+    pub const fn problem(&self) -> Problem {
+        match self {
+            CliTask::CeAd | CliTask::CeAdD => Problem::Count,
+            CliTask::EeAd | CliTask::EeAdD => Problem::Enumerate,
+            CliTask::SeAd | CliTask::SeAdD => Problem::Sample,
+            CliTask::MaxAd | CliTask::MaxAdD => Problem::Optimal(Objective::Maximum),
+            CliTask::MinAd | CliTask::MinAdD => Problem::Optimal(Objective::Minimum),
+            CliTask::DcAd
+            | CliTask::DcAdD
+            | CliTask::DcCo
+            | CliTask::DcCoD
+            | CliTask::DcSt
+            | CliTask::DcStD => Problem::Credulous,
+            CliTask::DsAd
+            | CliTask::DsAdD
+            | CliTask::DsCo
+            | CliTask::DsCoD
+            | CliTask::DsPr
+            | CliTask::DsPrD
+            | CliTask::DsSt
+            | CliTask::DsStD => Problem::Skeptical,
+            CliTask::EcAd
+            | CliTask::EcAdD
+            | CliTask::EcCo
+            | CliTask::EcCoD
+            | CliTask::EcPr
+            | CliTask::EcPrD
+            | CliTask::EcSt
+            | CliTask::EcStD => Problem::CredulousArguments,
+            CliTask::EsAd
+            | CliTask::EsAdD
+            | CliTask::EsCo
+            | CliTask::EsCoD
+            | CliTask::EsPr
+            | CliTask::EsPrD
+            | CliTask::EsSt
+            | CliTask::EsStD => Problem::SkepticalArguments,
+            CliTask::ExSt | CliTask::ExStD => Problem::ExistsExtension,
+        }
+    }
+}
+
+impl CliSemantics {
+    /// The semantics to create frameworks under at runtime.
+    pub const fn id(self) -> SemanticsId {
+        match self {
+            CliSemantics::Ad => SemanticsId::Admissible,
+            CliSemantics::Co => SemanticsId::Complete,
+            CliSemantics::Gr => SemanticsId::Grounded,
+            CliSemantics::St => SemanticsId::Stable,
+        }
+    }
+}
+
+impl SolveArgs {
+    /// Framework configuration for the requested task.
+    pub fn framework_config(&self) -> FrameworkConfig {
+        let mut config = self.solver.framework_config();
+        config.conflict_limit = self.max_conflicts;
+        match self.task {
+            CliTask::EeAd | CliTask::EeAdD => config.models = self.first,
+            _ if self.first.is_some() => {
+                log::warn!("--first only applies to EE tasks, ignoring it")
+            }
+            _ => {}
+        }
+        match self.task {
+            CliTask::SeAd | CliTask::SeAdD if self.sample_k.is_some() => {
+                config.models = self.sample_k;
+                let seed = random_seed();
+                log::info!("Sampling with random seed {seed}");
+                config.random_seed = Some(seed);
+            }
+            _ if self.sample_k.is_some() => {
+                log::warn!("--sample-k only applies to SE tasks, ignoring it")
+            }
+            _ => {}
+        }
+        config
+    }
+
+    /// Update lines requested on the command line, empty for static tasks.
+    pub fn updates(&self) -> Result<Updates<'static>> {
+        if self.task.is_dynamic() {
+            Ok(Box::new(
+                self.update_file.lines(self.follow)?.map_err(Error::from),
+            ))
+        } else {
+            Ok(no_updates())
+        }
+    }
+}
+
+/// Updates for static tasks.
+fn no_updates() -> Updates<'static> {
+    Box::new(fallible_iterator::convert(std::iter::empty()))
+}
+
+/// Run the requested task, writing all answers to `out`.
+pub fn run_task(args: &SolveArgs, updates: Updates, out: &mut impl Write) -> Result {
+    if let Some(path) = &args.semantics_file {
+        return run_custom_task(args, path, updates, out);
+    }
+    match args.task {
+        CliTask::CeAd | CliTask::CeAdD => {
+            run_task_count_extensions::<semantics::Admissible>(args, updates, out)
+        }
+        CliTask::EeAd | CliTask::EeAdD => {
+            run_task_enumerate_extensions::<semantics::Admissible>(args, updates, out)
+        }
+        CliTask::SeAd | CliTask::SeAdD => {
+            run_task_sample_extension::<semantics::Admissible>(args, updates, out)
+        }
+        CliTask::MaxAd | CliTask::MaxAdD | CliTask::MinAd | CliTask::MinAdD => {
+            run_task_optimal_extension::<semantics::Admissible>(args, updates, out)
+        }
+        CliTask::DcAd | CliTask::DcAdD | CliTask::DsAd | CliTask::DsAdD => {
+            run_task_decide_acceptance::<semantics::Admissible>(args, updates, out)
+        }
+        CliTask::DcCo | CliTask::DcCoD | CliTask::DsCo | CliTask::DsCoD => {
+            run_task_decide_acceptance::<semantics::Complete>(args, updates, out)
+        }
+        CliTask::DcSt | CliTask::DcStD | CliTask::DsSt | CliTask::DsStD => {
+            run_task_decide_acceptance::<semantics::Stable>(args, updates, out)
+        }
+        CliTask::DsPr | CliTask::DsPrD => {
+            run_task_decide_acceptance::<semantics::Preferred>(args, updates, out)
+        }
+        CliTask::EcAd | CliTask::EcAdD | CliTask::EsAd | CliTask::EsAdD => {
+            run_task_unqueried::<semantics::Admissible>(args, updates, out)
+        }
+        CliTask::EcCo | CliTask::EcCoD | CliTask::EsCo | CliTask::EsCoD => {
+            run_task_unqueried::<semantics::Complete>(args, updates, out)
+        }
+        CliTask::EcPr | CliTask::EcPrD | CliTask::EsPr | CliTask::EsPrD => {
+            run_task_unqueried::<semantics::Preferred>(args, updates, out)
+        }
+        CliTask::EcSt
+        | CliTask::EcStD
+        | CliTask::EsSt
+        | CliTask::EsStD
+        | CliTask::ExSt
+        | CliTask::ExStD => run_task_unqueried::<semantics::Stable>(args, updates, out),
+    }
+}
+
+/// Run the requested task under the semantics loaded from `path`.
+fn run_custom_task(
+    args: &SolveArgs,
+    path: &std::path::Path,
+    updates: Updates,
+    out: &mut impl Write,
+) -> Result {
+    custom::load_file(path)?;
+    log::info!("Solving under the semantics of {}", path.display());
+    match args.task.problem() {
+        Problem::Count => run_task_count_extensions::<semantics::Custom>(args, updates, out),
+        Problem::Enumerate => {
+            run_task_enumerate_extensions::<semantics::Custom>(args, updates, out)
+        }
+        Problem::Sample => run_task_sample_extension::<semantics::Custom>(args, updates, out),
+        Problem::Optimal(_) => run_task_optimal_extension::<semantics::Custom>(args, updates, out),
+        Problem::Credulous | Problem::Skeptical => {
+            run_task_decide_acceptance::<semantics::Custom>(args, updates, out)
+        }
+        Problem::ExistsExtension | Problem::CredulousArguments | Problem::SkepticalArguments => {
+            run_task_unqueried::<semantics::Custom>(args, updates, out)
+        }
+    }
+}
+
+/// Update lines applied to a framework one by one.
+///
+/// Keeps the checkpoint up to date, if one was requested. An update counts as
+/// completed once the next update is requested, i.e. after its answer was written.
+struct UpdateStream<'a> {
+    updates: Updates<'a>,
+    /// Number of the next update line
+    next_nr: usize,
+    /// Update applied last, not yet recorded in the checkpoint
+    pending: Option<String>,
+    checkpoint: Option<Checkpoint>,
+    on_error: OnUpdateError,
+    /// Name of the update file, for error messages
+    source: String,
+    summary: Option<Summary<BufWriter<File>>>,
+}
+
+impl<'a> UpdateStream<'a> {
+    fn new(args: &SolveArgs, updates: Updates<'a>) -> Result<Self> {
+        let checkpoint = args
+            .checkpoint
+            .as_deref()
+            .filter(|_| args.task.is_dynamic())
+            .map(|path| Checkpoint::load_or_new(path, args.checkpoint_every))
+            .transpose()?;
+        let summary = args
+            .summary
+            .as_deref()
+            .filter(|_| args.task.is_dynamic())
+            .map(Summary::create)
+            .transpose()?;
+        Ok(UpdateStream {
+            updates,
+            next_nr: 0,
+            pending: None,
+            checkpoint,
+            on_error: args.on_update_error,
+            source: args.update_file.to_string(),
+            summary,
+        })
+    }
+
+    /// Restore the checkpointed state and skip all update lines applied before.
+    /// The summary starts with the resulting state.
+    ///
+    /// Returns whether the framework has been changed.
+    fn restore<S: ArgumentationFrameworkSemantic>(
+        &mut self,
+        af: &mut ArgumentationFramework<S>,
+    ) -> Result<bool> {
+        let mut restored = false;
+        if let Some(checkpoint) = &self.checkpoint {
+            af.apply_patches(checkpoint.patches())?;
+            while self.next_nr < checkpoint.applied() && self.updates.next()?.is_some() {
+                self.next_nr += 1;
+            }
+            restored = checkpoint.applied() > 0;
+        }
+        if let Some(summary) = &mut self.summary {
+            summary.record(af, None)?;
+        }
+        Ok(restored)
+    }
+
+    /// Apply the next update line to the framework, returning its number and content.
+    fn apply_next<S: ArgumentationFrameworkSemantic>(
+        &mut self,
+        af: &mut ArgumentationFramework<S>,
+    ) -> Result<Option<(usize, String)>> {
+        if let (Some(update), Some(checkpoint)) = (self.pending.take(), &mut self.checkpoint) {
+            checkpoint.record(&update)?;
+        }
+        limits::check()?;
+        while let Some(update) = self.updates.next()? {
+            let nr = self.next_nr;
+            self.next_nr += 1;
+            match af.update(&update) {
+                Ok(()) => {
+                    if let Some(summary) = &mut self.summary {
+                        summary.record(af, Some((nr, &update)))?;
+                    }
+                    self.pending = Some(update.clone());
+                    return Ok(Some((nr, update)));
+                }
+                Err(why) if self.should_skip(nr, &update, &why)? => {
+                    log::warn!("Skipping update #{nr} {update:?}: {why}");
+                    if let Some(checkpoint) = &mut self.checkpoint {
+                        checkpoint.skip()?;
+                    }
+                }
+                Err(why) => return Err(Error::in_file(&self.source, Some(nr + 1), why.into())),
+            }
+        }
+        if let Some(checkpoint) = self.checkpoint.take() {
+            checkpoint.remove()?;
+        }
+        Ok(None)
+    }
+
+    /// Decide whether the failed update should be skipped.
+    fn should_skip(&self, nr: usize, update: &str, why: &lib::Error) -> Result<bool> {
+        match self.on_error {
+            OnUpdateError::Skip => Ok(true),
+            OnUpdateError::Stop => Ok(false),
+            OnUpdateError::Prompt => prompt_skip(nr, update, why),
+        }
+    }
+}
+
+/// Ask on the terminal whether to skip the failed update.
+///
+/// The terminal is used instead of stdin, as the updates may be read from stdin.
+fn prompt_skip(nr: usize, update: &str, why: &lib::Error) -> Result<bool> {
+    let tty = match std::fs::File::open("/dev/tty") {
+        Ok(tty) => tty,
+        Err(open_error) => {
+            log::warn!("Cannot prompt without a terminal ({open_error}), stopping");
+            return Ok(false);
+        }
+    };
+    eprint!("Update #{nr} {update:?} failed: {why}\nSkip it and continue? [y/N] ");
+    let mut answer = String::new();
+    std::io::BufReader::new(tty).read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+/// Pass the content of the initial file to `f`, memory-mapped if requested.
+fn with_initial_file<T>(args: &SolveArgs, f: impl FnOnce(&str) -> Result<T>) -> Result<T> {
+    #[cfg(unix)]
+    if args.mmap {
+        let mapped = crate::mmap::Mmap::open(&args.file)?;
+        return f(mapped.as_str()?);
+    }
+    f(&std::fs::read_to_string(&args.file)?)
+}
+
+pub fn load_initial_file_into_af<S: ArgumentationFrameworkSemantic>(
+    args: &SolveArgs,
+) -> Result<ArgumentationFramework<S>> {
+    let af = with_initial_file(args, |content| {
+        Ok(ArgumentationFramework::new_with_config(
+            content,
+            args.framework_config(),
+        )?)
+    })
+    .map_err(|why| Error::in_file(args.file.display(), None, why))?;
+    log::info!("Successfully populated AF from initial file");
+    Ok(af)
+}
+
+fn run_task_count_extensions<S: ArgumentationFrameworkSemantic>(
+    args: &SolveArgs,
+    updates: Updates,
+    out: &mut impl Write,
+) -> Result {
+    let mut af = load_initial_file_into_af::<S>(args)?;
+    begin_answer(args, out, 0)?;
+    writeln!(out, "// Initial count")?;
+    let initial_count = af.count_extensions()?;
+    writeln!(out, "{initial_count}")?;
+    end_answer(args, out)?;
+    let mut previous_count = initial_count;
+    let mut updates = UpdateStream::new(args, updates)?;
+    updates.restore(&mut af)?;
+    while let Some((nr, update)) = updates.apply_next(&mut af)? {
+        begin_answer(args, out, nr + 1)?;
+        writeln!(out, "// Update #{nr} -- {update}")?;
+        let count = af.count_extensions()?;
+        writeln!(out, "{count}")?;
+        if args.deltas {
+            writeln!(
+                out,
+                "// Delta {} -- Trend {}",
+                signed_difference(count, previous_count),
+                signed_difference(count, initial_count)
+            )?;
+        }
+        end_answer(args, out)?;
+        previous_count = count;
+    }
+    print_profile(args, &af);
+    Ok(())
+}
+
+/// Print the phase timings of the framework to stderr, if requested.
+fn print_profile<S: ArgumentationFrameworkSemantic>(
+    args: &SolveArgs,
+    af: &ArgumentationFramework<S>,
+) {
+    #[cfg(feature = "profiling")]
+    if args.profile {
+        eprint!("{}", af.profile());
+    }
+    #[cfg(not(feature = "profiling"))]
+    let _ = (args, af);
+}
+
+/// Start the answer for the framework after the first `answer` update lines, if framed.
+fn begin_answer(args: &SolveArgs, out: &mut impl Write, answer: usize) -> Result {
+    if args.framed {
+        writeln!(out, "BEGIN ANSWER {answer}")?;
+    }
+    Ok(())
+}
+
+/// End the current answer, if framed. Flushes, as consumers wait for complete answers.
+fn end_answer(args: &SolveArgs, out: &mut impl Write) -> Result {
+    if args.framed {
+        writeln!(out, "END ANSWER")?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+/// Format `current - previous` with an explicit sign.
+pub fn signed_difference(current: usize, previous: usize) -> String {
+    if current >= previous {
+        format!("+{}", current - previous)
+    } else {
+        format!("-{}", previous - current)
+    }
+}
+
+fn run_task_enumerate_extensions<S: ArgumentationFrameworkSemantic>(
+    args: &SolveArgs,
+    updates: Updates,
+    out: &mut impl Write,
+) -> Result {
+    let mut af = load_initial_file_into_af::<S>(args)?;
+    begin_answer(args, out, 0)?;
+    writeln!(out, "// Initial extensions")?;
+    // Extensions of the previous framework, only tracked for delta output
+    let mut previous = BTreeSet::new();
+    if args.delta_output {
+        previous = af.enumerate_extensions()?.by_ref().collect()?;
+        for ext in &previous {
+            writeln!(out, "{}", ext.format())?;
+        }
+    } else {
+        write_extensions(&mut af, out)?;
+    }
+    end_answer(args, out)?;
+    let mut updates = UpdateStream::new(args, updates)?;
+    if updates.restore(&mut af)? && args.delta_output {
+        previous = af.enumerate_extensions()?.by_ref().collect()?;
+    }
+    while let Some((nr, update)) = updates.apply_next(&mut af)? {
+        begin_answer(args, out, nr + 1)?;
+        writeln!(out, "// Update #{nr} -- {update}")?;
+        if args.delta_output {
+            let current: BTreeSet<_> = af.enumerate_extensions()?.by_ref().collect()?;
+            for ext in current.difference(&previous) {
+                writeln!(out, "+{}", ext.format())?;
+            }
+            for ext in previous.difference(&current) {
+                writeln!(out, "-{}", ext.format())?;
+            }
+            previous = current;
+        } else {
+            write_extensions(&mut af, out)?;
+        }
+        end_answer(args, out)?;
+    }
+    print_profile(args, &af);
+    Ok(())
+}
+
+fn run_task_sample_extension<S: ArgumentationFrameworkSemantic>(
+    args: &SolveArgs,
+    updates: Updates,
+    out: &mut impl Write,
+) -> Result {
+    let mut af = load_initial_file_into_af::<S>(args)?;
+    // The model limit of the framework restricts the enumeration to k samples
+    let write = |af: &mut _, out: &mut _| match args.sample_k {
+        Some(_) => write_samples(af, out),
+        None => write_sample(af, SampleFormat::List, out),
+    };
+    begin_answer(args, out, 0)?;
+    write(&mut af, out)?;
+    end_answer(args, out)?;
+    let mut updates = UpdateStream::new(args, updates)?;
+    updates.restore(&mut af)?;
+    while let Some((nr, _)) = updates.apply_next(&mut af)? {
+        begin_answer(args, out, nr + 1)?;
+        write(&mut af, out)?;
+        end_answer(args, out)?;
+    }
+    print_profile(args, &af);
+    Ok(())
+}
+
+fn run_task_optimal_extension<S: ArgumentationFrameworkSemantic>(
+    args: &SolveArgs,
+    updates: Updates,
+    out: &mut impl Write,
+) -> Result {
+    let Problem::Optimal(objective) = args.task.problem() else {
+        unreachable!("Only called for optimization tasks");
+    };
+    let mut af = load_initial_file_into_af::<S>(args)?;
+    begin_answer(args, out, 0)?;
+    write_optimal(&mut af, objective, out)?;
+    end_answer(args, out)?;
+    let mut updates = UpdateStream::new(args, updates)?;
+    updates.restore(&mut af)?;
+    while let Some((nr, _)) = updates.apply_next(&mut af)? {
+        begin_answer(args, out, nr + 1)?;
+        write_optimal(&mut af, objective, out)?;
+        end_answer(args, out)?;
+    }
+    print_profile(args, &af);
+    Ok(())
+}
+
+fn run_task_decide_acceptance<S: ArgumentationFrameworkSemantic>(
+    args: &SolveArgs,
+    updates: Updates,
+    out: &mut impl Write,
+) -> Result {
+    let Some(id) = &args.argument else {
+        return Err(lib::Error::Logic(String::from(
+            "Acceptance tasks need the argument to query, see --argument",
+        ))
+        .into());
+    };
+    let af = load_initial_file_into_af::<S>(args)?;
+    let argument = symbols::find(af.arguments(), id)?.clone();
+    run_task_with_driver(args, af, Some(&argument), updates, out)
+}
+
+/// Run a task of the driver that queries no argument.
+fn run_task_unqueried<S: ArgumentationFrameworkSemantic>(
+    args: &SolveArgs,
+    updates: Updates,
+    out: &mut impl Write,
+) -> Result {
+    let af = load_initial_file_into_af::<S>(args)?;
+    run_task_with_driver(args, af, None, updates, out)
+}
+
+/// Answer the problem of the task like the driver does, again after every update.
+fn run_task_with_driver<S: ArgumentationFrameworkSemantic>(
+    args: &SolveArgs,
+    mut af: ArgumentationFramework<S>,
+    argument: Option<&symbols::Argument>,
+    updates: Updates,
+    out: &mut impl Write,
+) -> Result {
+    let problem = args.task.problem();
+    let write = |af: &mut ArgumentationFramework<S>, out: &mut _| {
+        driver::task::answer(af, problem, SampleFormat::List, argument, out)
+    };
+    begin_answer(args, out, 0)?;
+    write(&mut af, out)?;
+    end_answer(args, out)?;
+    let mut updates = UpdateStream::new(args, updates)?;
+    updates.restore(&mut af)?;
+    while let Some((nr, _)) = updates.apply_next(&mut af)? {
+        begin_answer(args, out, nr + 1)?;
+        write(&mut af, out)?;
+        end_answer(args, out)?;
+    }
+    print_profile(args, &af);
+    Ok(())
+}
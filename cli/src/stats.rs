@@ -0,0 +1,182 @@
+//! Statistics about the structure of a framework
+use std::collections::HashMap;
+
+use lib::argumentation_framework::{
+    decomposition::strongly_connected_components,
+    parse_initial_file,
+    symbols::{Argument, Attack},
+};
+use serde_json::json;
+
+use crate::{args::StatsArgs, Result};
+
+/// Analyze the framework and print its statistics.
+pub fn run(args: &StatsArgs) -> Result {
+    let content = std::fs::read_to_string(&args.file)?;
+    let (arguments, attacks) = parse_initial_file(&content)?;
+    let stats = Stats::new(&arguments, &attacks);
+    if args.json {
+        println!("{}", stats.to_json());
+    } else {
+        stats.print();
+    }
+    Ok(())
+}
+
+/// Structural statistics of a framework.
+#[derive(Debug, PartialEq)]
+struct Stats {
+    arguments: usize,
+    optional_arguments: usize,
+    attacks: usize,
+    optional_attacks: usize,
+    self_attacks: usize,
+    /// Attacks divided by the number of possible attacks
+    density: f64,
+    in_degree: Summary,
+    out_degree: Summary,
+    /// Sizes of all strongly connected components, largest first
+    scc_sizes: Vec<usize>,
+}
+
+/// Summary of a distribution.
+#[derive(Debug, PartialEq)]
+struct Summary {
+    min: usize,
+    median: usize,
+    mean: f64,
+    max: usize,
+}
+
+impl Stats {
+    fn new(arguments: &[Argument], attacks: &[Attack]) -> Self {
+        let index: HashMap<&str, usize> = arguments
+            .iter()
+            .enumerate()
+            .map(|(idx, arg)| (arg.id.as_str(), idx))
+            .collect();
+        let mut successors = vec![vec![]; arguments.len()];
+        let mut in_degree = vec![0; arguments.len()];
+        for attack in attacks {
+            // Attacks between undeclared arguments are ignored
+            if let (Some(&from), Some(&to)) = (
+                index.get(attack.from.as_str()),
+                index.get(attack.to.as_str()),
+            ) {
+                successors[from].push(to);
+                in_degree[to] += 1;
+            }
+        }
+        let out_degree: Vec<_> = successors.iter().map(Vec::len).collect();
+        let mut scc_sizes: Vec<_> = strongly_connected_components(&successors)
+            .iter()
+            .map(Vec::len)
+            .collect();
+        scc_sizes.sort_unstable_by(|a, b| b.cmp(a));
+        Stats {
+            arguments: arguments.len(),
+            optional_arguments: arguments.iter().filter(|arg| arg.optional).count(),
+            attacks: attacks.len(),
+            optional_attacks: attacks.iter().filter(|att| att.optional).count(),
+            self_attacks: attacks.iter().filter(|att| att.from == att.to).count(),
+            density: if arguments.is_empty() {
+                0.0
+            } else {
+                attacks.len() as f64 / (arguments.len() * arguments.len()) as f64
+            },
+            in_degree: Summary::of(in_degree),
+            out_degree: Summary::of(out_degree),
+            scc_sizes,
+        }
+    }
+
+    fn print(&self) {
+        println!(
+            "arguments     {} ({} optional)",
+            self.arguments, self.optional_arguments
+        );
+        println!(
+            "attacks       {} ({} optional)",
+            self.attacks, self.optional_attacks
+        );
+        println!("self-attacks  {}", self.self_attacks);
+        println!("density       {:.4}", self.density);
+        println!("in-degree     {}", self.in_degree);
+        println!("out-degree    {}", self.out_degree);
+        println!(
+            "sccs          {} (largest {}, singletons {})",
+            self.scc_sizes.len(),
+            self.scc_sizes.first().copied().unwrap_or_default(),
+            self.scc_sizes.iter().filter(|&&size| size == 1).count()
+        );
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        json!({
+            "arguments": self.arguments,
+            "optional_arguments": self.optional_arguments,
+            "attacks": self.attacks,
+            "optional_attacks": self.optional_attacks,
+            "self_attacks": self.self_attacks,
+            "density": self.density,
+            "in_degree": self.in_degree.to_json(),
+            "out_degree": self.out_degree.to_json(),
+            "scc_count": self.scc_sizes.len(),
+            "scc_sizes": self.scc_sizes,
+        })
+    }
+}
+
+impl Summary {
+    fn of(mut values: Vec<usize>) -> Self {
+        if values.is_empty() {
+            return Summary {
+                min: 0,
+                median: 0,
+                mean: 0.0,
+                max: 0,
+            };
+        }
+        values.sort_unstable();
+        Summary {
+            min: values[0],
+            median: values[values.len() / 2],
+            mean: values.iter().sum::<usize>() as f64 / values.len() as f64,
+            max: values[values.len() - 1],
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        json!({ "min": self.min, "median": self.median, "mean": self.mean, "max": self.max })
+    }
+}
+
+impl std::fmt::Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {} / median {} / mean {:.2} / max {}",
+            self.min, self.median, self.mean, self.max
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statistics_are_computed() {
+        let (arguments, attacks) =
+            parse_initial_file("a\nb\nc?\nd\n#\na b\nb a\nb c\nc c?\n").unwrap();
+        let stats = Stats::new(&arguments, &attacks);
+        assert_eq!(stats.arguments, 4);
+        assert_eq!(stats.optional_arguments, 1);
+        assert_eq!(stats.optional_attacks, 1);
+        assert_eq!(stats.self_attacks, 1);
+        assert_eq!(stats.density, 0.25);
+        assert_eq!(stats.out_degree.max, 2);
+        assert_eq!(stats.in_degree.min, 0);
+        assert_eq!(stats.scc_sizes, vec![2, 1, 1]);
+    }
+}
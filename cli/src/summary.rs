@@ -0,0 +1,97 @@
+//! CSV summary of a dynamic run
+use std::{
+    collections::BTreeSet,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    time::Instant,
+};
+
+use fallible_iterator::FallibleIterator;
+use lib::{
+    argumentation_framework::{semantics::ArgumentationFrameworkSemantic, ArgumentationFramework},
+    Framework,
+};
+
+use crate::Result;
+
+/// Writes one row per framework state: the initial framework and every update.
+///
+/// Rows are computed by enumerating all extensions, independently of the task
+/// being solved. `solve_ms` is the time this enumeration took.
+pub struct Summary<W> {
+    out: W,
+}
+
+impl Summary<BufWriter<File>> {
+    pub fn create(path: &Path) -> Result<Self> {
+        Summary::new(BufWriter::new(File::create(path)?))
+    }
+}
+
+impl<W: Write> Summary<W> {
+    pub fn new(mut out: W) -> Result<Self> {
+        writeln!(out, "update,patch,extensions,solve_ms,accepted_arguments")?;
+        Ok(Summary { out })
+    }
+
+    /// Add the row for the current state of `af`, reached by the given update.
+    ///
+    /// The initial framework has neither update number nor patch.
+    pub fn record<S: ArgumentationFrameworkSemantic>(
+        &mut self,
+        af: &mut ArgumentationFramework<S>,
+        update: Option<(usize, &str)>,
+    ) -> Result {
+        let start = Instant::now();
+        let mut extensions = 0;
+        let mut accepted = BTreeSet::new();
+        af.enumerate_extensions()?.by_ref().for_each(|ext| {
+            extensions += 1;
+            accepted.extend(ext.arguments().map(|arg| arg.id.clone()));
+            Ok(())
+        })?;
+        let solve_ms = start.elapsed().as_secs_f64() * 1000.0;
+        let (nr, patch) = match update {
+            Some((nr, patch)) => (nr.to_string(), quote(patch)),
+            None => (String::new(), String::new()),
+        };
+        writeln!(
+            self.out,
+            "{nr},{patch},{extensions},{solve_ms:.3},{}",
+            accepted.len()
+        )?;
+        // Keep the file useful if the run is interrupted
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+/// Quote a CSV field, as patches contain commas.
+fn quote(field: &str) -> String {
+    format!("\"{}\"", field.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_are_written_per_update() {
+        let mut af = ArgumentationFramework::<lib::semantics::Admissible>::new(
+            "arg(a). arg(b). att(a,b). opt(att(a,b)).",
+        )
+        .unwrap();
+        let mut out = vec![];
+        let mut summary = Summary::new(&mut out).unwrap();
+        summary.record(&mut af, None).unwrap();
+        af.update("+att(a,b).").unwrap();
+        summary.record(&mut af, Some((0, "+att(a,b)."))).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        let rows: Vec<Vec<_>> = csv.lines().map(|row| row.split(',').collect()).collect();
+        assert_eq!(rows[0].len(), 5);
+        assert_eq!((rows[1][0], rows[1][2], rows[1][4]), ("", "4", "2"));
+        assert_eq!(rows[2][..4], ["0", "\"+att(a", "b).\"", "2"]);
+        assert_eq!(rows[2][5], "1");
+    }
+}
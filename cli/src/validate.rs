@@ -0,0 +1,255 @@
+//! Validate input files without solving
+use std::{collections::HashMap, path::Path};
+
+use driver::PathOrStdin;
+use fallible_iterator::FallibleIterator;
+use lib::{
+    argumentation_framework::{
+        parse_iccma_file, parse_initial_file,
+        symbols::{Argument, Attack},
+        validation, Patch, PatchValidity,
+    },
+    semantics,
+};
+
+use crate::{
+    args::{SolveArgs, ValidateArgs},
+    solve, Error, Result,
+};
+
+/// Parse the initial file and every update line, reporting all problems.
+pub fn run(args: &ValidateArgs) -> Result {
+    match &args.update_file {
+        Some(update_file) => {
+            check_updates(&args.file, update_file, args.strict, args.iccma_compliant)
+        }
+        None => match load(&args.file, args.strict, args.iccma_compliant)? {
+            (_, _, 0) => Ok(()),
+            (_, _, problems) => Err(Error::Invalid(problems)),
+        },
+    }
+}
+
+/// Parse the initial file, printing its size and all declaration issues.
+///
+/// Declaration issues are only counted as problems if `strict` is set. Optional
+/// arguments and attacks are rejected if `iccma_compliant` is set.
+fn load(
+    file: &Path,
+    strict: bool,
+    iccma_compliant: bool,
+) -> Result<(Vec<Argument>, Vec<Attack>, usize)> {
+    let content = std::fs::read_to_string(file)?;
+    let (arguments, attacks) = if iccma_compliant {
+        parse_iccma_file(&content)?
+    } else {
+        parse_initial_file(&content)?
+    };
+    println!(
+        "{}: {} arguments, {} attacks",
+        file.display(),
+        arguments.len(),
+        attacks.len()
+    );
+    let issues = validation::declaration_issues(&arguments, &attacks);
+    let severity = if strict { "error" } else { "warning" };
+    for issue in &issues {
+        eprintln!("{}: {severity}: {issue}", file.display());
+    }
+    let problems = if strict { issues.len() } else { 0 };
+    Ok((arguments, attacks, problems))
+}
+
+/// Check that every update line parses and only touches optional arguments and
+/// attacks, enables new ones or disables arguments. All problems are reported with
+/// their line number.
+pub fn check_updates(
+    file: &Path,
+    update_file: &PathOrStdin,
+    strict: bool,
+    iccma_compliant: bool,
+) -> Result {
+    let (arguments, attacks, mut problems) = load(file, strict, iccma_compliant)?;
+    let mut targets = Targets::new(&arguments, &attacks);
+    let mut update_iter = update_file.lines(false)?.enumerate();
+    while let Some((nr, update)) = update_iter.next()? {
+        let patches = match Patch::parse_line(&update) {
+            Ok(patches) => patches,
+            Err(why) => {
+                eprintln!("{update_file}:{}: {why}", nr + 1);
+                problems += 1;
+                continue;
+            }
+        };
+        for problem in patches.iter().filter_map(|patch| targets.check(patch)) {
+            eprintln!("{update_file}:{}: {problem}", nr + 1);
+            problems += 1;
+        }
+    }
+    if problems > 0 {
+        return Err(Error::Invalid(problems));
+    }
+    println!("{update_file}: all updates are applicable");
+    Ok(())
+}
+
+/// Check every update line against the framework loaded like for solving.
+///
+/// Lines without problems are applied, so that later lines are checked against the
+/// updated framework. Patches that do not change the framework are warned about.
+pub fn validate_updates(args: &SolveArgs) -> Result {
+    // The semantics do not matter, as only the elements of the framework are checked
+    let mut af = solve::load_initial_file_into_af::<semantics::ConflictFree>(args)?;
+    let update_file = &args.update_file;
+    let mut problems = 0;
+    let mut update_iter = update_file.lines(false)?.enumerate();
+    while let Some((nr, update)) = update_iter.next()? {
+        let patches = match Patch::parse_line(&update) {
+            Ok(patches) => patches,
+            Err(why) => {
+                eprintln!("{update_file}:{}: {why}", nr + 1);
+                problems += 1;
+                continue;
+            }
+        };
+        // Patches are applied one by one, as later ones may involve elements added by earlier ones
+        for (applied, patch) in patches.iter().enumerate() {
+            match af.validate_patch(patch)? {
+                PatchValidity::Applicable => {}
+                PatchValidity::Unchanged => eprintln!(
+                    "{update_file}:{}: warning: {patch} does not change the framework",
+                    nr + 1
+                ),
+                PatchValidity::Invalid(why) => {
+                    eprintln!("{update_file}:{}: {why}", nr + 1);
+                    problems += 1;
+                    af.undo(applied)?;
+                    break;
+                }
+            }
+            af.apply_patch(patch)?;
+        }
+    }
+    if problems > 0 {
+        return Err(Error::Invalid(problems));
+    }
+    println!("{update_file}: all updates are applicable");
+    Ok(())
+}
+
+/// Arguments and attacks of the framework, mapped to whether they are optional.
+struct Targets {
+    arguments: HashMap<String, bool>,
+    attacks: HashMap<(String, String), bool>,
+}
+
+impl Targets {
+    fn new(arguments: &[Argument], attacks: &[Attack]) -> Self {
+        Targets {
+            arguments: arguments
+                .iter()
+                .map(|arg| (arg.id.clone(), arg.optional))
+                .collect(),
+            attacks: attacks
+                .iter()
+                .map(|att| ((att.from.clone(), att.to.clone()), att.optional))
+                .collect(),
+        }
+    }
+
+    /// Describe why the patch cannot be applied, if it cannot.
+    ///
+    /// Enabled arguments and attacks that do not exist yet are added as optional ones
+    /// and disabled arguments become optional, like the solver does.
+    fn check(&mut self, patch: &Patch) -> Option<String> {
+        let (target, optional) = match patch {
+            Patch::EnableArgument(arg) | Patch::DisableArgument(arg) => {
+                (arg.to_string(), self.arguments.get(&arg.id))
+            }
+            Patch::EnableAttack(att) | Patch::DisableAttack(att) => (
+                att.to_string(),
+                self.attacks.get(&(att.from.clone(), att.to.clone())),
+            ),
+        };
+        match (optional, patch) {
+            (Some(true), _) => None,
+            (Some(false), Patch::DisableArgument(arg)) => {
+                self.arguments.insert(arg.id.clone(), true);
+                None
+            }
+            (Some(false), _) => Some(format!("{target} is not optional and cannot be changed")),
+            (None, Patch::EnableArgument(arg)) => {
+                self.arguments.insert(arg.id.clone(), true);
+                None
+            }
+            (None, Patch::EnableAttack(att)) => {
+                let unknown = [&att.from, &att.to]
+                    .into_iter()
+                    .find(|id| !self.arguments.contains_key(*id));
+                if let Some(id) = unknown {
+                    return Some(format!("{target} involves the unknown argument {id}"));
+                }
+                self.attacks
+                    .insert((att.from.clone(), att.to.clone()), true);
+                None
+            }
+            (None, _) => Some(format!("{target} does not exist")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patches_must_target_optional_elements() {
+        let (arguments, attacks) = parse_initial_file("a\nb?\n#\na b\nb a?\n").unwrap();
+        let mut targets = Targets::new(&arguments, &attacks);
+        let mut check = |line: &str| targets.check(&Patch::parse_line(line).unwrap()[0]);
+        assert_eq!(check("+b"), None);
+        assert_eq!(check("-b a"), None);
+        assert_eq!(check("-c").as_deref(), Some("arg(c) does not exist"));
+        assert_eq!(
+            check("+a b").as_deref(),
+            Some("att(a,b) is not optional and cannot be changed")
+        );
+    }
+
+    #[test]
+    fn enabled_unknown_elements_are_added() {
+        let (arguments, attacks) = parse_initial_file(
+            "a
+#
+",
+        )
+        .unwrap();
+        let mut targets = Targets::new(&arguments, &attacks);
+        let mut check = |line: &str| targets.check(&Patch::parse_line(line).unwrap()[0]);
+        assert_eq!(
+            check("+c a").as_deref(),
+            Some("att(c,a) involves the unknown argument c")
+        );
+        assert_eq!(check("+c"), None);
+        assert_eq!(check("+c a"), None);
+        assert_eq!(check("-c a"), None);
+    }
+
+    #[test]
+    fn disabled_arguments_become_optional() {
+        let (arguments, attacks) = parse_initial_file(
+            "a
+#
+",
+        )
+        .unwrap();
+        let mut targets = Targets::new(&arguments, &attacks);
+        let mut check = |line: &str| targets.check(&Patch::parse_line(line).unwrap()[0]);
+        assert_eq!(
+            check("+a").as_deref(),
+            Some("arg(a) is not optional and cannot be changed")
+        );
+        assert_eq!(check("-a"), None);
+        assert_eq!(check("+a"), None);
+    }
+}
@@ -0,0 +1,50 @@
+//! Check the known relations between the semantics on a framework
+use fallible_iterator::FallibleIterator;
+use lib::{
+    argumentation_framework::{verification::Relation, ArgumentationFramework},
+    semantics, Framework, GenericExtension,
+};
+
+use crate::{args::VerifyArgs, Error, Result};
+
+/// Report every relation on the initial framework and after every update.
+pub fn run(args: &VerifyArgs) -> Result {
+    let content = std::fs::read_to_string(&args.file)?;
+    // The semantics of the framework only matters for updates, which are checked by all
+    let mut af = ArgumentationFramework::<semantics::Admissible>::new_with_config(
+        &content,
+        args.solver.framework_config(),
+    )?;
+    println!("// Initial");
+    let mut violated = report(&af)?;
+    if let Some(update_file) = &args.update_file {
+        let mut updates = update_file.lines(false)?.enumerate();
+        while let Some((nr, update)) = updates.next()? {
+            af.update(&update)?;
+            println!("// Update #{nr} -- {update}");
+            violated += report(&af)?;
+        }
+    }
+    if violated > 0 {
+        return Err(Error::Verify(violated));
+    }
+    Ok(())
+}
+
+/// Print the outcome of every relation, returning the number of violated ones.
+fn report(af: &ArgumentationFramework<semantics::Admissible>) -> Result<usize> {
+    let violations = af.verify_relations()?;
+    for relation in Relation::ALL {
+        match violations
+            .iter()
+            .find(|violation| violation.relation == relation)
+        {
+            Some(violation) => match &violation.witness {
+                Some(witness) => println!("FAIL  {relation}: counterexample {}", witness.format()),
+                None => println!("FAIL  {relation}"),
+            },
+            None => println!("ok    {relation}"),
+        }
+    }
+    Ok(violations.len())
+}
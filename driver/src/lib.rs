@@ -0,0 +1,10 @@
+//! Shared driver of the command line interfaces
+//!
+//! Solving a task on a framework, answering it again after every update line and
+//! writing the answers in the formats expected by the ICCMA.
+pub mod output;
+pub mod path_or_stdin;
+pub mod task;
+
+pub use path_or_stdin::PathOrStdin;
+pub use task::Problem;
@@ -0,0 +1,77 @@
+//! Writing the answers of tasks
+use std::io::Write;
+
+use fallible_iterator::FallibleIterator;
+use lib::{Error, Framework, GenericExtension, Objective, Result};
+
+/// How single extensions of SE tasks are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SampleFormat {
+    /// As list, i.e. `[a1,a2]`, like ICCMA'19 and '21
+    #[default]
+    List,
+    /// As witness, i.e. `w a1 a2`, like ICCMA'23
+    Witness,
+}
+
+/// Write every extension on its own line.
+pub fn write_extensions<F: Framework>(af: &mut F, out: &mut impl Write) -> Result {
+    af.enumerate_extensions()?
+        .by_ref()
+        .for_each(|ext| Ok(writeln!(out, "{}", ext.format())?))
+}
+
+/// Write a single extension, or `NO` if there is none.
+pub fn write_sample<F: Framework>(
+    af: &mut F,
+    format: SampleFormat,
+    out: &mut impl Write,
+) -> Result {
+    match (af.sample_extension()?, format) {
+        (Some(ext), SampleFormat::List) => writeln!(out, "{}", ext.format())?,
+        (Some(ext), SampleFormat::Witness) => writeln!(out, "{}", ext.format_witness())?,
+        (None, _) => writeln!(out, "NO")?,
+    }
+    Ok(())
+}
+
+/// Write an extension of optimal size, or `NO` if there is none.
+pub fn write_optimal<F: Framework>(
+    af: &mut F,
+    objective: Objective,
+    out: &mut impl Write,
+) -> Result {
+    match af.optimal_extension(objective)? {
+        Some(ext) => writeln!(out, "{}", ext.format())?,
+        None => writeln!(out, "NO")?,
+    }
+    Ok(())
+}
+
+/// Write a set of arguments like an extension, or `NO` if there is no extension.
+pub fn write_arguments(arguments: Option<impl GenericExtension>, out: &mut impl Write) -> Result {
+    match arguments {
+        Some(arguments) => writeln!(out, "{}", arguments.format())?,
+        None => writeln!(out, "NO")?,
+    }
+    Ok(())
+}
+
+/// Write the answer to a decision problem, `YES` or `NO`.
+pub fn write_decision(accepted: bool, out: &mut impl Write) -> Result {
+    writeln!(out, "{}", if accepted { "YES" } else { "NO" })?;
+    Ok(())
+}
+
+/// Write all extensions found within the model limit, or `NO` if there is none.
+pub fn write_samples<F: Framework>(af: &mut F, out: &mut impl Write) -> Result {
+    let mut found = false;
+    af.enumerate_extensions()?.by_ref().for_each(|ext| {
+        found = true;
+        writeln!(out, "{}", ext.format()).map_err(Error::from)
+    })?;
+    if !found {
+        writeln!(out, "NO")?;
+    }
+    Ok(())
+}
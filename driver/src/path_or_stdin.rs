@@ -0,0 +1,93 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use fallible_iterator::FallibleIterator;
+use lib::{
+    argumentation_framework::updates::{Follow, UpdateLines},
+    Error, Result,
+};
+
+/// Path to a file, or stdin if given as `-` on the command line.
+#[derive(Debug, Clone)]
+pub enum PathOrStdin {
+    Path(PathBuf),
+    Stdin,
+}
+
+impl PathOrStdin {
+    /// Read either the underlying path or stdin line by line, see [`UpdateLines`]
+    ///
+    /// Unless `follow` is set, an empty line ends the iterator. Otherwise empty lines are
+    /// skipped and reaching the end of a file waits for new lines to be appended, while
+    /// stdin is read until it is closed.
+    pub fn lines(
+        &self,
+        follow: bool,
+    ) -> Result<impl FallibleIterator<Item = String, Error = Error>> {
+        let (reader, follow): (Box<dyn BufRead>, _) = match self {
+            PathOrStdin::Path(path) => (
+                Box::new(BufReader::new(File::open(path)?)),
+                if follow { Follow::File } else { Follow::No },
+            ),
+            PathOrStdin::Stdin => (
+                Box::new(std::io::stdin().lock()),
+                if follow { Follow::Stream } else { Follow::No },
+            ),
+        };
+        Ok(UpdateLines::new(reader, follow))
+    }
+}
+
+impl FromStr for PathOrStdin {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "-" {
+            Ok(Self::Stdin)
+        } else {
+            let path: Result<PathBuf, std::convert::Infallible> = s.parse();
+            Ok(Self::Path(path.unwrap()))
+        }
+    }
+}
+
+impl std::fmt::Display for PathOrStdin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathOrStdin::Path(path) => write!(f, "{}", path.to_string_lossy()),
+            PathOrStdin::Stdin => write!(f, "-"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, thread};
+
+    use lib::argumentation_framework::updates::FOLLOW_INTERVAL;
+
+    use super::*;
+
+    #[test]
+    fn followed_files_wait_for_complete_lines() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, "+a\n-").unwrap();
+        let mut lines = PathOrStdin::Path(file.path().to_owned())
+            .lines(true)
+            .unwrap();
+        assert_eq!(lines.next().unwrap(), Some(String::from("+a")));
+        let writer = thread::spawn(move || {
+            thread::sleep(2 * FOLLOW_INTERVAL);
+            write!(file, "a\n\n+b\n").unwrap();
+            file
+        });
+        assert_eq!(lines.next().unwrap(), Some(String::from("-a")));
+        // Empty lines do not end followed files
+        assert_eq!(lines.next().unwrap(), Some(String::from("+b")));
+        writer.join().unwrap();
+    }
+}
@@ -0,0 +1,147 @@
+//! Answering tasks on static and dynamic frameworks
+use std::io::Write;
+
+use fallible_iterator::FallibleIterator;
+use lib::{Error, Framework, GenericExtension, Objective, Result};
+
+use crate::output::{self, SampleFormat};
+
+/// The problem of a task, independent of the semantics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Problem {
+    /// Count the extensions, `CE`
+    Count,
+    /// Enumerate the extensions, `EE`
+    Enumerate,
+    /// Give some extension, `SE`
+    Sample,
+    /// Decide whether there is an extension, `EX`
+    ExistsExtension,
+    /// Give an extension of optimal size
+    Optimal(Objective),
+    /// Decide whether some extension contains the queried argument, `DC`
+    Credulous,
+    /// Decide whether every extension contains the queried argument, `DS`
+    Skeptical,
+    /// Give the arguments contained in some extension, `EC`
+    CredulousArguments,
+    /// Give the arguments contained in every extension, `ES`
+    SkepticalArguments,
+}
+
+/// Argument type of the extensions of a framework.
+pub type Argument<F> = <<F as Framework>::Extension as GenericExtension>::Arg;
+
+/// Write the answer to the problem for the current framework.
+///
+/// Acceptance problems fail without an `argument` to query.
+pub fn answer<F: Framework>(
+    af: &mut F,
+    problem: Problem,
+    format: SampleFormat,
+    argument: Option<&Argument<F>>,
+    out: &mut impl Write,
+) -> Result {
+    let query = || {
+        argument.ok_or_else(|| {
+            Error::Logic(String::from(
+                "Acceptance problems need an argument to query",
+            ))
+        })
+    };
+    match problem {
+        Problem::Count => writeln!(out, "{}", af.count_extensions()?)?,
+        Problem::Enumerate => output::write_extensions(af, out)?,
+        Problem::Sample => output::write_sample(af, format, out)?,
+        Problem::ExistsExtension => output::write_decision(af.has_extension()?, out)?,
+        Problem::Optimal(objective) => output::write_optimal(af, objective, out)?,
+        Problem::Credulous => {
+            let accepted = af.is_credulous_accepted(query()?)?;
+            output::write_decision(accepted, out)?
+        }
+        Problem::Skeptical => {
+            let accepted = af.is_skeptical_accepted(query()?)?;
+            output::write_decision(accepted, out)?
+        }
+        Problem::CredulousArguments => {
+            output::write_arguments(af.credulously_accepted_arguments()?, out)?
+        }
+        Problem::SkepticalArguments => {
+            output::write_arguments(af.skeptically_accepted_arguments()?, out)?
+        }
+    }
+    Ok(())
+}
+
+/// Answer the problem for the framework and again after every update line.
+///
+/// Output is flushed after every answer, as consumers usually wait for it.
+pub fn run<F: Framework>(
+    af: &mut F,
+    problem: Problem,
+    format: SampleFormat,
+    argument: Option<&Argument<F>>,
+    mut updates: impl FallibleIterator<Item = String, Error = Error>,
+    out: &mut impl Write,
+) -> Result {
+    answer(af, problem, format, argument, out)?;
+    out.flush()?;
+    while let Some(update) = updates.next()? {
+        log::trace!("Found update: {update:?}");
+        af.update(&update)?;
+        answer(af, problem, format, argument, out)?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use lib::{argumentation_framework::ArgumentationFramework, semantics::Admissible};
+
+    use super::*;
+
+    #[test]
+    fn problems_are_answered_after_every_update() {
+        let run = |problem, format| {
+            let mut af = ArgumentationFramework::<Admissible>::new(
+                "arg(a). arg(b). att(a,b). att(b,a). opt(att(b,a)).",
+            )
+            .unwrap();
+            let updates = ["+att(b,a).", "-att(b,a)."].map(String::from);
+            let mut out = vec![];
+            run(
+                &mut af,
+                problem,
+                format,
+                Some(&lib::argumentation_framework::symbols::Argument::new(
+                    "b", false,
+                )),
+                fallible_iterator::convert(updates.into_iter().map(Ok)),
+                &mut out,
+            )
+            .unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(run(Problem::Count, SampleFormat::List), "2\n3\n2\n");
+        assert_eq!(
+            run(Problem::ExistsExtension, SampleFormat::List),
+            "YES\nYES\nYES\n"
+        );
+        assert_eq!(run(Problem::Credulous, SampleFormat::List), "NO\nYES\nNO\n");
+        // The empty set is admissible, so nothing is skeptically accepted
+        assert_eq!(run(Problem::Skeptical, SampleFormat::List), "NO\nNO\nNO\n");
+        assert_eq!(
+            run(Problem::CredulousArguments, SampleFormat::List),
+            "[a]\n[a,b]\n[a]\n"
+        );
+        assert_eq!(
+            run(Problem::SkepticalArguments, SampleFormat::List),
+            "[]\n[]\n[]\n"
+        );
+        assert_eq!(
+            run(Problem::Sample, SampleFormat::Witness).lines().count(),
+            3
+        );
+    }
+}
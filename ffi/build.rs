@@ -0,0 +1,19 @@
+//! Generate the C header at build time.
+//!
+//! The header is written to `$HEADER_DIR` if set, otherwise to `$OUT_DIR`.
+use std::{env, path::PathBuf};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-env-changed=HEADER_DIR");
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo");
+    let header_dir = env::var_os("HEADER_DIR")
+        .or_else(|| env::var_os("OUT_DIR"))
+        .map(PathBuf::from)
+        .expect("OUT_DIR is set by cargo");
+    let config = cbindgen::Config::from_root_or_default(&crate_dir);
+    cbindgen::generate_with_config(&crate_dir, config)
+        .expect("Generating the C header")
+        .write_to_file(header_dir.join("dasp.h"));
+}
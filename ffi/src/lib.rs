@@ -0,0 +1,386 @@
+//! C interface for embedding DASP in other solvers
+//!
+//! Frameworks are opaque handles created by [`dasp_framework_new`] and destroyed by
+//! [`dasp_framework_free`]. Every other function returns a [`DaspStatus`], results are
+//! written to out-pointers. On [`DaspStatus::Error`], the message is available from
+//! [`dasp_last_error`] on the same thread. Strings are NUL-terminated UTF-8.
+//!
+//! The header `dasp.h` is generated during the build and written to `$HEADER_DIR`
+//! if set, otherwise to cargo's `OUT_DIR`.
+use std::{
+    cell::RefCell,
+    ffi::{c_char, c_void, CStr, CString},
+    ops::ControlFlow,
+    panic::{self, AssertUnwindSafe},
+    ptr,
+};
+
+use lib::{
+    argumentation_framework::{
+        semantics::ArgumentationFrameworkSemantic, symbols, ArgumentationFramework, Extension,
+    },
+    semantics, Error, Framework, Result,
+};
+
+/// Semantics to solve a framework under.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaspSemantics {
+    Admissible,
+    Complete,
+    Grounded,
+    Stable,
+}
+
+/// Outcome of a call.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaspStatus {
+    Ok,
+    /// See [`dasp_last_error`] for the reason
+    Error,
+}
+
+/// Called with the arguments of every extension, their number and the user data.
+///
+/// The argument strings are only valid during the call. Returning `false` stops
+/// the enumeration.
+pub type DaspExtensionCallback =
+    extern "C" fn(arguments: *const *const c_char, len: usize, user_data: *mut c_void) -> bool;
+
+/// A framework under some semantics, opaque to C.
+pub struct DaspFramework {
+    solver: Box<dyn Solver>,
+}
+
+/// Framework of any semantics.
+trait Solver {
+    fn update(&mut self, line: &str) -> Result;
+    fn for_each_extension(&mut self, f: &mut dyn FnMut(&Extension) -> ControlFlow<()>) -> Result;
+    fn count(&mut self) -> Result<usize>;
+    fn credulous(&mut self, argument: &symbols::Argument) -> Result<bool>;
+    fn skeptical(&mut self, argument: &symbols::Argument) -> Result<bool>;
+}
+
+impl<S: ArgumentationFrameworkSemantic> Solver for ArgumentationFramework<S> {
+    fn update(&mut self, line: &str) -> Result {
+        Framework::update(self, line)
+    }
+    fn for_each_extension(&mut self, f: &mut dyn FnMut(&Extension) -> ControlFlow<()>) -> Result {
+        ArgumentationFramework::for_each_extension(self, f)?;
+        Ok(())
+    }
+    fn count(&mut self) -> Result<usize> {
+        self.count_extensions()
+    }
+    fn credulous(&mut self, argument: &symbols::Argument) -> Result<bool> {
+        self.is_credulous_accepted(argument)
+    }
+    fn skeptical(&mut self, argument: &symbols::Argument) -> Result<bool> {
+        self.is_skeptical_accepted(argument)
+    }
+}
+
+thread_local! {
+    /// Message of the last error on this thread
+    static LAST_ERROR: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', "")).expect("NUL bytes are removed");
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// The error with all its sources, as some variants only describe their kind.
+fn describe(why: &dyn std::error::Error) -> String {
+    let mut message = why.to_string();
+    let mut source = why.source();
+    while let Some(inner) = source {
+        message += &format!(": {inner}");
+        source = inner.source();
+    }
+    message
+}
+
+/// Run `f`, turning errors and panics into a status, as neither may cross into C.
+fn guard(f: impl FnOnce() -> Result) -> DaspStatus {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => DaspStatus::Ok,
+        Ok(Err(why)) => {
+            set_last_error(describe(&why));
+            DaspStatus::Error
+        }
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| String::from("unknown cause"));
+            set_last_error(format!("panicked: {message}"));
+            DaspStatus::Error
+        }
+    }
+}
+
+/// # Safety
+///
+/// `ptr` must be null or point to a NUL-terminated string valid for `'a`.
+unsafe fn str_arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a str> {
+    if ptr.is_null() {
+        return Err(Error::Logic(format!("{name} is null")));
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|why| Error::Logic(format!("{name} is not UTF-8: {why}")))
+}
+
+/// # Safety
+///
+/// `ptr` must be null or point to a framework created by [`dasp_framework_new`]
+/// that is not used elsewhere for `'a`.
+unsafe fn framework_arg<'a>(ptr: *mut DaspFramework) -> Result<&'a mut DaspFramework> {
+    ptr.as_mut()
+        .ok_or_else(|| Error::Logic(String::from("framework is null")))
+}
+
+/// # Safety
+///
+/// `ptr` must be null or valid for writes.
+unsafe fn write_out<T>(ptr: *mut T, value: T) -> Result {
+    if ptr.is_null() {
+        return Err(Error::Logic(String::from("out-pointer is null")));
+    }
+    ptr.write(value);
+    Ok(())
+}
+
+/// Load the initial framework in APX or TGF format and write its handle to `framework`.
+///
+/// # Safety
+///
+/// `input` must be a NUL-terminated string, `framework` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn dasp_framework_new(
+    input: *const c_char,
+    semantics: DaspSemantics,
+    framework: *mut *mut DaspFramework,
+) -> DaspStatus {
+    fn boxed<S: ArgumentationFrameworkSemantic + 'static>(input: &str) -> Result<Box<dyn Solver>> {
+        Ok(Box::new(ArgumentationFramework::<S>::new(input)?))
+    }
+    guard(|| {
+        let input = str_arg(input, "input")?;
+        let solver = match semantics {
+            DaspSemantics::Admissible => boxed::<semantics::Admissible>(input)?,
+            DaspSemantics::Complete => boxed::<semantics::Complete>(input)?,
+            DaspSemantics::Grounded => boxed::<semantics::Ground>(input)?,
+            DaspSemantics::Stable => boxed::<semantics::Stable>(input)?,
+        };
+        let handle = Box::into_raw(Box::new(DaspFramework { solver }));
+        write_out(framework, handle).inspect_err(|_| drop(Box::from_raw(handle)))
+    })
+}
+
+/// Destroy the framework. Null is ignored.
+///
+/// # Safety
+///
+/// `framework` must be null or created by [`dasp_framework_new`] and not freed before.
+#[no_mangle]
+pub unsafe extern "C" fn dasp_framework_free(framework: *mut DaspFramework) {
+    if !framework.is_null() {
+        drop(Box::from_raw(framework));
+    }
+}
+
+/// Apply an update line in APXM or TGFM format.
+///
+/// # Safety
+///
+/// `framework` must be a valid handle, `line` a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn dasp_framework_update(
+    framework: *mut DaspFramework,
+    line: *const c_char,
+) -> DaspStatus {
+    guard(|| {
+        let framework = framework_arg(framework)?;
+        framework.solver.update(str_arg(line, "line")?)
+    })
+}
+
+/// Call `callback` with every extension, until it returns `false`.
+///
+/// # Safety
+///
+/// `framework` must be a valid handle. `user_data` is passed to `callback` unchanged.
+#[no_mangle]
+pub unsafe extern "C" fn dasp_framework_enumerate(
+    framework: *mut DaspFramework,
+    callback: DaspExtensionCallback,
+    user_data: *mut c_void,
+) -> DaspStatus {
+    guard(|| {
+        let framework = framework_arg(framework)?;
+        let mut failed = None;
+        framework.solver.for_each_extension(&mut |ext| {
+            let ids: Result<Vec<_>, _> = ext
+                .arguments()
+                .map(|arg| CString::new(arg.id.as_str()))
+                .collect();
+            match ids {
+                Ok(ids) => {
+                    let ptrs: Vec<*const c_char> = ids.iter().map(|id| id.as_ptr()).collect();
+                    if callback(ptrs.as_ptr(), ptrs.len(), user_data) {
+                        ControlFlow::Continue(())
+                    } else {
+                        ControlFlow::Break(())
+                    }
+                }
+                Err(why) => {
+                    failed = Some(why);
+                    ControlFlow::Break(())
+                }
+            }
+        })?;
+        match failed {
+            Some(why) => Err(Error::Logic(format!("Argument id contains NUL: {why}"))),
+            None => Ok(()),
+        }
+    })
+}
+
+/// Count the extensions and write their number to `count`.
+///
+/// # Safety
+///
+/// `framework` must be a valid handle, `count` valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn dasp_framework_count(
+    framework: *mut DaspFramework,
+    count: *mut usize,
+) -> DaspStatus {
+    guard(|| {
+        let framework = framework_arg(framework)?;
+        write_out(count, framework.solver.count()?)
+    })
+}
+
+/// Decide whether the argument is in some extension.
+///
+/// # Safety
+///
+/// `framework` must be a valid handle, `argument` a NUL-terminated string and
+/// `accepted` valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn dasp_framework_credulous(
+    framework: *mut DaspFramework,
+    argument: *const c_char,
+    accepted: *mut bool,
+) -> DaspStatus {
+    guard(|| {
+        let framework = framework_arg(framework)?;
+        let argument = symbols::Argument::new(str_arg(argument, "argument")?, false);
+        write_out(accepted, framework.solver.credulous(&argument)?)
+    })
+}
+
+/// Decide whether the argument is in every extension.
+///
+/// # Safety
+///
+/// `framework` must be a valid handle, `argument` a NUL-terminated string and
+/// `accepted` valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn dasp_framework_skeptical(
+    framework: *mut DaspFramework,
+    argument: *const c_char,
+    accepted: *mut bool,
+) -> DaspStatus {
+    guard(|| {
+        let framework = framework_arg(framework)?;
+        let argument = symbols::Argument::new(str_arg(argument, "argument")?, false);
+        write_out(accepted, framework.solver.skeptical(&argument)?)
+    })
+}
+
+/// Message of the last error on the calling thread, or null if there was none.
+///
+/// The string stays valid until the next error on the same thread.
+#[no_mangle]
+pub extern "C" fn dasp_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern "C" fn collect(
+        arguments: *const *const c_char,
+        len: usize,
+        user_data: *mut c_void,
+    ) -> bool {
+        let extensions = unsafe { &mut *(user_data as *mut Vec<Vec<String>>) };
+        let arguments = unsafe { std::slice::from_raw_parts(arguments, len) };
+        let mut extension: Vec<_> = arguments
+            .iter()
+            .map(|&arg| unsafe { CStr::from_ptr(arg) }.to_str().unwrap().to_owned())
+            .collect();
+        extension.sort();
+        extensions.push(extension);
+        true
+    }
+
+    #[test]
+    fn frameworks_are_solved_through_the_c_interface() {
+        let input = CString::new("arg(a). arg(b). att(a,b). opt(att(a,b)).").unwrap();
+        let mut framework = ptr::null_mut();
+        unsafe {
+            let status =
+                dasp_framework_new(input.as_ptr(), DaspSemantics::Admissible, &mut framework);
+            assert_eq!(status, DaspStatus::Ok);
+            let mut count = 0;
+            assert_eq!(dasp_framework_count(framework, &mut count), DaspStatus::Ok);
+            assert_eq!(count, 4);
+            let update = CString::new("+att(a,b).").unwrap();
+            assert_eq!(
+                dasp_framework_update(framework, update.as_ptr()),
+                DaspStatus::Ok
+            );
+            let mut extensions: Vec<Vec<String>> = vec![];
+            let user_data = &mut extensions as *mut _ as *mut c_void;
+            assert_eq!(
+                dasp_framework_enumerate(framework, collect, user_data),
+                DaspStatus::Ok
+            );
+            extensions.sort();
+            assert_eq!(extensions, vec![vec![], vec![String::from("a")]]);
+            let (b, mut accepted) = (CString::new("b").unwrap(), true);
+            assert_eq!(
+                dasp_framework_credulous(framework, b.as_ptr(), &mut accepted),
+                DaspStatus::Ok
+            );
+            assert!(!accepted);
+            dasp_framework_free(framework);
+        }
+    }
+
+    #[test]
+    fn errors_are_reported_per_thread() {
+        let input = CString::new("arg(a").unwrap();
+        let mut framework = ptr::null_mut();
+        let status =
+            unsafe { dasp_framework_new(input.as_ptr(), DaspSemantics::Stable, &mut framework) };
+        assert_eq!(status, DaspStatus::Error);
+        assert!(framework.is_null());
+        let message = unsafe { CStr::from_ptr(dasp_last_error()) };
+        assert!(!message.to_str().unwrap().is_empty());
+        let status = unsafe { dasp_framework_update(ptr::null_mut(), input.as_ptr()) };
+        assert_eq!(status, DaspStatus::Error);
+    }
+}
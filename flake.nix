@@ -117,6 +117,18 @@
         lib.overrides.fixClingoLinking = {
           buildInputs = [(clingoFixed common.pkgs)];
         };
+        driver.depsOverrides.fixClingoSysBuild = {
+          CLINGO_LIBRARY_PATH = "${clingoFixed common.pkgs}/lib";
+        };
+        driver.overrides.fixClingoLinking = {
+          buildInputs = [(clingoFixed common.pkgs)];
+        };
+        dasp-ffi.depsOverrides.fixClingoSysBuild = {
+          CLINGO_LIBRARY_PATH = "${clingoFixed common.pkgs}/lib";
+        };
+        dasp-ffi.overrides.fixClingoLinking = {
+          buildInputs = [(clingoFixed common.pkgs)];
+        };
         cli-iccma.depsOverrides.fixClingoSysBuild = {
           CLINGO_LIBRARY_PATH = "${clingoFixed common.pkgs}/lib";
         };
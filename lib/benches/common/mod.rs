@@ -0,0 +1,18 @@
+//! Helpers shared by the benchmarks
+
+/// Xorshift generator, so that every run benchmarks the same instances.
+pub struct XorShift(u64);
+
+impl XorShift {
+    pub fn new() -> Self {
+        XorShift(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Next number below `bound`.
+    pub fn below(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 as usize % bound
+    }
+}
@@ -0,0 +1,159 @@
+use common::XorShift;
+use criterion::{criterion_group, criterion_main, Criterion};
+use fallible_iterator::FallibleIterator;
+use lib::{
+    argumentation_framework::{
+        parse_initial_file, semantics::ArgumentationFrameworkSemantic, symbols::Attack,
+        ArgumentationFramework, FrameworkConfig, Patch,
+    },
+    semantics::{Admissible, Complete},
+    Framework,
+};
+
+mod common;
+
+/// A generated instance.
+struct Instance {
+    name: &'static str,
+    /// Content of the initial APX file
+    input: String,
+    /// Optional attacks, to be toggled by updates
+    optional: Vec<Attack>,
+}
+
+impl Instance {
+    /// Random framework with `args` arguments, where each attack exists with probability
+    /// `1 / sparsity`. Every tenth attack is optional.
+    fn generate(name: &'static str, args: usize, sparsity: usize) -> Self {
+        let mut rng = XorShift::new();
+        let mut input = String::new();
+        let mut optional = vec![];
+        for arg in 0..args {
+            input += &format!("arg(a{arg}).\n");
+        }
+        for from in 0..args {
+            for to in 0..args {
+                if rng.below(sparsity) != 0 {
+                    continue;
+                }
+                input += &format!("att(a{from},a{to}).\n");
+                if rng.below(10) == 0 {
+                    input += &format!("opt(att(a{from},a{to})).\n");
+                    optional.push(Attack::new(format!("a{from}"), format!("a{to}"), false));
+                }
+            }
+        }
+        Instance {
+            name,
+            input,
+            optional,
+        }
+    }
+
+    fn framework<S: ArgumentationFrameworkSemantic>(
+        &self,
+        decompose: bool,
+    ) -> ArgumentationFramework<S> {
+        self.framework_with_config(FrameworkConfig {
+            decompose,
+            ..Default::default()
+        })
+    }
+
+    fn framework_with_config<S: ArgumentationFrameworkSemantic>(
+        &self,
+        config: FrameworkConfig,
+    ) -> ArgumentationFramework<S> {
+        // A single thread keeps measurements comparable between machines
+        let config = FrameworkConfig {
+            threads: 1.try_into().unwrap(),
+            ..config
+        };
+        ArgumentationFramework::new_with_config(&self.input, config).expect("Creating AF")
+    }
+}
+
+fn instances() -> Vec<Instance> {
+    vec![
+        Instance::generate("n=40 sparse", 40, 12),
+        Instance::generate("n=200 dense", 200, 8),
+    ]
+}
+
+fn count<S: ArgumentationFrameworkSemantic>(af: &mut ArgumentationFramework<S>) -> usize {
+    af.enumerate_extensions()
+        .expect("Enumerating extensions")
+        .by_ref()
+        .count()
+        .expect("Counting extensions")
+}
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for instance in instances() {
+        group.bench_function(instance.name, |b| {
+            b.iter(|| parse_initial_file(&instance.input).expect("Parsing instance"))
+        });
+    }
+    group.finish();
+}
+
+fn ground(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ground");
+    for instance in instances() {
+        group.bench_function(instance.name, |b| {
+            b.iter(|| instance.framework::<Admissible>(false))
+        });
+    }
+    group.finish();
+}
+
+fn enumerate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("enumerate");
+    // Some iterations on the dense instance take seconds
+    group.sample_size(10);
+    for instance in instances() {
+        let mut af = instance.framework::<Admissible>(false);
+        group.bench_function(format!("ad {}", instance.name), |b| {
+            b.iter(|| count(&mut af))
+        });
+        let mut af = instance.framework::<Complete>(true);
+        group.bench_function(format!("co decomposed {}", instance.name), |b| {
+            b.iter(|| count(&mut af))
+        });
+    }
+    group.finish();
+}
+
+fn update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("update and re-solve");
+    // Some iterations on the dense instance take seconds
+    group.sample_size(10);
+    for instance in instances() {
+        for warm_start in [false, true] {
+            let mut af = instance.framework_with_config::<Admissible>(FrameworkConfig {
+                warm_start,
+                ..Default::default()
+            });
+            let mut toggle = instance.optional.iter().cycle().enumerate();
+            let name = if warm_start { "warm" } else { "cold" };
+            group.bench_function(format!("{name} {}", instance.name), |b| {
+                b.iter(|| {
+                    let (idx, attack) = toggle.next().unwrap();
+                    // Enable in the first pass over the optionals, disable in the second
+                    let patch = if idx / instance.optional.len() % 2 == 0 {
+                        Patch::EnableAttack(attack.clone())
+                    } else {
+                        Patch::DisableAttack(attack.clone())
+                    };
+                    af.apply_patch(&patch).expect("Applying patch");
+                    count(&mut af)
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parse, ground, enumerate, update);
+criterion_main!(benches);
@@ -0,0 +1,56 @@
+use common::XorShift;
+use criterion::{criterion_group, criterion_main, Criterion};
+use lib::argumentation_framework::parse_initial_file;
+
+mod common;
+
+const ARGUMENTS: usize = 100_000;
+const ATTACKS: usize = 1_000_000;
+
+/// Framework with a fixed pseudo-random attack relation, every `optional_every`th attack is optional.
+fn generate(optional_every: Option<usize>, tgf: bool) -> String {
+    let mut rng = XorShift::new();
+    let mut input = String::new();
+    let mut optionals = String::new();
+    for arg in 0..ARGUMENTS {
+        if tgf {
+            input += &format!("a{arg}\n");
+        } else {
+            input += &format!("arg(a{arg}).\n");
+        }
+    }
+    if tgf {
+        input += "#\n";
+    }
+    for attack in 0..ATTACKS {
+        let (from, to) = (rng.below(ARGUMENTS), rng.below(ARGUMENTS));
+        let optional = optional_every.is_some_and(|every| attack % every == 0);
+        if tgf {
+            input += &format!("a{from} a{to}{}\n", if optional { "?" } else { "" });
+        } else {
+            input += &format!("att(a{from},a{to}).\n");
+            if optional {
+                optionals += &format!("opt(att(a{from},a{to})).\n");
+            }
+        }
+    }
+    input + &optionals
+}
+
+fn parse_1m_attacks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse 1M attacks");
+    group.sample_size(10);
+    for (name, input) in [
+        ("apx", generate(None, false)),
+        ("apx, 1000 optional", generate(Some(ATTACKS / 1000), false)),
+        ("tgf", generate(None, true)),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| parse_initial_file(&input).expect("Parsing generated file"))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parse_1m_attacks);
+criterion_main!(benches);
@@ -0,0 +1,239 @@
+//! Approximate counting of extensions with random XOR constraints
+//!
+//! Follows ApproxMC by Chakraborty, Meel and Vardi. Every random XOR constraint over
+//! the arguments cuts the extensions roughly in half. Constraints are added until at
+//! most a threshold of extensions satisfies all of them, the remaining extensions times
+//! the number of such cells then estimate the total. The median over several
+//! repetitions lies within the tolerance with the requested confidence.
+use std::num::NonZeroUsize;
+
+use super::{
+    clingo, profile::Profile, semantics::ArgumentationFrameworkSemantic, symbols, Control,
+    FrameworkConfig,
+};
+use crate::{Error, Result};
+
+/// Seed of the random constraints, unless [`FrameworkConfig::random_seed`] is given.
+const DEFAULT_SEED: u64 = 0x2545_F491_4F6C_DD1D;
+
+/// Estimated number of extensions, see
+/// [`ArgumentationFramework::estimate_extension_count`](super::ArgumentationFramework::estimate_extension_count).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CountEstimate {
+    /// The estimated count
+    pub estimate: f64,
+    /// Lower bound, `estimate / (1 + epsilon)`
+    pub lower: f64,
+    /// Upper bound, `estimate * (1 + epsilon)`
+    pub upper: f64,
+    /// Probability of the true count lying within the bounds, `1 - delta`
+    pub confidence: f64,
+    /// Whether the count is exact, as there were only few extensions
+    pub exact: bool,
+}
+
+impl CountEstimate {
+    /// An exact count.
+    pub fn exact(count: usize) -> Self {
+        let count = count as f64;
+        CountEstimate {
+            estimate: count,
+            lower: count,
+            upper: count,
+            confidence: 1.0,
+            exact: true,
+        }
+    }
+}
+
+/// Estimate the number of extensions of the given framework.
+///
+/// The estimate is within a factor of `1 + epsilon` of the true count with
+/// probability at least `1 - delta`.
+pub fn estimate<S: ArgumentationFrameworkSemantic>(
+    arguments: &[symbols::Argument],
+    attacks: &[symbols::Attack],
+    config: &FrameworkConfig,
+    (epsilon, delta): (f64, f64),
+    profile: &mut Profile,
+) -> Result<CountEstimate> {
+    // Also rejects NaN
+    let valid = epsilon > 0.0 && delta > 0.0 && delta < 1.0;
+    if !valid {
+        return Err(Error::Logic(format!(
+            "Approximate counting needs epsilon > 0 and 0 < delta < 1, got {epsilon} and {delta}"
+        )));
+    }
+    // Bounds on the cell size and number of repetitions as given by ApproxMC
+    let threshold =
+        (1.0 + 9.84 * (1.0 + epsilon / (1.0 + epsilon)) * (1.0 + 1.0 / epsilon).powi(2)) as usize;
+    let repetitions = (17.0 * (3.0 / delta).log2()).ceil() as usize;
+    let mut counter = CellCounter {
+        arguments,
+        attacks,
+        config: FrameworkConfig {
+            // Counting beyond the threshold is never needed
+            models: NonZeroUsize::new(threshold + 1),
+            ..config.clone()
+        },
+        profile,
+    };
+    let count = counter.count::<S>(&[])?;
+    if count <= threshold {
+        return Ok(CountEstimate::exact(count));
+    }
+    log::debug!("Estimating with threshold {threshold} and {repetitions} repetitions");
+    let mut rng = XorShift::new(config.random_seed.map_or(DEFAULT_SEED, u64::from));
+    let mut estimates = Vec::with_capacity(repetitions);
+    // Number of constraints of the previous repetition, a good start for the next
+    let mut xors = 1;
+    for _ in 0..repetitions {
+        // Every prefix of the constraints is a hash with twice as many cells as the
+        // previous one, search the shortest prefix leaving few extensions
+        let mut hash = vec![];
+        let mut count_cell = |len: usize| {
+            while hash.len() < len {
+                hash.push(Xor::random(arguments.len(), &mut rng));
+            }
+            counter.count::<S>(&hash[..len])
+        };
+        let mut count = count_cell(xors)?;
+        while count > threshold {
+            xors += 1;
+            count = count_cell(xors)?;
+        }
+        while xors > 1 {
+            let fewer = count_cell(xors - 1)?;
+            if fewer > threshold {
+                break;
+            }
+            xors -= 1;
+            count = fewer;
+        }
+        log::trace!("{count} extensions in one of 2^{xors} cells");
+        estimates.push(count as f64 * 2f64.powi(xors as i32));
+    }
+    estimates.sort_by(f64::total_cmp);
+    let estimate = estimates[estimates.len() / 2];
+    Ok(CountEstimate {
+        estimate,
+        lower: estimate / (1.0 + epsilon),
+        upper: estimate * (1.0 + epsilon),
+        confidence: 1.0 - delta,
+        exact: false,
+    })
+}
+
+/// XOR constraint over the arguments.
+#[derive(Debug, Clone)]
+pub(super) struct Xor {
+    /// Whether the argument with this index is part of the constraint
+    members: Vec<bool>,
+    /// Whether an odd number of members has to be in the extension
+    odd: bool,
+}
+
+impl Xor {
+    /// Every argument is a member with probability 1/2.
+    pub(super) fn random(arguments: usize, rng: &mut XorShift) -> Self {
+        Xor {
+            members: (0..arguments).map(|_| rng.coin()).collect(),
+            odd: rng.coin(),
+        }
+    }
+}
+
+/// Bring the constraints into reduced row echelon form, without changing their solutions.
+///
+/// Solvers without XOR reasoning only propagate a constraint once all but one of its
+/// members are decided. Afterwards, every pivot occurs in a single constraint and
+/// follows from the other arguments, which speeds up counting by orders of magnitude.
+fn eliminate(xors: &mut [Xor]) {
+    let Some(arguments) = xors.first().map(|xor| xor.members.len()) else {
+        return;
+    };
+    let mut next = 0;
+    for pivot in 0..arguments {
+        let Some(found) = (next..xors.len()).find(|&row| xors[row].members[pivot]) else {
+            continue;
+        };
+        xors.swap(next, found);
+        let reduced = xors[next].clone();
+        for (row, xor) in xors.iter_mut().enumerate() {
+            if row != next && xor.members[pivot] {
+                for (member, reduced) in xor.members.iter_mut().zip(&reduced.members) {
+                    *member ^= reduced;
+                }
+                xor.odd ^= reduced.odd;
+            }
+        }
+        next += 1;
+    }
+}
+
+/// Counts the extensions within a cell of a hash, up to the model limit.
+struct CellCounter<'a> {
+    arguments: &'a [symbols::Argument],
+    attacks: &'a [symbols::Attack],
+    config: FrameworkConfig,
+    profile: &'a mut Profile,
+}
+
+impl CellCounter<'_> {
+    /// Number of extensions satisfying all constraints.
+    fn count<S: ArgumentationFrameworkSemantic>(&mut self, xors: &[Xor]) -> Result<usize> {
+        let ctl = cell::<S>(
+            self.arguments,
+            self.attacks,
+            &self.config,
+            self.profile,
+            xors,
+        )?;
+        let (_, count) = clingo::count_models(ctl)?;
+        Ok(count)
+    }
+}
+
+/// Backend whose models are the extensions satisfying all constraints.
+pub(super) fn cell<S: ArgumentationFrameworkSemantic>(
+    arguments: &[symbols::Argument],
+    attacks: &[symbols::Attack],
+    config: &FrameworkConfig,
+    profile: &mut Profile,
+    xors: &[Xor],
+) -> Result<Control> {
+    let mut xors = xors.to_vec();
+    eliminate(&mut xors);
+    // Warnings of the auxiliary controls are logged, but not collected
+    let logger = clingo::Logger::new(config, Default::default());
+    let mut ctl = clingo::initialize_backend::<S>(arguments, attacks, config, logger, profile)?;
+    clingo::add_xors(
+        &mut ctl,
+        xors.iter().map(|xor| {
+            let members = arguments
+                .iter()
+                .zip(&xor.members)
+                .filter(|(_, &member)| member)
+                .map(|(arg, _)| arg.id.as_str());
+            (members, xor.odd)
+        }),
+    )?;
+    Ok(ctl)
+}
+
+/// Small and fast pseudo random number generator, good enough for hashing.
+pub(super) struct XorShift(u64);
+
+impl XorShift {
+    /// Generator starting from the seed, which may be zero.
+    pub(super) fn new(seed: u64) -> Self {
+        XorShift(seed | 1)
+    }
+
+    fn coin(&mut self) -> bool {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 & 1 == 1
+    }
+}
@@ -0,0 +1,266 @@
+//! Checkable certificates for acceptance answers
+//!
+//! Certificates are created from the answers of the solver and validated by simple
+//! checks against the framework, independent of clingo and the encodings, so that
+//! results can be audited without trusting the solver.
+//!
+//! All given arguments and attacks are considered part of the framework, whether they
+//! are optional or not. Attacks between undeclared arguments are ignored.
+//!
+//! # Example
+//! ```
+//! use lib::argumentation_framework::{certify::Certificate, parse_initial_file};
+//!
+//! let (args, attacks) = parse_initial_file("arg(a). arg(b). arg(c). att(a,b). att(b,c).").unwrap();
+//! let certificate = Certificate::grounded(&args, &attacks, "c").expect("Labelling");
+//! assert_eq!(certificate.validate(&args, &attacks), Ok(()));
+//! ```
+use std::collections::{BTreeMap, HashSet};
+
+use super::{decomposition::Label, grounded::GroundedLabelling, symbols, ArgumentID, Extension};
+use crate::Result;
+
+/// Attack on a member of an extension, countered by an attack from the extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Defense {
+    pub attacker: ArgumentID,
+    pub defender: ArgumentID,
+}
+
+/// Evidence for the acceptance or rejection of an argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Certificate {
+    /// An admissible set containing the argument, with a defense against every attack
+    /// on its members.
+    ///
+    /// Proves credulous acceptance under admissible and complete semantics, as every
+    /// admissible set is contained in some complete extension.
+    Defended {
+        argument: ArgumentID,
+        extension: Vec<ArgumentID>,
+        defenses: Vec<Defense>,
+    },
+    /// The grounded labelling of the framework.
+    ///
+    /// The label of the argument decides acceptance under grounded semantics and
+    /// skeptical acceptance under complete semantics. An argument labelled out is not
+    /// credulously accepted under complete semantics either.
+    Labelling {
+        argument: ArgumentID,
+        labels: BTreeMap<ArgumentID, Label>,
+    },
+}
+
+/// Arguments and attacks between them, as used for validation.
+struct Graph<'a> {
+    arguments: HashSet<&'a str>,
+    attacks: HashSet<(&'a str, &'a str)>,
+}
+
+impl<'a> Graph<'a> {
+    fn new(arguments: &'a [symbols::Argument], attacks: &'a [symbols::Attack]) -> Self {
+        let arguments: HashSet<_> = arguments.iter().map(|arg| arg.id.as_str()).collect();
+        let attacks = attacks
+            .iter()
+            .map(|attack| (attack.from.as_str(), attack.to.as_str()))
+            .filter(|(from, to)| arguments.contains(from) && arguments.contains(to))
+            .collect();
+        Graph { arguments, attacks }
+    }
+
+    fn attackers(&self, id: &str) -> impl Iterator<Item = &'a str> + '_ {
+        let id = id.to_owned();
+        self.attacks
+            .iter()
+            .filter(move |(_, to)| *to == id)
+            .map(|&(from, _)| from)
+    }
+
+    /// Least fixpoint of the labelling, computed naively.
+    fn grounded_labels(&self) -> BTreeMap<ArgumentID, Label> {
+        let mut labels: BTreeMap<&str, Label> = BTreeMap::new();
+        loop {
+            let mut changed = false;
+            for &arg in &self.arguments {
+                if labels.contains_key(arg) {
+                    continue;
+                }
+                let attackers: Vec<_> = self
+                    .attackers(arg)
+                    .map(|from| labels.get(from).copied())
+                    .collect();
+                let label = if attackers.contains(&Some(Label::In)) {
+                    Label::Out
+                } else if attackers.iter().all(|label| *label == Some(Label::Out)) {
+                    Label::In
+                } else {
+                    continue;
+                };
+                labels.insert(arg, label);
+                changed = true;
+            }
+            if !changed {
+                break;
+            }
+        }
+        self.arguments
+            .iter()
+            .map(|&arg| {
+                let label = labels.get(arg).copied().unwrap_or(Label::Undec);
+                (arg.to_owned(), label)
+            })
+            .collect()
+    }
+}
+
+impl Certificate {
+    /// Certify the credulous acceptance of the argument by an admissible extension.
+    ///
+    /// `None` if the extension does not contain the argument or is not admissible.
+    pub fn defended(
+        arguments: &[symbols::Argument],
+        attacks: &[symbols::Attack],
+        extension: &Extension,
+        argument: &str,
+    ) -> Option<Self> {
+        let graph = Graph::new(arguments, attacks);
+        let arguments: Vec<_> = extension.arguments().collect();
+        let members: HashSet<&str> = arguments.iter().map(|arg| arg.id.as_str()).collect();
+        if !members.contains(argument) {
+            return None;
+        }
+        let mut defenses = vec![];
+        for &(attacker, attacked) in &graph.attacks {
+            if !members.contains(attacked) {
+                continue;
+            }
+            if members.contains(attacker) {
+                return None;
+            }
+            let defender = graph
+                .attackers(attacker)
+                .find(|defender| members.contains(defender))?;
+            defenses.push(Defense {
+                attacker: attacker.to_owned(),
+                defender: defender.to_owned(),
+            });
+        }
+        defenses.sort_by(|a, b| (&a.attacker, &a.defender).cmp(&(&b.attacker, &b.defender)));
+        defenses.dedup();
+        let mut extension: Vec<_> = members.into_iter().map(str::to_owned).collect();
+        extension.sort();
+        Some(Certificate::Defended {
+            argument: argument.to_owned(),
+            extension,
+            defenses,
+        })
+    }
+
+    /// Certify the label of the argument by the grounded labelling.
+    pub fn grounded(
+        arguments: &[symbols::Argument],
+        attacks: &[symbols::Attack],
+        argument: &str,
+    ) -> Result<Self> {
+        let arguments: Vec<_> = arguments
+            .iter()
+            .map(|arg| symbols::Argument::new(arg.id.as_str(), false))
+            .collect();
+        let attacks: Vec<_> = attacks
+            .iter()
+            .map(|attack| symbols::Attack::new(attack.from.as_str(), attack.to.as_str(), false))
+            .collect();
+        let labelling = GroundedLabelling::new(&arguments, &attacks)?;
+        let labels = labelling
+            .labels()
+            .map(|(id, label)| (id.clone(), label))
+            .collect();
+        Ok(Certificate::Labelling {
+            argument: argument.to_owned(),
+            labels,
+        })
+    }
+
+    /// The argument whose acceptance is certified.
+    pub fn argument(&self) -> &str {
+        match self {
+            Certificate::Defended { argument, .. } | Certificate::Labelling { argument, .. } => {
+                argument
+            }
+        }
+    }
+
+    /// Label of the argument, if this is a [`Certificate::Labelling`].
+    pub fn label(&self) -> Option<Label> {
+        match self {
+            Certificate::Defended { .. } => None,
+            Certificate::Labelling { argument, labels } => labels.get(argument).copied(),
+        }
+    }
+
+    /// Check the certificate against the framework, describing the first flaw found.
+    pub fn validate(
+        &self,
+        arguments: &[symbols::Argument],
+        attacks: &[symbols::Attack],
+    ) -> std::result::Result<(), String> {
+        let graph = Graph::new(arguments, attacks);
+        if !graph.arguments.contains(self.argument()) {
+            return Err(format!("{} is not an argument", self.argument()));
+        }
+        match self {
+            Certificate::Defended {
+                argument,
+                extension,
+                defenses,
+            } => {
+                let members: HashSet<&str> = extension.iter().map(String::as_str).collect();
+                if let Some(unknown) = members.iter().find(|id| !graph.arguments.contains(*id)) {
+                    return Err(format!("{unknown} is not an argument"));
+                }
+                if !members.contains(argument.as_str()) {
+                    return Err(format!("{argument} is not part of the extension"));
+                }
+                for &(attacker, attacked) in &graph.attacks {
+                    if !members.contains(attacked) {
+                        continue;
+                    }
+                    if members.contains(attacker) {
+                        return Err(format!("{attacker} and {attacked} are in conflict"));
+                    }
+                    let defended = defenses.iter().any(|defense| {
+                        defense.attacker == attacker
+                            && members.contains(defense.defender.as_str())
+                            && graph
+                                .attacks
+                                .contains(&(defense.defender.as_str(), attacker))
+                    });
+                    if !defended {
+                        return Err(format!("No defense of {attacked} against {attacker}"));
+                    }
+                }
+                Ok(())
+            }
+            Certificate::Labelling { labels, .. } => {
+                for (id, expected) in graph.grounded_labels() {
+                    match labels.get(&id) {
+                        Some(&label) if label == expected => {}
+                        Some(label) => {
+                            return Err(format!(
+                                "{id} is labelled {label:?}, but {expected:?} in the grounded labelling"
+                            ))
+                        }
+                        None => return Err(format!("{id} is not labelled")),
+                    }
+                }
+                if let Some(unknown) = labels
+                    .keys()
+                    .find(|id| !graph.arguments.contains(id.as_str()))
+                {
+                    return Err(format!("{unknown} is not an argument"));
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -1,103 +1,486 @@
 //! Main interface for communication between this library and clingo
 //!
 
-use ::clingo::Part;
-use clingo::SolverLiteral;
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+    mem,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex, PoisonError},
+};
 
-use super::{semantics::ArgumentationFrameworkSemantic, symbols, Control};
+use ::clingo::{Part, ShowType, SolveMode, SolveResult};
+use clingo::{SolverLiteral, Symbol};
+
+use super::{
+    profile::{Phase, Profile, Timer},
+    semantics::ArgumentationFrameworkSemantic,
+    statistics::{Diagnostic, MAX_COLLECTED_WARNINGS},
+    symbols, Control, FrameworkConfig,
+};
 
 use crate::Result;
 
-pub struct Logger;
+/// Solver literals by symbol, see [`symbol_literals`].
+pub type Literals = HashMap<Symbol, SolverLiteral>;
+
+/// Warnings collected by a [`Logger`], shared with the owner of the control.
+pub type Collected = Arc<Mutex<Vec<Diagnostic>>>;
+
+/// Forwards the warnings of clingo to [`log`] and collects them.
+///
+/// See [`FrameworkConfig::clingo_log_level`] and [`FrameworkConfig::ignored_warnings`].
+pub struct Logger {
+    level: Option<log::Level>,
+    ignored: Vec<clingo::Warning>,
+    collected: Collected,
+}
+
+impl Logger {
+    pub fn new(config: &FrameworkConfig, collected: Collected) -> Self {
+        Logger {
+            level: config.clingo_log_level,
+            ignored: config.ignored_warnings.clone(),
+            collected,
+        }
+    }
+}
+
+impl Default for Logger {
+    /// Log all warnings on the info level, collecting them nowhere.
+    fn default() -> Self {
+        Logger {
+            level: Some(log::Level::Info),
+            ignored: vec![],
+            collected: Collected::default(),
+        }
+    }
+}
 
 impl ::clingo::Logger for Logger {
     fn log(&mut self, code: clingo::Warning, message: &str) {
-        log::info!(target: "clingo", "[{code:?}] {message}")
+        let ignored = self
+            .ignored
+            .iter()
+            .any(|ignored| mem::discriminant(ignored) == mem::discriminant(&code));
+        if ignored {
+            return;
+        }
+        if let Some(level) = self.level {
+            log::log!(target: "clingo", level, "[{code:?}] {message}");
+        }
+        let mut collected = self
+            .collected
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if collected.len() < MAX_COLLECTED_WARNINGS {
+            collected.push(Diagnostic {
+                code: format!("{code:?}"),
+                message: message.trim_end().to_owned(),
+            });
+        }
     }
 }
 
 /// Initialize the clingo backend
 ///
-/// Loads the given args and attacks
+/// Loads the given args and attacks, recording the time spent on facts and grounding.
 pub fn initialize_backend<S: ArgumentationFrameworkSemantic>(
     args: &[symbols::Argument],
     attacks: &[symbols::Attack],
+    config: &FrameworkConfig,
+    logger: Logger,
+    profile: &mut Profile,
 ) -> Result<Control> {
-    let clingo_params = assemble_clingo_parameters();
-    let mut ctl = ::clingo::control_with_logger(clingo_params, Logger, u32::MAX)?;
-    // Add the facts
-    let facts = args.iter().fold(String::new(), |acc, argument| {
-        if argument.optional {
-            acc + &format!(r#"#external argument({}). "#, argument.id)
-        } else {
-            acc + &format!(r#"argument({}). "#, argument.id)
-        }
-    });
-    let facts = attacks.iter().fold(facts, |acc, attack| {
-        if attack.optional {
-            acc + &format!(r#"#external attack({}, {}). "#, attack.from, attack.to)
-        } else {
-            acc + &format!(r#"attack({}, {}). "#, attack.from, attack.to)
-        }
-    });
-    ctl.add("facts", &[], &facts)?;
+    let clingo_params = assemble_clingo_parameters(config);
+    let mut ctl = ::clingo::control_with_logger(clingo_params, logger, u32::MAX)?;
+    let timer = Timer::start();
+    add_facts(&mut ctl, args, attacks)?;
+    profile.record(Phase::Facts, timer);
     // Add the base program
-    ctl.add("base", &[], S::BASE)?;
+    ctl.add("base", &[], S::base())?;
     ctl.add(
         "show",
         &[],
         r#"
             #show.
             #show X: in(X).
+            #project in/1.
         "#,
     )?;
-    ground(&mut ctl)?;
+    if let Some(heuristic) = config.heuristic.program() {
+        ctl.add("heuristic", &[], heuristic)?;
+    }
+    let timer = Timer::start();
+    ground(&mut ctl, config)?;
+    profile.record(Phase::Ground, timer);
     Ok(ctl)
 }
 
-fn ground(ctl: &mut Control) -> Result {
-    log::trace!("Grounding programs: base(), show(), and facts()");
-    let parts = vec![
+/// Facts are passed to clingo in chunks of roughly this many bytes.
+const FACT_CHUNK_SIZE: usize = 1 << 16;
+
+/// Add the arguments and attacks to the `facts` program, optional ones as externals.
+///
+/// The facts are written to a reused buffer that is handed to clingo whenever it is
+/// full, instead of building the program for the whole framework in memory.
+fn add_facts(ctl: &mut Control, args: &[symbols::Argument], attacks: &[symbols::Attack]) -> Result {
+    let mut chunk = String::with_capacity(FACT_CHUNK_SIZE);
+    for argument in args {
+        write_fact(
+            &mut chunk,
+            argument.optional,
+            format_args!("argument({})", symbols::clingo_term(&argument.id)),
+        );
+        add_full_chunk(ctl, &mut chunk)?;
+    }
+    for attack in attacks {
+        write_fact(
+            &mut chunk,
+            attack.optional,
+            format_args!(
+                "attack({}, {})",
+                symbols::clingo_term(&attack.from),
+                symbols::clingo_term(&attack.to)
+            ),
+        );
+        add_full_chunk(ctl, &mut chunk)?;
+    }
+    // Always add the part, even if the framework is empty
+    ctl.add("facts", &[], &chunk)?;
+    Ok(())
+}
+
+fn write_fact(chunk: &mut String, optional: bool, atom: fmt::Arguments) {
+    if optional {
+        chunk.push_str("#external ");
+    }
+    chunk
+        .write_fmt(atom)
+        .expect("Writing to a String never fails");
+    chunk.push_str(". ");
+}
+
+fn add_full_chunk(ctl: &mut Control, chunk: &mut String) -> Result {
+    if chunk.len() >= FACT_CHUNK_SIZE {
+        ctl.add("facts", &[], chunk)?;
+        chunk.clear();
+    }
+    Ok(())
+}
+
+fn ground(ctl: &mut Control, config: &FrameworkConfig) -> Result {
+    let mut parts = vec![
         Part::new("base", vec![])?,
         Part::new("show", vec![])?,
         Part::new("facts", vec![])?,
     ];
+    if config.heuristic.program().is_some() {
+        parts.push(Part::new("heuristic", vec![])?);
+    }
+    log::trace!("Grounding {} programs", parts.len());
     ctl.ground(&parts)?;
     Ok(())
 }
 
-fn assemble_clingo_parameters() -> Vec<String> {
+fn assemble_clingo_parameters(config: &FrameworkConfig) -> Vec<String> {
     // Assemble clingo parameters
-    // FIXME: Make core count flexible
-    vec![
-        "--warn=all",
-        // Use multiple cores [--parallel-mode 12]
-        "--parallel-mode",
-        "12",
-        // Always prepare to compute all models [0]
-        "0",
-    ]
-    .into_iter()
-    .map(String::from)
-    .collect()
-}
-
-pub fn enable_argument(ctl: &mut Control, argument: SolverLiteral) -> Result {
-    ctl.assign_external(argument, clingo::TruthValue::True)?;
-    Ok(())
+    let mut params = vec![
+        String::from("--warn=all"),
+        // Use multiple cores [--parallel-mode N,MODE]
+        String::from("--parallel-mode"),
+        format!(
+            "{},{}",
+            config.threads,
+            config.parallel_mode.as_clingo_arg()
+        ),
+        // Enumerate every extension once, ignoring hidden atoms [--project]
+        String::from("--project"),
+        // Compute all models [0], unless limited
+        config.models.map_or(0, NonZeroUsize::get).to_string(),
+    ];
+    if config.heuristic.program().is_some() {
+        // Respect the #heuristic directives [--heuristic=Domain]
+        params.push(String::from("--heuristic=Domain"));
+    }
+    if config.warm_start {
+        // Never forget learnt information between steps and reuse the last assignment
+        // [--forget-on-step=0 --save-progress]
+        params.extend([
+            String::from("--forget-on-step=0"),
+            String::from("--save-progress"),
+        ]);
+    }
+    if let Some(conflicts) = config.conflict_limit {
+        // Stop every solve after this many conflicts [--solve-limit=N]
+        params.push(format!("--solve-limit={conflicts}"));
+    }
+    if let Some(seed) = config.random_seed {
+        // Random signs and decisions [--sign-def=rnd --rand-freq=1 --seed=N]
+        params.extend([
+            String::from("--sign-def=rnd"),
+            String::from("--rand-freq=1"),
+            format!("--seed={seed}"),
+        ]);
+    }
+    // User supplied parameters come last to be able to override the defaults
+    params.extend(config.clingo_args.iter().cloned());
+    log::trace!("Using clingo parameters: {params:?}");
+    params
 }
 
-pub fn disable_argument(ctl: &mut Control, argument: SolverLiteral) -> Result {
-    ctl.assign_external(argument, clingo::TruthValue::False)?;
-    Ok(())
+/// Parameters of the framework for a single solve of its own, without a model limit.
+///
+/// The `task` parameters come last, so that they override those of the configuration,
+/// e.g. the enumeration mode of [`Extremum::configure`](super::extremal::Extremum).
+fn task_parameters(config: &FrameworkConfig, task: &[&str]) -> Vec<String> {
+    let config = FrameworkConfig {
+        models: None,
+        ..config.clone()
+    };
+    let mut params = assemble_clingo_parameters(&config);
+    params.extend(task.iter().map(|&param| param.to_owned()));
+    params
+}
+
+/// Map every symbolic atom to its solver literal, and separately every external.
+///
+/// Only externals can be assigned, facts of the same predicates are not part of the
+/// second map.
+pub fn symbol_literals(ctl: &Control) -> Result<(Literals, Literals)> {
+    let mut literals = HashMap::new();
+    let mut externals = HashMap::new();
+    for atom in ctl.symbolic_atoms()?.iter()? {
+        let (symbol, literal) = (atom.symbol()?, atom.literal()?);
+        if atom.is_external()? {
+            externals.insert(symbol, literal);
+        }
+        literals.insert(symbol, literal);
+    }
+    log::trace!(
+        "Cached the literals of {} symbolic atoms, {} of them externals",
+        literals.len(),
+        externals.len()
+    );
+    Ok((literals, externals))
 }
 
-pub fn enable_attack(ctl: &mut Control, attack: SolverLiteral) -> Result {
-    ctl.assign_external(attack, clingo::TruthValue::True)?;
+/// Number of models enumerated by the last solve, according to the solver statistics.
+pub fn enumerated_models(ctl: &Control) -> Result<usize> {
+    let stats = ctl.statistics()?;
+    let summary = stats.map_at(stats.root()?, "summary")?;
+    let models = stats.map_at(summary, "models")?;
+    let enumerated = stats.map_at(models, "enumerated")?;
+    Ok(stats.value_get(enumerated)? as usize)
+}
+
+/// Fail if the solve ended before all models were enumerated, i.e. when the solve limit,
+/// see [`FrameworkConfig::conflict_limit`], was hit.
+///
+/// A solve stopping after `models` models is only complete if it was exhausted, or if it
+/// reached the model `limit`, where `0` means no limit.
+pub fn check_decided(result: SolveResult, models: usize, limit: usize) -> Result {
+    let decided = result.intersects(SolveResult::SATISFIABLE | SolveResult::UNSATISFIABLE);
+    let truncated = result.contains(SolveResult::SATISFIABLE)
+        && !result.contains(SolveResult::EXHAUSTED)
+        && (limit == 0 || models < limit);
+    if decided && !truncated {
+        Ok(())
+    } else {
+        Err(crate::Error::LimitExceeded)
+    }
+}
+
+/// The model limit of the control, `0` if all models are enumerated.
+fn model_limit(ctl: &mut Control) -> Result<usize> {
+    let conf = ctl.configuration_mut()?;
+    let models = conf.map_at(conf.root()?, "solve.models")?;
+    let limit = conf.value_get(models)?;
+    limit
+        .parse()
+        .map_err(|_| crate::Error::Logic(format!("Unexpected model limit {limit}")))
+}
+
+/// Solve and count the models, up to the model limit of the control.
+pub fn count_models(mut ctl: Control) -> Result<(Control, usize)> {
+    let limit = model_limit(&mut ctl)?;
+    let mut handle = ctl.solve(SolveMode::empty(), &[])?;
+    let result = handle.get()?;
+    let ctl = handle.close()?;
+    let count = enumerated_models(&ctl)?;
+    check_decided(result, count, limit)?;
+    Ok((ctl, count))
+}
+
+/// Solve for a single model under the assumptions, returning whether there is one.
+///
+/// The model limit of the control is lowered to one for this solve only.
+pub fn has_model(mut ctl: Control, assumptions: &[SolverLiteral]) -> Result<(Control, bool)> {
+    let conf = ctl.configuration_mut()?;
+    let models = conf.map_at(conf.root()?, "solve.models")?;
+    let limit = conf.value_get(models)?;
+    conf.value_set(models, "1")?;
+    let mut handle = ctl.solve(SolveMode::empty(), assumptions)?;
+    let result = handle.get()?;
+    let mut ctl = handle.close()?;
+    ctl.configuration_mut()?.value_set(models, &limit)?;
+    let found = result.contains(SolveResult::SATISFIABLE);
+    check_decided(result, usize::from(found), 1)?;
+    Ok((ctl, found))
+}
+
+/// Add XOR constraints over `in/1` and ground them.
+///
+/// Every constraint is given by its arguments and whether an odd number of them has to
+/// be in the extension. The parity is propagated along a chain of auxiliary atoms, which
+/// stays linear in the number of arguments, unlike a `#count` aggregate.
+pub fn add_xors<'a, A: IntoIterator<Item = &'a str>>(
+    ctl: &mut Control,
+    xors: impl IntoIterator<Item = (A, bool)>,
+) -> Result {
+    let mut program = String::new();
+    for (idx, (arguments, odd)) in xors.into_iter().enumerate() {
+        let mut last = None;
+        for (pos, argument) in arguments.into_iter().enumerate() {
+            let argument = symbols::clingo_term(argument);
+            program += &match pos.checked_sub(1) {
+                None => format!("xor_odd({idx}, 0) :- in({argument}). "),
+                Some(prev) => format!(
+                    "xor_odd({idx}, {pos}) :- xor_odd({idx}, {prev}), not in({argument}). \
+                     xor_odd({idx}, {pos}) :- not xor_odd({idx}, {prev}), in({argument}). "
+                ),
+            };
+            last = Some(pos);
+        }
+        program += &match (last, odd) {
+            (None, false) => String::new(),
+            (None, true) => String::from(":- . "),
+            (Some(last), true) => format!(":- not xor_odd({idx}, {last}). "),
+            (Some(last), false) => format!(":- xor_odd({idx}, {last}). "),
+        };
+    }
+    ctl.add("xors", &[], &program)?;
+    ctl.ground(&[Part::new("xors", vec![])?])?;
     Ok(())
 }
 
-pub fn disable_attack(ctl: &mut Control, attack: SolverLiteral) -> Result {
-    ctl.assign_external(attack, clingo::TruthValue::False)?;
+/// Set the truth values of the given external atoms.
+pub fn assign_externals(
+    ctl: &mut Control,
+    assignments: impl IntoIterator<Item = (SolverLiteral, bool)>,
+) -> Result {
+    for (literal, value) in assignments {
+        let value = if value {
+            clingo::TruthValue::True
+        } else {
+            clingo::TruthValue::False
+        };
+        ctl.assign_external(literal, value)?;
+    }
     Ok(())
 }
+
+/// Program with the arguments and attacks as facts, all of them part of the framework.
+pub fn fact_program(arguments: &[symbols::Argument], attacks: &[symbols::Attack]) -> String {
+    let mut program = String::new();
+    for arg in arguments {
+        let id = symbols::clingo_term(&arg.id);
+        writeln!(program, "argument({id}).").expect("Writing to a String never fails");
+    }
+    for attack in attacks {
+        let (from, to) = (
+            symbols::clingo_term(&attack.from),
+            symbols::clingo_term(&attack.to),
+        );
+        writeln!(program, "attack({from}, {to}).").expect("Writing to a String never fails");
+    }
+    program
+}
+
+/// Shown symbols of every model of the given program.
+pub fn models(program: &str) -> Result<Vec<Vec<Symbol>>> {
+    let mut ctl =
+        ::clingo::control_with_logger(vec![String::from("0")], Logger::default(), u32::MAX)?;
+    ctl.add("base", &[], program)?;
+    ctl.ground(&[Part::new("base", vec![])?])?;
+    let mut handle = ctl.solve(SolveMode::YIELD, &[])?;
+    let mut models = vec![];
+    loop {
+        handle.resume()?;
+        match handle.model()? {
+            Some(model) => models.push(model.symbols(ShowType::SHOWN)?),
+            None => break,
+        }
+    }
+    handle.close()?;
+    Ok(models)
+}
+
+/// Shown symbols of every optimal model of the given program.
+///
+/// Models are projected onto the shown atoms. Models found before the optimum is
+/// proven are skipped, as they may be of higher cost. The solve follows the threads,
+/// limits and arguments of the `config`, warnings are passed to the `logger`.
+pub fn optimal_models(
+    program: &str,
+    config: &FrameworkConfig,
+    logger: Logger,
+) -> Result<Vec<Vec<Symbol>>> {
+    // Enumerate all optimal models [--opt-mode=optN --enum-mode=auto]
+    let params = task_parameters(config, &["--opt-mode=optN", "--enum-mode=auto"]);
+    let mut ctl = ::clingo::control_with_logger(params, logger, u32::MAX)?;
+    ctl.add("base", &[], program)?;
+    ctl.ground(&[Part::new("base", vec![])?])?;
+    let mut handle = ctl.solve(SolveMode::YIELD, &[])?;
+    let mut models = vec![];
+    let mut found = 0;
+    loop {
+        handle.resume()?;
+        match handle.model()? {
+            Some(model) if model.optimality_proven()? => {
+                models.push(model.symbols(ShowType::SHOWN)?)
+            }
+            Some(_) => {}
+            None => break,
+        }
+        found += 1;
+    }
+    let result = handle.get()?;
+    handle.close()?;
+    check_decided(result, found, 0)?;
+    Ok(models)
+}
+
+/// Shown symbols of the consequences of the given program, `None` if it has no model.
+///
+/// Clingo refines the consequences with every model it reports, only the last one is
+/// final. The `mode` is either `brave` or `cautious`. The solve follows the threads,
+/// limits and arguments of the `config`, warnings are passed to the `logger`.
+pub fn consequences(
+    program: &str,
+    mode: &str,
+    config: &FrameworkConfig,
+    logger: Logger,
+) -> Result<Option<Vec<Symbol>>> {
+    // Compute the brave or cautious consequences [--enum-mode=MODE]
+    let enum_mode = format!("--enum-mode={mode}");
+    let params = task_parameters(config, &[&enum_mode]);
+    let mut ctl = ::clingo::control_with_logger(params, logger, u32::MAX)?;
+    ctl.add("base", &[], program)?;
+    ctl.ground(&[Part::new("base", vec![])?])?;
+    let mut handle = ctl.solve(SolveMode::YIELD, &[])?;
+    let mut last = None;
+    let mut found = 0;
+    loop {
+        handle.resume()?;
+        match handle.model()? {
+            Some(model) => last = Some(model.symbols(ShowType::SHOWN)?),
+            None => break,
+        }
+        found += 1;
+    }
+    let result = handle.get()?;
+    handle.close()?;
+    check_decided(result, found, 0)?;
+    Ok(last)
+}
@@ -0,0 +1,170 @@
+//! Configuration of the clingo backend
+use std::num::{NonZeroU64, NonZeroUsize};
+
+/// Configuration for an [`ArgumentationFramework`](super::ArgumentationFramework).
+///
+/// Use [`Default::default`] for sensible defaults.
+#[derive(Debug, Clone)]
+pub struct FrameworkConfig {
+    /// Number of threads clingo may use for solving.
+    ///
+    /// Defaults to the available parallelism of the machine.
+    pub threads: NonZeroUsize,
+    /// How multiple threads work together, see [`ParallelMode`].
+    ///
+    /// Has no effect when solving with a single thread.
+    pub parallel_mode: ParallelMode,
+    /// Additional parameters passed verbatim to clingo.
+    ///
+    /// These are appended after the default parameters and may thus override them.
+    pub clingo_args: Vec<String>,
+    /// Level on which warnings of clingo are logged, `None` to not log them at all.
+    ///
+    /// Warnings are collected in the [`Statistics`](super::statistics::Statistics)
+    /// regardless, unless ignored.
+    pub clingo_log_level: Option<log::Level>,
+    /// Kinds of clingo warnings that are neither logged nor collected.
+    pub ignored_warnings: Vec<clingo::Warning>,
+    /// Maximum number of extensions computed per solve.
+    ///
+    /// Defaults to computing all extensions.
+    pub models: Option<NonZeroUsize>,
+    /// Randomize the solver heuristics using this seed.
+    ///
+    /// Useful to sample different extensions in different runs.
+    pub random_seed: Option<u32>,
+    /// Domain heuristic guiding the decisions of the solver.
+    pub heuristic: Heuristic,
+    /// Solve SCC-recursive semantics component by component.
+    ///
+    /// Pays off on frameworks with many small strongly connected components. Has no
+    /// effect on semantics without a
+    /// [`DECOMPOSITION`](super::semantics::ArgumentationFrameworkSemantic::DECOMPOSITION).
+    pub decompose: bool,
+    /// Skip extensions that were already handed out during the same solve.
+    ///
+    /// Clingo projects the models onto the arguments in the extension, so this is only
+    /// needed if projection is disabled, e.g. by passing `--project=no` in
+    /// [`clingo_args`](Self::clingo_args). At most this many extensions are remembered
+    /// by their hash, duplicates of later ones are handed out again.
+    pub deduplicate: Option<NonZeroUsize>,
+    /// Keep everything learnt while solving for the solves after the next updates.
+    ///
+    /// Clingo reuses the control object between updates anyway, but may forget learnt
+    /// clauses and heuristic scores per step. This keeps them and additionally saves the
+    /// phase of every atom, so that consecutive solves on similar frameworks start where
+    /// the last one ended.
+    pub warm_start: bool,
+    /// Reject frameworks with duplicate or conflicting declarations, instead of warning.
+    ///
+    /// See [`validation::declaration_issues`](super::validation::declaration_issues).
+    pub strict: bool,
+    /// Reject the DASP-specific optional markers, `opt(..)` and `?`, in the initial file.
+    ///
+    /// Ensures that the file is a plain ICCMA instance, see
+    /// [`parse_iccma_file`](super::parse_iccma_file). Nothing can be updated then.
+    pub iccma_compliant: bool,
+    /// How to handle attacks on arguments that were never declared.
+    pub undeclared: UndeclaredArguments,
+    /// Remember extensions and counts per framework state.
+    ///
+    /// Queries on a state that was solved before, i.e. with the same arguments and
+    /// attacks enabled, are answered without solving again.
+    pub cache: bool,
+    /// Maximum number of conflicts per solve, passed to clasp.
+    ///
+    /// Solves that hit the limit before deciding whether there is an extension fail
+    /// with [`Error::LimitExceeded`](crate::Error::LimitExceeded).
+    pub conflict_limit: Option<NonZeroU64>,
+}
+
+/// Flavor of parallel solving for [`FrameworkConfig::parallel_mode`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParallelMode {
+    /// Let all threads solve the whole problem with different configurations.
+    #[default]
+    Compete,
+    /// Split the search space between the threads.
+    Split,
+}
+
+impl ParallelMode {
+    /// Name of this mode as expected by `--parallel-mode`.
+    pub(crate) fn as_clingo_arg(self) -> &'static str {
+        match self {
+            ParallelMode::Compete => "compete",
+            ParallelMode::Split => "split",
+        }
+    }
+}
+
+/// Policy for [`FrameworkConfig::undeclared`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UndeclaredArguments {
+    /// Reject the framework.
+    Reject,
+    /// Warn and drop the attack.
+    #[default]
+    Ignore,
+    /// Declare the missing argument, as non-optional argument.
+    Declare,
+}
+
+/// Domain heuristic for [`FrameworkConfig::heuristic`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Heuristic {
+    /// Use the default heuristic of clingo.
+    #[default]
+    Default,
+    /// Prefer including arguments in the extension.
+    PreferIn,
+    /// Prefer leaving arguments out of the extension.
+    PreferOut,
+    /// Decide on arguments attacking many others first.
+    AttackDegree,
+}
+
+impl Heuristic {
+    /// `#heuristic` directives implementing this heuristic, if any.
+    pub(crate) fn program(self) -> Option<&'static str> {
+        match self {
+            Heuristic::Default => None,
+            Heuristic::PreferIn => Some("#heuristic in(X) : argument(X). [1, true]"),
+            Heuristic::PreferOut => Some("#heuristic in(X) : argument(X). [1, false]"),
+            Heuristic::AttackDegree => {
+                Some("#heuristic in(X) : argument(X), D = #count { Y : attack(X, Y) }. [D, level]")
+            }
+        }
+    }
+}
+
+impl Default for FrameworkConfig {
+    fn default() -> Self {
+        Self {
+            threads: available_threads(),
+            parallel_mode: ParallelMode::Compete,
+            clingo_args: vec![],
+            clingo_log_level: Some(log::Level::Info),
+            ignored_warnings: vec![],
+            models: None,
+            random_seed: None,
+            heuristic: Heuristic::Default,
+            decompose: false,
+            deduplicate: None,
+            warm_start: false,
+            strict: false,
+            iccma_compliant: false,
+            undeclared: UndeclaredArguments::Ignore,
+            cache: false,
+            conflict_limit: None,
+        }
+    }
+}
+
+/// Number of threads available on this machine, falling back to one.
+fn available_threads() -> NonZeroUsize {
+    std::thread::available_parallelism().unwrap_or_else(|why| {
+        log::warn!("Could not determine available parallelism, using one thread: {why}");
+        NonZeroUsize::MIN
+    })
+}
@@ -0,0 +1,59 @@
+//! Arguments accepted by some or all extensions
+//!
+//! Instead of enumerating all extensions, clingo computes the consequences of the
+//! encoding of the semantics in a single solve: the brave consequences are the atoms
+//! true in some model, i.e. the union of all extensions, the cautious consequences
+//! those true in every model, i.e. their intersection.
+use super::{
+    clingo, semantics::ArgumentationFrameworkSemantic, symbols, Extension, FrameworkConfig,
+};
+use crate::{Error, Result};
+
+/// Which consequences to compute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Consequences {
+    /// Arguments contained in some extension
+    Brave,
+    /// Arguments contained in every extension
+    Cautious,
+}
+
+impl Consequences {
+    /// Value of clingo's `--enum-mode` computing these consequences.
+    fn enum_mode(self) -> &'static str {
+        match self {
+            Consequences::Brave => "brave",
+            Consequences::Cautious => "cautious",
+        }
+    }
+}
+
+/// Compute the arguments that are consequences of the semantics, `None` if there is no
+/// extension.
+///
+/// All elements are considered part of the framework. Only correct if every model of
+/// the program of the semantics is an extension, i.e. without
+/// [`ArgumentationFrameworkSemantic::EXTREMUM`].
+pub(super) fn arguments<S: ArgumentationFrameworkSemantic>(
+    arguments: &[symbols::Argument],
+    attacks: &[symbols::Attack],
+    consequences: Consequences,
+    config: &FrameworkConfig,
+    logger: clingo::Logger,
+) -> Result<Option<Extension>> {
+    let mut program = clingo::fact_program(arguments, attacks);
+    program += S::base();
+    program += "\n#show in/1.\n";
+    let Some(shown) = clingo::consequences(&program, consequences.enum_mode(), config, logger)?
+    else {
+        return Ok(None);
+    };
+    shown
+        .into_iter()
+        .map(|symbol| match symbol.arguments()?[..] {
+            [id] => Ok(symbols::symbol_id(id)?),
+            _ => Err(Error::Logic(format!("Unexpected symbol {symbol}"))),
+        })
+        .collect::<Result<Extension>>()
+        .map(Some)
+}
@@ -0,0 +1,489 @@
+//! Solving SCC-recursive semantics component by component
+//!
+//! The strongly connected components of the attack graph form a DAG. Visiting them
+//! in topological order, the labels of all attackers outside of the current component
+//! are already known, so every component can be solved on its own. The labellings of
+//! the components are then combined into labellings of the whole framework.
+use std::collections::{HashMap, HashSet};
+
+use super::{clingo, symbols, Extension};
+use crate::{Error, Result};
+
+/// Semantics that can be solved component by component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decomposition {
+    Complete,
+    Grounded,
+    Stable,
+    /// Maximal conflict-free sets of the components, after removing the arguments
+    /// attacked from preceding components, applied recursively
+    Cf2,
+}
+
+/// Label of an argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Label {
+    /// Accepted
+    In,
+    /// Attacked by an accepted argument
+    Out,
+    /// Neither accepted nor rejected
+    Undec,
+}
+
+/// Complete labellings of a single component, given the labels of outside attackers.
+const COMPLETE_PROGRAM: &str = r#"
+    1 { lab(X, in); lab(X, out); lab(X, undec) } 1 :- argument(X).
+
+    %% Some attacker is in
+    defeated(X) :- attack(Y, X), lab(Y, in).
+    %% Some attacker is not out
+    challenged(X) :- attack(Y, X), not lab(Y, out).
+
+    %% Labels of outside attackers are given and not checked
+    :- argument(X), lab(X, in), challenged(X).
+    :- argument(X), lab(X, out), not defeated(X).
+    :- argument(X), lab(X, undec), defeated(X).
+    :- argument(X), lab(X, undec), not challenged(X).
+
+    #show.
+    #show lab(X, L) : lab(X, L), argument(X).
+"#;
+
+/// Restricts [`COMPLETE_PROGRAM`] to stable labellings.
+const STABLE_PROGRAM: &str = r#"
+    :- lab(X, undec).
+"#;
+
+/// Compute all extensions of the given framework.
+///
+/// Attacks from or to arguments that are not part of the framework are ignored.
+pub fn extensions(
+    arguments: &[symbols::Argument],
+    attacks: &[symbols::Attack],
+    decomposition: Decomposition,
+) -> Result<Vec<Extension>> {
+    Ok(labellings(arguments, attacks, decomposition)?
+        .into_iter()
+        .map(|labelling| {
+            labelling
+                .iter()
+                .zip(arguments)
+                .filter(|(label, _)| **label == Label::In)
+                .map(|(_, arg)| arg.id.clone())
+                .collect()
+        })
+        .collect())
+}
+
+/// Count the extensions of the given framework, without converting them.
+///
+/// Attacks from or to arguments that are not part of the framework are ignored.
+pub fn count(
+    arguments: &[symbols::Argument],
+    attacks: &[symbols::Attack],
+    decomposition: Decomposition,
+) -> Result<usize> {
+    Ok(labellings(arguments, attacks, decomposition)?.len())
+}
+
+/// Labellings of all arguments, one per extension, in the order of the arguments.
+fn labellings(
+    arguments: &[symbols::Argument],
+    attacks: &[symbols::Attack],
+    decomposition: Decomposition,
+) -> Result<Vec<Vec<Label>>> {
+    let index: HashMap<&str, usize> = arguments
+        .iter()
+        .enumerate()
+        .map(|(idx, arg)| (arg.id.as_str(), idx))
+        .collect();
+    let mut successors = vec![vec![]; arguments.len()];
+    let mut attackers = vec![vec![]; arguments.len()];
+    for attack in attacks {
+        if let (Some(&from), Some(&to)) = (
+            index.get(attack.from.as_str()),
+            index.get(attack.to.as_str()),
+        ) {
+            successors[from].push(to);
+            attackers[to].push(from);
+        }
+    }
+    // Tarjan's algorithm finishes components after all components they attack
+    let mut components = strongly_connected_components(&successors);
+    components.reverse();
+    let mut component_of = vec![0; arguments.len()];
+    for (component_idx, component) in components.iter().enumerate() {
+        for &member in component {
+            component_of[member] = component_idx;
+        }
+    }
+    let mut solver = ComponentSolver {
+        decomposition,
+        ids: arguments.iter().map(|arg| arg.id.as_str()).collect(),
+        attackers,
+        component_of,
+        cache: HashMap::new(),
+    };
+    let mut labellings = vec![vec![Label::Undec; arguments.len()]];
+    for (component_idx, component) in components.iter().enumerate() {
+        let inputs = solver.inputs(component_idx, component);
+        let mut combined = vec![];
+        for labelling in labellings {
+            let input_labels = inputs.iter().map(|&arg| labelling[arg]).collect();
+            let partials = solver.solve(component_idx, component, input_labels, &labelling)?;
+            for partial in partials {
+                let mut labelling = labelling.clone();
+                for (&member, label) in component.iter().zip(partial) {
+                    labelling[member] = label;
+                }
+                combined.push(labelling);
+            }
+        }
+        labellings = combined;
+    }
+    Ok(labellings)
+}
+
+/// Solves single components and remembers the results per input.
+struct ComponentSolver<'a> {
+    decomposition: Decomposition,
+    ids: Vec<&'a str>,
+    attackers: Vec<Vec<usize>>,
+    /// Index of the component of every argument
+    component_of: Vec<usize>,
+    /// Labellings of the component members by component and labels of the inputs
+    cache: HashMap<(usize, Vec<Label>), Vec<Vec<Label>>>,
+}
+
+impl ComponentSolver<'_> {
+    /// Attackers of the component that are not part of it.
+    fn inputs(&self, component_idx: usize, component: &[usize]) -> Vec<usize> {
+        let mut inputs: Vec<_> = component
+            .iter()
+            .flat_map(|&member| &self.attackers[member])
+            .copied()
+            .filter(|&attacker| self.component_of[attacker] != component_idx)
+            .collect();
+        inputs.sort_unstable();
+        inputs.dedup();
+        inputs
+    }
+
+    /// Labellings of the component members, in the order of the component.
+    fn solve(
+        &mut self,
+        component_idx: usize,
+        component: &[usize],
+        input_labels: Vec<Label>,
+        labelling: &[Label],
+    ) -> Result<Vec<Vec<Label>>> {
+        let key = (component_idx, input_labels);
+        if let Some(partials) = self.cache.get(&key) {
+            return Ok(partials.clone());
+        }
+        let unattacked_singleton =
+            component.len() == 1 && !self.attackers[component[0]].contains(&component[0]);
+        let partials = if self.decomposition == Decomposition::Cf2 {
+            self.cf2(component, labelling)
+        } else if self.decomposition == Decomposition::Grounded || unattacked_singleton {
+            // The least fixpoint is the only complete labelling of a singleton
+            let partial = self.propagate(component, labelling);
+            if self.decomposition == Decomposition::Stable && partial.contains(&Label::Undec) {
+                vec![]
+            } else {
+                vec![partial]
+            }
+        } else {
+            self.search(component_idx, component, labelling)?
+        };
+        self.cache.insert(key, partials.clone());
+        Ok(partials)
+    }
+
+    /// Least fixpoint of the component, i.e. its grounded labelling.
+    fn propagate(&self, component: &[usize], labelling: &[Label]) -> Vec<Label> {
+        let mut labelling = labelling.to_vec();
+        for &member in component {
+            labelling[member] = Label::Undec;
+        }
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &member in component {
+                if labelling[member] != Label::Undec {
+                    continue;
+                }
+                let mut attackers = self.attackers[member].iter().map(|&arg| labelling[arg]);
+                if attackers.clone().all(|label| label == Label::Out) {
+                    labelling[member] = Label::In;
+                    changed = true;
+                } else if attackers.any(|label| label == Label::In) {
+                    labelling[member] = Label::Out;
+                    changed = true;
+                }
+            }
+        }
+        component.iter().map(|&member| labelling[member]).collect()
+    }
+
+    /// CF2 labellings of the component, in the order of the component.
+    ///
+    /// Members attacked by an accepted outside argument are out, the extensions of the
+    /// others are computed by [`cf2_extensions`]. Remaining members attacked by the
+    /// extension are out as well, all others undecided.
+    fn cf2(&self, component: &[usize], labelling: &[Label]) -> Vec<Vec<Label>> {
+        let defeated = |member: usize, extension: &[usize]| {
+            self.attackers[member].iter().any(|&attacker| {
+                extension.contains(&attacker)
+                    || (self.component_of[attacker] != self.component_of[member]
+                        && labelling[attacker] == Label::In)
+            })
+        };
+        let undefeated: Vec<_> = component
+            .iter()
+            .copied()
+            .filter(|&member| !defeated(member, &[]))
+            .collect();
+        cf2_extensions(&undefeated, &self.attackers)
+            .into_iter()
+            .map(|extension| {
+                component
+                    .iter()
+                    .map(|&member| {
+                        if extension.contains(&member) {
+                            Label::In
+                        } else if defeated(member, &extension) {
+                            Label::Out
+                        } else {
+                            Label::Undec
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// All labellings of the component, computed by clingo.
+    fn search(
+        &self,
+        component_idx: usize,
+        component: &[usize],
+        labelling: &[Label],
+    ) -> Result<Vec<Vec<Label>>> {
+        let mut program = String::from(COMPLETE_PROGRAM);
+        if self.decomposition == Decomposition::Stable {
+            program += STABLE_PROGRAM;
+        }
+        let mut position = HashMap::new();
+        for (pos, &member) in component.iter().enumerate() {
+            position.insert(self.ids[member], pos);
+            let member_term = symbols::clingo_term(self.ids[member]);
+            program += &format!("argument({member_term}). ");
+            for &attacker in &self.attackers[member] {
+                let attacker_term = symbols::clingo_term(self.ids[attacker]);
+                program += &format!("attack({attacker_term}, {member_term}). ");
+                if self.component_of[attacker] != component_idx {
+                    let label = match labelling[attacker] {
+                        Label::In => "in",
+                        Label::Out => "out",
+                        Label::Undec => "undec",
+                    };
+                    program += &format!("lab({attacker_term}, {label}). ");
+                }
+            }
+        }
+        clingo::models(&program)?
+            .into_iter()
+            .map(|model| {
+                let mut partial = vec![Label::Undec; component.len()];
+                for symbol in model {
+                    let arguments = symbol.arguments()?;
+                    let id = symbols::symbol_id(arguments[0])?;
+                    let pos = position
+                        .get(id.as_str())
+                        .ok_or_else(|| Error::Logic(format!("Unknown argument {id} in model")))?;
+                    partial[*pos] = match arguments[1].name()? {
+                        "in" => Label::In,
+                        "out" => Label::Out,
+                        _ => Label::Undec,
+                    };
+                }
+                Ok(partial)
+            })
+            .collect()
+    }
+}
+
+/// CF2 extensions of the framework restricted to the given arguments.
+///
+/// A single component has its maximal conflict-free sets as extensions. Otherwise the
+/// components are visited in topological order, removing the arguments attacked by the
+/// extension so far and recursing into the rest, which may fall apart into smaller
+/// components.
+fn cf2_extensions(arguments: &[usize], attackers: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let position: HashMap<usize, usize> = arguments
+        .iter()
+        .enumerate()
+        .map(|(pos, &arg)| (arg, pos))
+        .collect();
+    let mut successors = vec![vec![]; arguments.len()];
+    for (pos, &arg) in arguments.iter().enumerate() {
+        for attacker in &attackers[arg] {
+            if let Some(&from) = position.get(attacker) {
+                successors[from].push(pos);
+            }
+        }
+    }
+    let mut components = strongly_connected_components(&successors);
+    if components.len() <= 1 {
+        return maximal_conflict_free(arguments, attackers);
+    }
+    components.reverse();
+    let mut extensions = vec![vec![]];
+    for component in components {
+        let mut combined = vec![];
+        for extension in extensions {
+            let undefeated: Vec<_> = component
+                .iter()
+                .map(|&pos| arguments[pos])
+                .filter(|arg| {
+                    !attackers[*arg]
+                        .iter()
+                        .any(|attacker| extension.contains(attacker))
+                })
+                .collect();
+            for partial in cf2_extensions(&undefeated, attackers) {
+                let mut extension = extension.clone();
+                extension.extend(partial);
+                combined.push(extension);
+            }
+        }
+        extensions = combined;
+    }
+    extensions
+}
+
+/// Maximal conflict-free subsets of the arguments, using Bron–Kerbosch with pivoting.
+///
+/// These are the maximal cliques of the graph connecting arguments without conflict.
+fn maximal_conflict_free(arguments: &[usize], attackers: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let candidates: Vec<usize> = arguments
+        .iter()
+        .copied()
+        .filter(|&arg| !attackers[arg].contains(&arg))
+        .collect();
+    let compatible: Vec<HashSet<usize>> = candidates
+        .iter()
+        .map(|&arg| {
+            candidates
+                .iter()
+                .copied()
+                .filter(|&other| {
+                    other != arg
+                        && !attackers[arg].contains(&other)
+                        && !attackers[other].contains(&arg)
+                })
+                .collect()
+        })
+        .collect();
+    let neighbours: HashMap<usize, &HashSet<usize>> =
+        candidates.iter().copied().zip(&compatible).collect();
+    let mut cliques = vec![];
+    // Stack of (clique, candidates to add, already excluded)
+    let mut calls = vec![(vec![], candidates, vec![])];
+    while let Some((clique, mut open, mut excluded)) = calls.pop() {
+        if open.is_empty() && excluded.is_empty() {
+            cliques.push(clique);
+            continue;
+        }
+        let pivot = open
+            .iter()
+            .chain(&excluded)
+            .copied()
+            .max_by_key(|pivot| {
+                open.iter()
+                    .filter(|arg| neighbours[pivot].contains(arg))
+                    .count()
+            })
+            .expect("Open or excluded arguments are left");
+        let branches: Vec<usize> = open
+            .iter()
+            .copied()
+            .filter(|arg| !neighbours[&pivot].contains(arg))
+            .collect();
+        for arg in branches {
+            let mut extended = clique.clone();
+            extended.push(arg);
+            let keep = |others: &[usize]| -> Vec<usize> {
+                others
+                    .iter()
+                    .copied()
+                    .filter(|other| neighbours[&arg].contains(other))
+                    .collect()
+            };
+            calls.push((extended, keep(&open), keep(&excluded)));
+            open.retain(|&other| other != arg);
+            excluded.push(arg);
+        }
+    }
+    cliques
+}
+
+/// Strongly connected components of the graph, using Tarjan's algorithm.
+///
+/// Components are returned in reverse topological order, i.e. every component
+/// comes after all components it has edges to.
+/// Implemented iteratively, as real-world frameworks easily exceed the stack with recursion.
+pub fn strongly_connected_components(successors: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+    let mut index = vec![UNVISITED; successors.len()];
+    let mut lowlink = vec![0; successors.len()];
+    let mut on_stack = vec![false; successors.len()];
+    let mut stack = vec![];
+    let mut components = vec![];
+    let mut next_index = 0;
+    for root in 0..successors.len() {
+        if index[root] != UNVISITED {
+            continue;
+        }
+        // Call stack of (node, next successor to visit)
+        let mut calls = vec![(root, 0)];
+        while let Some((node, child)) = calls.pop() {
+            if child == 0 {
+                index[node] = next_index;
+                lowlink[node] = next_index;
+                next_index += 1;
+                stack.push(node);
+                on_stack[node] = true;
+            } else {
+                // Finished components must not be merged into this one
+                let previous = successors[node][child - 1];
+                if on_stack[previous] {
+                    lowlink[node] = lowlink[node].min(lowlink[previous]);
+                }
+            }
+            if let Some(&next) = successors[node].get(child) {
+                calls.push((node, child + 1));
+                if index[next] == UNVISITED {
+                    calls.push((next, 0));
+                } else if on_stack[next] {
+                    lowlink[node] = lowlink[node].min(index[next]);
+                }
+                continue;
+            }
+            if lowlink[node] == index[node] {
+                let mut component = vec![];
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+    components
+}
@@ -0,0 +1,169 @@
+//! Differential tests comparing the solver against the brute-force [`reference`](super::reference)
+use std::collections::{BTreeSet, HashSet};
+
+use proptest::{prelude::*, sample::Index};
+
+use crate::semantics::{
+    Admissible, Cf2, Complete, ConflictFree, Ground, Preferred, ResolutionGrounded, Stable,
+    StronglyAdmissible,
+};
+
+use super::{
+    properties::{all_extensions, frameworks, patches, ArbitraryAf},
+    reference::{self, ReferenceSemantics},
+    *,
+};
+
+/// Largest framework checked, every subset of its arguments is visited by the reference.
+const MAX_ARGUMENTS: usize = 15;
+
+/// Active elements of the framework, tracked independently of the solver.
+struct State {
+    enabled: HashSet<String>,
+}
+
+impl State {
+    fn apply(&mut self, patch: &Patch) {
+        match patch {
+            Patch::EnableArgument(arg) => self.enabled.insert(arg.to_string()),
+            Patch::DisableArgument(arg) => self.enabled.remove(&arg.to_string()),
+            Patch::EnableAttack(attack) => self.enabled.insert(attack.to_string()),
+            Patch::DisableAttack(attack) => self.enabled.remove(&attack.to_string()),
+        };
+    }
+    fn reference(&self, af: &ArbitraryAf, semantics: ReferenceSemantics) -> BTreeSet<Extension> {
+        let arguments: Vec<_> = af
+            .arguments
+            .iter()
+            .filter(|arg| !arg.optional || self.enabled.contains(&arg.to_string()))
+            .cloned()
+            .collect();
+        let attacks: Vec<_> = af
+            .attacks
+            .iter()
+            .filter(|attack| !attack.optional || self.enabled.contains(&attack.to_string()))
+            .cloned()
+            .collect();
+        reference::extensions(&arguments, &attacks, semantics)
+            .expect("Frameworks are small enough")
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Compare the extensions after every update against the reference.
+fn check<S: ArgumentationFrameworkSemantic>(
+    af: &ArbitraryAf,
+    updates: &[(Index, bool)],
+    semantics: ReferenceSemantics,
+    config: FrameworkConfig,
+) -> Result<(), TestCaseError> {
+    let mut framework = ArgumentationFramework::<S>::new_with_config(&af.program(), config)
+        .expect("Loading the framework");
+    let mut state = State {
+        enabled: HashSet::new(),
+    };
+    let patches = patches(af, updates);
+    for patch in [None].into_iter().chain(patches.iter().map(Some)) {
+        if let Some(patch) = patch {
+            framework.apply_patch(patch).expect("Applying the patch");
+            state.apply(patch);
+        }
+        prop_assert_eq!(
+            all_extensions(&mut framework),
+            state.reference(af, semantics),
+            "{:?} after {:?}",
+            semantics,
+            patch
+        );
+    }
+    Ok(())
+}
+
+fn af_with_updates() -> impl Strategy<Value = (ArbitraryAf, Vec<(Index, bool)>)> {
+    (
+        frameworks(MAX_ARGUMENTS),
+        proptest::collection::vec((any::<Index>(), any::<bool>()), 0..6),
+    )
+}
+
+fn decomposed() -> FrameworkConfig {
+    FrameworkConfig {
+        decompose: true,
+        ..Default::default()
+    }
+}
+
+fn config() -> ProptestConfig {
+    // Conflict-free sets of large frameworks are numerous, keep the number small for CI
+    ProptestConfig::with_cases(16)
+}
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn conflict_free_matches_reference((af, updates) in af_with_updates()) {
+        check::<ConflictFree>(&af, &updates, ReferenceSemantics::ConflictFree, Default::default())?;
+    }
+
+    #[test]
+    fn admissible_matches_reference((af, updates) in af_with_updates()) {
+        check::<Admissible>(&af, &updates, ReferenceSemantics::Admissible, Default::default())?;
+    }
+
+    #[test]
+    fn complete_matches_reference((af, updates) in af_with_updates()) {
+        check::<Complete>(&af, &updates, ReferenceSemantics::Complete, decomposed())?;
+    }
+
+    #[test]
+    fn complete_encoding_matches_reference((af, updates) in af_with_updates()) {
+        check::<Complete>(&af, &updates, ReferenceSemantics::Complete, Default::default())?;
+    }
+
+    #[test]
+    fn stable_matches_reference((af, updates) in af_with_updates()) {
+        check::<Stable>(&af, &updates, ReferenceSemantics::Stable, decomposed())?;
+    }
+
+    #[test]
+    fn grounded_matches_reference((af, updates) in af_with_updates()) {
+        check::<Ground>(&af, &updates, ReferenceSemantics::Grounded, Default::default())?;
+    }
+
+    #[test]
+    fn preferred_matches_reference((af, updates) in af_with_updates()) {
+        check::<Preferred>(&af, &updates, ReferenceSemantics::Preferred, Default::default())?;
+    }
+
+    #[test]
+    fn cf2_matches_reference((af, updates) in af_with_updates()) {
+        check::<Cf2>(&af, &updates, ReferenceSemantics::Cf2, Default::default())?;
+    }
+
+    #[test]
+    fn decomposed_cf2_matches_reference((af, updates) in af_with_updates()) {
+        check::<Cf2>(&af, &updates, ReferenceSemantics::Cf2, decomposed())?;
+    }
+
+    #[test]
+    fn strongly_admissible_matches_reference((af, updates) in af_with_updates()) {
+        check::<StronglyAdmissible>(
+            &af,
+            &updates,
+            ReferenceSemantics::StronglyAdmissible,
+            Default::default(),
+        )?;
+    }
+
+    #[test]
+    fn resolution_grounded_matches_reference((af, updates) in af_with_updates()) {
+        check::<ResolutionGrounded>(
+            &af,
+            &updates,
+            ReferenceSemantics::ResolutionGrounded,
+            Default::default(),
+        )?;
+    }
+}
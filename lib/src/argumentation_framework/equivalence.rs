@@ -0,0 +1,171 @@
+//! Equivalence of two frameworks under a semantics
+//!
+//! Two frameworks are *standard equivalent* if they have the same extensions. They are
+//! *strong expansion equivalent* if they stay standard equivalent after adding the same
+//! arguments and attacks to both. The latter is decided syntactically, as shown by
+//! Oikarinen and Woltran: the frameworks need to have the same arguments and the same
+//! [`Kernel`], i.e. the same attacks after dropping those that cannot matter under the
+//! semantics.
+//!
+//! # Example
+//! ```
+//! use lib::argumentation_framework::{equivalence::{self, Kernel}, parse_initial_file};
+//!
+//! let (args_a, attacks_a) = parse_initial_file("arg(a). arg(b). att(a,a). att(a,b).").unwrap();
+//! let (args_b, attacks_b) = parse_initial_file("arg(a). arg(b). att(a,a).").unwrap();
+//! // The self-attacking argument cannot defeat others under stable semantics
+//! let witness = equivalence::strong((&args_a, &attacks_a), (&args_b, &attacks_b), Kernel::Stable);
+//! assert_eq!(witness, None);
+//! ```
+use std::collections::BTreeSet;
+
+use fallible_iterator::FallibleIterator;
+
+use super::{
+    semantics::ArgumentationFrameworkSemantic, symbols, ArgumentID, ArgumentationFramework,
+    Extension, FrameworkConfig,
+};
+use crate::{Framework, Result};
+
+/// Which of the two compared frameworks a witness belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    First,
+    Second,
+}
+
+/// Reason why two frameworks are not equivalent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Witness {
+    /// Extension of only one of the frameworks
+    Extension { extension: Extension, only_in: Side },
+    /// Argument of only one of the frameworks
+    Argument { id: ArgumentID, only_in: Side },
+    /// Attack in the [`Kernel`] of only one of the frameworks, so that some expansion
+    /// distinguishes them
+    Attack {
+        attack: symbols::Attack,
+        only_in: Side,
+    },
+}
+
+/// Kernel of a semantics, characterizing its strong expansion equivalence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kernel {
+    Admissible,
+    Complete,
+    Grounded,
+    Stable,
+}
+
+impl Kernel {
+    /// Whether the attack between two distinct arguments is kept in the kernel.
+    fn keeps(self, from: &str, to: &str, attacked: impl Fn(&str, &str) -> bool) -> bool {
+        if from == to {
+            return true;
+        }
+        let self_from = attacked(from, from);
+        let self_to = attacked(to, to);
+        let removed = match self {
+            Kernel::Stable => self_from,
+            Kernel::Admissible => self_from && (attacked(to, from) || self_to),
+            Kernel::Grounded => self_to && (self_from || attacked(to, from)),
+            Kernel::Complete => self_from && self_to,
+        };
+        !removed
+    }
+
+    /// Attacks of the kernel, as pairs of argument ids.
+    pub fn attacks(self, attacks: &[symbols::Attack]) -> BTreeSet<(ArgumentID, ArgumentID)> {
+        let all: BTreeSet<(&str, &str)> = attacks
+            .iter()
+            .map(|attack| (attack.from.as_str(), attack.to.as_str()))
+            .collect();
+        let attacked = |from: &str, to: &str| all.contains(&(from, to));
+        all.iter()
+            .filter(|(from, to)| self.keeps(from, to, attacked))
+            .map(|&(from, to)| (from.to_owned(), to.to_owned()))
+            .collect()
+    }
+}
+
+/// Check standard equivalence, returning an extension of only one framework if they differ.
+///
+/// Optional elements are disabled, like in a freshly loaded framework.
+pub fn standard<S: ArgumentationFrameworkSemantic>(
+    first: (&[symbols::Argument], &[symbols::Attack]),
+    second: (&[symbols::Argument], &[symbols::Attack]),
+    config: &FrameworkConfig,
+) -> Result<Option<Witness>> {
+    let extensions = |(arguments, attacks): (&[symbols::Argument], &[symbols::Attack])| {
+        let config = FrameworkConfig {
+            models: None,
+            ..config.clone()
+        };
+        let mut af = ArgumentationFramework::<S>::from_elements(
+            arguments.to_vec(),
+            attacks.to_vec(),
+            config,
+        )?;
+        let extensions: BTreeSet<Extension> = af.enumerate_extensions()?.by_ref().collect()?;
+        Ok::<_, crate::Error>(extensions)
+    };
+    let (first, second) = (extensions(first)?, extensions(second)?);
+    let witness = first
+        .difference(&second)
+        .map(|extension| (extension, Side::First))
+        .chain(second.difference(&first).map(|ext| (ext, Side::Second)))
+        .next()
+        .map(|(extension, only_in)| Witness::Extension {
+            extension: extension.clone(),
+            only_in,
+        });
+    Ok(witness)
+}
+
+/// Check strong expansion equivalence, returning the first difference if they are not.
+///
+/// Optional elements are treated like all others, i.e. as enabled.
+pub fn strong(
+    (first_arguments, first_attacks): (&[symbols::Argument], &[symbols::Attack]),
+    (second_arguments, second_attacks): (&[symbols::Argument], &[symbols::Attack]),
+    kernel: Kernel,
+) -> Option<Witness> {
+    let ids = |arguments: &[symbols::Argument]| -> BTreeSet<ArgumentID> {
+        arguments.iter().map(|arg| arg.id.clone()).collect()
+    };
+    let (first_ids, second_ids) = (ids(first_arguments), ids(second_arguments));
+    let argument = first_ids
+        .difference(&second_ids)
+        .map(|id| (id, Side::First))
+        .chain(
+            second_ids
+                .difference(&first_ids)
+                .map(|id| (id, Side::Second)),
+        )
+        .next()
+        .map(|(id, only_in)| Witness::Argument {
+            id: id.clone(),
+            only_in,
+        });
+    if argument.is_some() {
+        return argument;
+    }
+    let (first, second) = (
+        kernel.attacks(first_attacks),
+        kernel.attacks(second_attacks),
+    );
+    first
+        .difference(&second)
+        .map(|attack| (attack, Side::First))
+        .chain(
+            second
+                .difference(&first)
+                .map(|attack| (attack, Side::Second)),
+        )
+        .next()
+        .map(|((from, to), only_in)| Witness::Attack {
+            attack: symbols::Attack::new(from.as_str(), to.as_str(), false),
+            only_in,
+        })
+}
@@ -0,0 +1,118 @@
+//! Explanations of acceptance answers
+//!
+//! A rejected argument is explained by its diagnoses: the smallest sets of enabled
+//! optional attacks whose removal makes the argument credulously accepted. As only
+//! optional elements can be disabled by updates, every diagnosis corresponds to an
+//! update line, see [`Diagnosis::update_line`].
+use std::{collections::BTreeSet, fmt::Write};
+
+use super::{clingo, semantics::ArgumentationFrameworkSemantic, symbols, FrameworkConfig};
+use crate::{Error, Result};
+
+/// Enabled optional attacks whose removal makes an argument credulously accepted.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Diagnosis {
+    /// The attacks to remove, ordered by their arguments
+    pub attacks: Vec<symbols::Attack>,
+}
+
+impl Diagnosis {
+    /// Update line in APXM format disabling all attacks, i.e. `-att(a,b):att(c,b).`
+    ///
+    /// `None` for the empty diagnosis of an argument that is accepted already.
+    pub fn update_line(&self) -> Option<String> {
+        let atoms: Vec<_> = self.attacks.iter().map(ToString::to_string).collect();
+        (!atoms.is_empty()).then(|| format!("-{}.", atoms.join(":")))
+    }
+}
+
+/// Compute all diagnoses of minimum size for the argument.
+///
+/// Enabled optional attacks are given as optional, all other elements as not optional.
+/// Returns only the empty diagnosis if the argument is accepted already, and none if
+/// removing all enabled optional attacks does not suffice. Fails for semantics with an
+/// [`ArgumentationFrameworkSemantic::EXTREMUM`], as the models are only candidates.
+pub(super) fn diagnoses<S: ArgumentationFrameworkSemantic>(
+    arguments: &[symbols::Argument],
+    attacks: &[symbols::Attack],
+    argument: &symbols::Argument,
+    config: &FrameworkConfig,
+    logger: clingo::Logger,
+) -> Result<Vec<Diagnosis>> {
+    if S::base().trim().is_empty() {
+        return Err(Error::Logic(String::from(
+            "Explanations need a clingo encoding of the semantics",
+        )));
+    }
+    if S::EXTREMUM.is_some() {
+        // Accepted in some model is not accepted in some ⊆-extremal one
+        return Err(Error::Logic(String::from(
+            "Explanations are not supported for semantics of ⊆-extremal models",
+        )));
+    }
+    if !arguments.iter().any(|arg| arg.id == argument.id) {
+        return Err(Error::Logic(format!(
+            "The argument {} is not part of the framework",
+            argument.id
+        )));
+    }
+    let program = program::<S>(arguments, attacks, argument);
+    let models = clingo::optimal_models(&program, config, logger)?;
+    let mut diagnoses = BTreeSet::new();
+    for shown in models {
+        let mut attacks = shown
+            .into_iter()
+            .map(|symbol| {
+                let [from, to] = symbol.arguments()?[..] else {
+                    return Err(Error::Logic(format!("Unexpected symbol {symbol}")));
+                };
+                Ok(symbols::Attack::new(
+                    symbols::symbol_id(from)?,
+                    symbols::symbol_id(to)?,
+                    true,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        attacks.sort();
+        diagnoses.insert(Diagnosis { attacks });
+    }
+    Ok(diagnoses.into_iter().collect())
+}
+
+/// Program guessing the removed attacks, minimizing their number.
+fn program<S: ArgumentationFrameworkSemantic>(
+    arguments: &[symbols::Argument],
+    attacks: &[symbols::Attack],
+    argument: &symbols::Argument,
+) -> String {
+    let mut program = String::new();
+    for arg in arguments {
+        let id = symbols::clingo_term(&arg.id);
+        writeln!(program, "argument({id}).").expect("Writing to a String never fails");
+    }
+    for attack in attacks {
+        let (from, to) = (
+            symbols::clingo_term(&attack.from),
+            symbols::clingo_term(&attack.to),
+        );
+        let kind = if attack.optional {
+            "removable"
+        } else {
+            "attack"
+        };
+        writeln!(program, "{kind}({from}, {to}).").expect("Writing to a String never fails");
+    }
+    program += S::base();
+    program += &format!(
+        r#"
+            {{ removed(X, Y) }} :- removable(X, Y).
+            attack(X, Y) :- removable(X, Y), not removed(X, Y).
+            :- not in({}).
+            #minimize {{ 1,X,Y : removed(X, Y) }}.
+            #show removed/2.
+            #project removed/2.
+        "#,
+        symbols::clingo_term(&argument.id)
+    );
+    program
+}
@@ -0,0 +1,63 @@
+//! Subset-maximal and subset-minimal extensions of a semantics
+//!
+//! Clingo enumerates them directly with a domain heuristic on `in/1` and
+//! `--enum-mode=domRec`, so that e.g. the preferred extensions are found as the
+//! maximal admissible ones without an encoding of their own, see
+//! [`Preferred`](crate::semantics::Preferred). Extensions computed without clingo are
+//! filtered instead.
+use super::{Extension, FrameworkConfig, Heuristic};
+
+/// Which extensions to keep, compared by set inclusion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Extremum {
+    /// Extensions that are no subset of another extension
+    Maximal,
+    /// Extensions that are no superset of another extension
+    Minimal,
+}
+
+impl Extremum {
+    /// Heuristic whose recorded models are exactly the extrema, with `--enum-mode=domRec`.
+    pub(crate) fn heuristic(self) -> Heuristic {
+        match self {
+            Extremum::Maximal => Heuristic::PreferIn,
+            Extremum::Minimal => Heuristic::PreferOut,
+        }
+    }
+    /// Configure clingo to enumerate only the extrema of the models.
+    pub(crate) fn configure(self, config: FrameworkConfig) -> FrameworkConfig {
+        let mut clingo_args = config.clingo_args;
+        clingo_args.push(String::from("--enum-mode=domRec"));
+        FrameworkConfig {
+            heuristic: self.heuristic(),
+            clingo_args,
+            ..config
+        }
+    }
+}
+
+/// Keep only the extrema of the given extensions.
+///
+/// # Example
+/// ```
+/// # use lib::argumentation_framework::{extremal::{self, Extremum}, Extension};
+/// let ext = |ids: &[&str]| -> Extension { ids.iter().map(|id| id.to_string()).collect() };
+/// let extensions = vec![ext(&[]), ext(&["a"]), ext(&["a", "b"]), ext(&["c"])];
+/// let maximal = extremal::filter(extensions, Extremum::Maximal);
+/// assert_eq!(maximal, vec![ext(&["a", "b"]), ext(&["c"])]);
+/// ```
+pub fn filter(extensions: Vec<Extension>, extremum: Extremum) -> Vec<Extension> {
+    let is_dominated = |ext: &Extension, other: &Extension| match extremum {
+        Extremum::Maximal => ext.is_subset(other),
+        Extremum::Minimal => other.is_subset(ext),
+    };
+    extensions
+        .iter()
+        .filter(|ext| {
+            !extensions
+                .iter()
+                .any(|other| other != *ext && is_dominated(ext, other))
+        })
+        .cloned()
+        .collect()
+}
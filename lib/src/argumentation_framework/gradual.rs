@@ -0,0 +1,255 @@
+//! Gradual semantics on weighted frameworks
+//!
+//! A gradual semantics assigns every argument a degree of acceptability in [0, 1]
+//! instead of accepting sets of arguments. Every argument has a basic weight, its degree
+//! without any attackers, which the attackers lower depending on the [`Aggregation`]
+//! of their degrees, as defined by Amgoud et al. for weighted frameworks.
+//!
+//! Like the [`GroundedLabelling`](super::grounded::GroundedLabelling), the degrees are
+//! kept up to date under patches. The degree of an argument only depends on the
+//! arguments reaching it via attacks, so only the cone of the changed element is
+//! iterated to its fixpoint again, starting from the previous degrees.
+//!
+//! # Example
+//! ```
+//! use lib::argumentation_framework::{gradual::{Aggregation, GradualScores}, parse_initial_file};
+//!
+//! let (args, attacks) = parse_initial_file("arg(a). arg(b). arg(c). att(a,c). att(b,c).").unwrap();
+//! let scores = GradualScores::new(Aggregation::Max, &args, &attacks).unwrap();
+//! assert_eq!(scores.score("c"), Some(0.5));
+//! ```
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::{symbols, ArgumentID, Patch};
+use crate::{Error, Result};
+
+/// Largest change of a degree between two iterations at which they are considered final
+const TOLERANCE: f64 = 1e-12;
+/// Iterations after which the degrees are kept, even if they did not converge yet
+const MAX_ITERATIONS: usize = 10_000;
+
+/// How the degrees of the attackers of an argument lower its weight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    /// Weighted h-categoriser, `w(a) / (1 + Σ Deg(b))`
+    Sum,
+    /// Weighted max-based semantics, `w(a) / (1 + max Deg(b))`
+    Max,
+    /// Weighted card-based semantics, `w(a) / (1 + n + Σ Deg(b) / n)`, where only the
+    /// `n` attackers of positive degree are considered
+    Card,
+}
+
+impl Aggregation {
+    /// Degree of an argument with the given weight and degrees of its attackers.
+    fn degree(self, weight: f64, attackers: impl Iterator<Item = f64>) -> f64 {
+        match self {
+            Aggregation::Sum => weight / (1.0 + attackers.sum::<f64>()),
+            Aggregation::Max => weight / (1.0 + attackers.fold(0.0, f64::max)),
+            Aggregation::Card => {
+                let (count, sum) = attackers
+                    .filter(|&degree| degree > 0.0)
+                    .fold((0.0, 0.0), |(count, sum), degree| {
+                        (count + 1.0, sum + degree)
+                    });
+                if count == 0.0 {
+                    weight
+                } else {
+                    weight / (1.0 + count + sum / count)
+                }
+            }
+        }
+    }
+}
+
+/// Degrees of all arguments of a framework that are kept up to date under patches.
+///
+/// Like in the solver backend, optional elements start disabled. All weights start at 1.
+#[derive(Debug, Clone)]
+pub struct GradualScores {
+    aggregation: Aggregation,
+    index: HashMap<ArgumentID, usize>,
+    ids: Vec<ArgumentID>,
+    weights: Vec<f64>,
+    /// Whether the argument is currently part of the framework
+    active: Vec<bool>,
+    /// Enabled attacks, also between inactive arguments
+    attacks: HashSet<(usize, usize)>,
+    attackers: Vec<Vec<usize>>,
+    successors: Vec<Vec<usize>>,
+    /// Degrees of active arguments, meaningless for inactive ones
+    degrees: Vec<f64>,
+}
+
+impl GradualScores {
+    /// Compute the degrees of all arguments of the given framework.
+    pub fn new(
+        aggregation: Aggregation,
+        arguments: &[symbols::Argument],
+        attacks: &[symbols::Attack],
+    ) -> Result<Self> {
+        let mut scores = GradualScores {
+            aggregation,
+            index: HashMap::new(),
+            ids: vec![],
+            weights: vec![],
+            active: vec![],
+            attacks: HashSet::new(),
+            attackers: vec![],
+            successors: vec![],
+            degrees: vec![],
+        };
+        scores.add(arguments, attacks)?;
+        Ok(scores)
+    }
+    /// Add arguments and attacks that were not known before.
+    pub fn add(&mut self, arguments: &[symbols::Argument], attacks: &[symbols::Attack]) -> Result {
+        let mut changed = vec![];
+        for argument in arguments {
+            if self.index.contains_key(&argument.id) {
+                continue;
+            }
+            let arg = self.ids.len();
+            self.index.insert(argument.id.clone(), arg);
+            self.ids.push(argument.id.clone());
+            self.weights.push(1.0);
+            self.active.push(!argument.optional);
+            self.attackers.push(vec![]);
+            self.successors.push(vec![]);
+            self.degrees.push(1.0);
+            changed.push(arg);
+        }
+        for attack in attacks.iter().filter(|attack| !attack.optional) {
+            let (from, to) = self.resolve(attack)?;
+            if self.connect(from, to) {
+                changed.push(to);
+            }
+        }
+        self.rescore(changed);
+        Ok(())
+    }
+    /// Enable or disable the element targeted by the patch and rescore its cone.
+    ///
+    /// Patches that do not change the framework are ignored.
+    pub fn apply_patch(&mut self, patch: &Patch) -> Result {
+        let changed = match patch {
+            Patch::EnableArgument(argument) | Patch::DisableArgument(argument) => {
+                let arg = self.argument(&argument.id)?;
+                let enable = matches!(patch, Patch::EnableArgument(_));
+                (self.active[arg] != enable).then(|| {
+                    self.active[arg] = enable;
+                    arg
+                })
+            }
+            Patch::EnableAttack(attack) => {
+                let (from, to) = self.resolve(attack)?;
+                self.connect(from, to).then_some(to)
+            }
+            Patch::DisableAttack(attack) => {
+                let (from, to) = self.resolve(attack)?;
+                self.disconnect(from, to).then_some(to)
+            }
+        };
+        self.rescore(changed);
+        Ok(())
+    }
+    /// Change the basic weight of the argument, which has to be in [0, 1].
+    pub fn set_weight(&mut self, id: &str, weight: f64) -> Result {
+        if !(0.0..=1.0).contains(&weight) {
+            return Err(Error::Logic(format!(
+                "The weight of {id} has to be in [0, 1], got {weight}"
+            )));
+        }
+        let arg = self.argument(id)?;
+        self.weights[arg] = weight;
+        self.rescore([arg]);
+        Ok(())
+    }
+    /// The aggregation the degrees are computed with.
+    pub fn aggregation(&self) -> Aggregation {
+        self.aggregation
+    }
+    /// Degree of the argument, `None` if it is not part of the framework.
+    pub fn score(&self, id: &str) -> Option<f64> {
+        let &arg = self.index.get(id)?;
+        self.active[arg].then_some(self.degrees[arg])
+    }
+    /// Degrees of all arguments currently part of the framework.
+    pub fn scores(&self) -> impl Iterator<Item = (&ArgumentID, f64)> {
+        self.ids
+            .iter()
+            .zip(&self.degrees)
+            .zip(&self.active)
+            .filter(|(_, &active)| active)
+            .map(|((id, &degree), _)| (id, degree))
+    }
+
+    fn argument(&self, id: &str) -> Result<usize> {
+        self.index
+            .get(id)
+            .copied()
+            .ok_or_else(|| Error::Logic(format!("Unknown argument {id}")))
+    }
+
+    fn resolve(&self, attack: &symbols::Attack) -> Result<(usize, usize)> {
+        Ok((self.argument(&attack.from)?, self.argument(&attack.to)?))
+    }
+
+    /// Add the attack, returns whether it was missing.
+    fn connect(&mut self, from: usize, to: usize) -> bool {
+        if !self.attacks.insert((from, to)) {
+            return false;
+        }
+        self.successors[from].push(to);
+        self.attackers[to].push(from);
+        true
+    }
+
+    /// Remove the attack, returns whether it was present.
+    fn disconnect(&mut self, from: usize, to: usize) -> bool {
+        if !self.attacks.remove(&(from, to)) {
+            return false;
+        }
+        self.successors[from].retain(|&succ| succ != to);
+        self.attackers[to].retain(|&attacker| attacker != from);
+        true
+    }
+
+    /// Iterate the degrees of everything reachable from the changed arguments to their
+    /// fixpoint, keeping all others.
+    fn rescore(&mut self, changed: impl IntoIterator<Item = usize>) {
+        let mut in_cone = vec![false; self.ids.len()];
+        let mut cone = vec![];
+        let mut queue: VecDeque<_> = changed.into_iter().collect();
+        while let Some(arg) = queue.pop_front() {
+            if in_cone[arg] {
+                continue;
+            }
+            in_cone[arg] = true;
+            if self.active[arg] {
+                cone.push(arg);
+            }
+            queue.extend(&self.successors[arg]);
+        }
+        log::trace!("Rescoring cone of {} arguments", cone.len());
+        for iteration in 0.. {
+            let mut change: f64 = 0.0;
+            for &arg in &cone {
+                let attackers = self.attackers[arg]
+                    .iter()
+                    .filter(|&&attacker| self.active[attacker])
+                    .map(|&attacker| self.degrees[attacker]);
+                let degree = self.aggregation.degree(self.weights[arg], attackers);
+                change = change.max((degree - self.degrees[arg]).abs());
+                self.degrees[arg] = degree;
+            }
+            if change < TOLERANCE {
+                break;
+            }
+            if iteration == MAX_ITERATIONS {
+                log::warn!("The degrees did not converge, the largest change is {change}");
+                break;
+            }
+        }
+    }
+}
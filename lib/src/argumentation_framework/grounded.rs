@@ -0,0 +1,195 @@
+//! Incremental maintenance of the grounded labelling
+//!
+//! The grounded labelling of an argument only depends on the arguments that reach it
+//! via attacks. After enabling or disabling an element, only the cone of influence,
+//! i.e. everything reachable from the changed element, is relabelled. Labels outside
+//! the cone are kept and act as fixed inputs of the cone's least fixpoint.
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::{decomposition::Label, symbols, ArgumentID, Extension, Patch};
+use crate::{Error, Result};
+
+/// Grounded labelling of a framework that is kept up to date under patches.
+///
+/// Like in the solver backend, optional elements start disabled.
+#[derive(Debug, Clone)]
+pub struct GroundedLabelling {
+    index: HashMap<ArgumentID, usize>,
+    ids: Vec<ArgumentID>,
+    /// Whether the argument is currently part of the framework
+    active: Vec<bool>,
+    /// Enabled attacks, also between inactive arguments
+    attacks: HashSet<(usize, usize)>,
+    attackers: Vec<Vec<usize>>,
+    successors: Vec<Vec<usize>>,
+    /// Labels of active arguments, meaningless for inactive ones
+    labels: Vec<Label>,
+}
+
+impl GroundedLabelling {
+    /// Compute the grounded labelling of the given framework.
+    pub fn new(arguments: &[symbols::Argument], attacks: &[symbols::Attack]) -> Result<Self> {
+        let mut labelling = GroundedLabelling {
+            index: HashMap::new(),
+            ids: vec![],
+            active: vec![],
+            attacks: HashSet::new(),
+            attackers: vec![],
+            successors: vec![],
+            labels: vec![],
+        };
+        labelling.add(arguments, attacks)?;
+        Ok(labelling)
+    }
+    /// Add arguments and attacks that were not known before.
+    pub fn add(&mut self, arguments: &[symbols::Argument], attacks: &[symbols::Attack]) -> Result {
+        let mut changed = vec![];
+        for argument in arguments {
+            if self.index.contains_key(&argument.id) {
+                continue;
+            }
+            let arg = self.ids.len();
+            self.index.insert(argument.id.clone(), arg);
+            self.ids.push(argument.id.clone());
+            self.active.push(!argument.optional);
+            self.attackers.push(vec![]);
+            self.successors.push(vec![]);
+            self.labels.push(Label::Undec);
+            changed.push(arg);
+        }
+        for attack in attacks.iter().filter(|attack| !attack.optional) {
+            let (from, to) = self.resolve(attack)?;
+            if self.connect(from, to) {
+                changed.push(to);
+            }
+        }
+        self.relabel(changed);
+        Ok(())
+    }
+    /// Enable or disable the element targeted by the patch and relabel its cone.
+    ///
+    /// Patches that do not change the framework are ignored.
+    pub fn apply_patch(&mut self, patch: &Patch) -> Result {
+        let changed = match patch {
+            Patch::EnableArgument(argument) | Patch::DisableArgument(argument) => {
+                let arg = self.argument(&argument.id)?;
+                let enable = matches!(patch, Patch::EnableArgument(_));
+                (self.active[arg] != enable).then(|| {
+                    self.active[arg] = enable;
+                    arg
+                })
+            }
+            Patch::EnableAttack(attack) => {
+                let (from, to) = self.resolve(attack)?;
+                self.connect(from, to).then_some(to)
+            }
+            Patch::DisableAttack(attack) => {
+                let (from, to) = self.resolve(attack)?;
+                self.disconnect(from, to).then_some(to)
+            }
+        };
+        self.relabel(changed);
+        Ok(())
+    }
+    /// Label of the argument, `None` if it is not part of the framework.
+    pub fn label(&self, id: &str) -> Option<Label> {
+        let &arg = self.index.get(id)?;
+        self.active[arg].then_some(self.labels[arg])
+    }
+    /// Labels of all arguments currently part of the framework.
+    pub fn labels(&self) -> impl Iterator<Item = (&ArgumentID, Label)> {
+        self.ids
+            .iter()
+            .zip(&self.labels)
+            .zip(&self.active)
+            .filter(|(_, &active)| active)
+            .map(|((id, &label), _)| (id, label))
+    }
+    /// The grounded extension, i.e. all arguments labelled in.
+    pub fn extension(&self) -> Extension {
+        self.labels()
+            .filter(|(_, label)| *label == Label::In)
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    fn argument(&self, id: &str) -> Result<usize> {
+        self.index
+            .get(id)
+            .copied()
+            .ok_or_else(|| Error::Logic(format!("Unknown argument {id}")))
+    }
+
+    fn resolve(&self, attack: &symbols::Attack) -> Result<(usize, usize)> {
+        Ok((self.argument(&attack.from)?, self.argument(&attack.to)?))
+    }
+
+    /// Add the attack, returns whether it was missing.
+    fn connect(&mut self, from: usize, to: usize) -> bool {
+        if !self.attacks.insert((from, to)) {
+            return false;
+        }
+        self.successors[from].push(to);
+        self.attackers[to].push(from);
+        true
+    }
+
+    /// Remove the attack, returns whether it was present.
+    fn disconnect(&mut self, from: usize, to: usize) -> bool {
+        if !self.attacks.remove(&(from, to)) {
+            return false;
+        }
+        self.successors[from].retain(|&succ| succ != to);
+        self.attackers[to].retain(|&attacker| attacker != from);
+        true
+    }
+
+    /// Label as seen by the attacked arguments, inactive arguments attack nothing.
+    fn effective_label(&self, arg: usize) -> Label {
+        if self.active[arg] {
+            self.labels[arg]
+        } else {
+            Label::Out
+        }
+    }
+
+    /// Recompute the least fixpoint on everything reachable from the changed arguments.
+    fn relabel(&mut self, changed: impl IntoIterator<Item = usize>) {
+        let mut in_cone = vec![false; self.ids.len()];
+        let mut cone = vec![];
+        let mut queue: VecDeque<_> = changed.into_iter().collect();
+        while let Some(arg) = queue.pop_front() {
+            if in_cone[arg] {
+                continue;
+            }
+            in_cone[arg] = true;
+            cone.push(arg);
+            self.labels[arg] = Label::Undec;
+            queue.extend(&self.successors[arg]);
+        }
+        log::trace!("Relabelling cone of {} arguments", cone.len());
+        // Arguments of the cone whose attackers might allow a decision
+        let mut queue: VecDeque<_> = cone.into_iter().collect();
+        while let Some(arg) = queue.pop_front() {
+            if !self.active[arg] || self.labels[arg] != Label::Undec {
+                continue;
+            }
+            let mut attackers = self.attackers[arg]
+                .iter()
+                .map(|&attacker| self.effective_label(attacker));
+            let label = if attackers.clone().all(|label| label == Label::Out) {
+                Label::In
+            } else if attackers.any(|label| label == Label::In) {
+                Label::Out
+            } else {
+                continue;
+            };
+            self.labels[arg] = label;
+            queue.extend(
+                self.successors[arg]
+                    .iter()
+                    .filter(|&&succ| in_cone[succ] && self.labels[succ] == Label::Undec),
+            );
+        }
+    }
+}
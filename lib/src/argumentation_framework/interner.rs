@@ -0,0 +1,100 @@
+//! Interning of argument ids, scoped to a framework
+//!
+//! Extensions store their arguments as indices into the interner of the framework they
+//! were computed for, see [`Extension`](super::Extension). The interner is freed along
+//! with the framework and the last of its extensions.
+use std::{
+    collections::HashMap,
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use super::symbols;
+
+/// Argument ids by index, shared by a framework and its extensions.
+#[derive(Debug, Default)]
+pub struct Interner {
+    inner: RwLock<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    /// Index of every interned id
+    index: HashMap<String, usize>,
+    /// Interned arguments by index
+    arguments: Vec<Arc<symbols::Argument>>,
+    /// Number of leading indices whose ids are ascending
+    sorted: usize,
+}
+
+impl Interner {
+    /// Interner with the given ids, indexed in ascending order.
+    pub fn with_ids<'a>(ids: impl IntoIterator<Item = &'a str>) -> Arc<Self> {
+        let mut ids: Vec<_> = ids.into_iter().collect();
+        ids.sort_unstable();
+        ids.dedup();
+        let interner = Interner::default();
+        for id in ids {
+            interner.intern(id);
+        }
+        Arc::new(interner)
+    }
+
+    /// Index of the argument with the given id, interning it if necessary.
+    pub fn intern(&self, id: &str) -> usize {
+        if let Some(idx) = self.lookup(id) {
+            return idx;
+        }
+        let mut inner = self.write();
+        // Another thread may have interned the id in between
+        if let Some(&idx) = inner.index.get(id) {
+            return idx;
+        }
+        let idx = inner.arguments.len();
+        if inner.sorted == idx && inner.arguments.last().map_or(true, |last| *last.id < *id) {
+            inner.sorted += 1;
+        }
+        inner
+            .arguments
+            .push(Arc::new(symbols::Argument::new(id, false)));
+        inner.index.insert(id.to_owned(), idx);
+        idx
+    }
+
+    /// Index of the argument with the given id, if it was interned before.
+    pub fn lookup(&self, id: &str) -> Option<usize> {
+        self.read().index.get(id).copied()
+    }
+
+    /// The non-optional arguments with the given indices.
+    ///
+    /// # Panics
+    ///
+    /// If any index was not returned by [`Interner::intern`].
+    pub fn resolve_all(
+        &self,
+        indices: impl IntoIterator<Item = usize>,
+    ) -> Vec<Arc<symbols::Argument>> {
+        let inner = self.read();
+        indices
+            .into_iter()
+            .map(|idx| inner.arguments[idx].clone())
+            .collect()
+    }
+
+    /// Number of leading indices that are ordered like their ids.
+    ///
+    /// Arguments interned after the initial ones, e.g. added by updates, may break
+    /// the order.
+    pub fn sorted(&self) -> usize {
+        self.read().sorted
+    }
+
+    // The interner is consistent after every operation, even if a holder panicked
+    fn read(&self) -> RwLockReadGuard<'_, Inner> {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Inner> {
+        self.inner.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
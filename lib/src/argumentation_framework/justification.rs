@@ -0,0 +1,127 @@
+//! Grounded justifications of accepted arguments
+//!
+//! An argument of the grounded extension is justified by a strongly admissible set
+//! containing it: its members are derived level by level, each defended by members of
+//! lower levels, starting from unattacked arguments. The smallest such set is found by
+//! clingo, minimizing over the [`StronglyAdmissible`] encoding.
+use std::{collections::HashSet, fmt::Write};
+
+use super::{
+    clingo, semantics::ArgumentationFrameworkSemantic, symbols, ArgumentID, FrameworkConfig,
+};
+use crate::{semantics::StronglyAdmissible, Error, Result};
+
+/// Strongly admissible set containing an argument, by derivation level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Justification {
+    /// Arguments of every level, defended by the arguments of lower levels
+    pub levels: Vec<Vec<ArgumentID>>,
+}
+
+impl Justification {
+    /// Number of arguments in the justification.
+    pub fn len(&self) -> usize {
+        self.levels.iter().map(Vec::len).sum()
+    }
+    /// Whether the justification contains no arguments.
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+}
+
+/// Compute a justification of minimum size for the argument.
+///
+/// All elements are considered part of the framework. Returns `None` if the argument
+/// is not part of the grounded extension.
+pub(super) fn minimum(
+    arguments: &[symbols::Argument],
+    attacks: &[symbols::Attack],
+    argument: &symbols::Argument,
+    config: &FrameworkConfig,
+    logger: clingo::Logger,
+) -> Result<Option<Justification>> {
+    if !arguments.iter().any(|arg| arg.id == argument.id) {
+        return Err(Error::Logic(format!(
+            "The argument {} is not part of the framework",
+            argument.id
+        )));
+    }
+    let models = clingo::optimal_models(&program(arguments, attacks, argument), config, logger)?;
+    let Some(shown) = models.into_iter().next() else {
+        return Ok(None);
+    };
+    let members = shown
+        .into_iter()
+        .map(|symbol| match symbol.arguments()?[..] {
+            [id] => Ok(symbols::symbol_id(id)?),
+            _ => Err(Error::Logic(format!("Unexpected symbol {symbol}"))),
+        })
+        .collect::<Result<HashSet<_>>>()?;
+    Ok(Some(Justification {
+        levels: levels(members, attacks),
+    }))
+}
+
+/// Derivation levels of a strongly admissible set.
+fn levels(mut remaining: HashSet<ArgumentID>, attacks: &[symbols::Attack]) -> Vec<Vec<ArgumentID>> {
+    let mut defeated = HashSet::new();
+    let mut levels = vec![];
+    while !remaining.is_empty() {
+        let mut level: Vec<_> = remaining
+            .iter()
+            .filter(|id| {
+                attacks
+                    .iter()
+                    .filter(|attack| &&attack.to == id)
+                    .all(|attack| defeated.contains(&attack.from))
+            })
+            .cloned()
+            .collect();
+        if level.is_empty() {
+            // Not strongly admissible, which the encoding rules out
+            break;
+        }
+        level.sort();
+        for id in &level {
+            remaining.remove(id);
+            defeated.extend(
+                attacks
+                    .iter()
+                    .filter(|attack| &attack.from == id)
+                    .map(|attack| attack.to.clone()),
+            );
+        }
+        levels.push(level);
+    }
+    levels
+}
+
+/// Program guessing a strongly admissible set with the argument, minimizing its size.
+fn program(
+    arguments: &[symbols::Argument],
+    attacks: &[symbols::Attack],
+    argument: &symbols::Argument,
+) -> String {
+    let mut program = String::new();
+    for arg in arguments {
+        let id = symbols::clingo_term(&arg.id);
+        writeln!(program, "argument({id}).").expect("Writing to a String never fails");
+    }
+    for attack in attacks {
+        let (from, to) = (
+            symbols::clingo_term(&attack.from),
+            symbols::clingo_term(&attack.to),
+        );
+        writeln!(program, "attack({from}, {to}).").expect("Writing to a String never fails");
+    }
+    program += StronglyAdmissible::BASE;
+    program += &format!(
+        r#"
+            :- not in({}).
+            #minimize {{ 1,X : in(X) }}.
+            #show in/1.
+        "#,
+        symbols::clingo_term(&argument.id)
+    );
+    program
+}
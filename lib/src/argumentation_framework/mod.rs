@@ -1,17 +1,43 @@
 //! Solver for Dung's Argumentation Frameworks.
-use std::{collections::BTreeSet, marker::PhantomData, sync::atomic::AtomicUsize};
+use std::sync::{atomic::AtomicUsize, Arc};
+#[cfg(feature = "clingo")]
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::{Hash, Hasher},
+    io::BufRead,
+    marker::PhantomData,
+    num::NonZeroUsize,
+    ops::ControlFlow,
+};
 
-use crate::{Error, Result};
-use ::clingo::{defaults::Non, ShowType, SolveMode, ToSymbol};
+use crate::{framework::GenericExtension, Result};
+#[cfg(feature = "clingo")]
+use ::clingo::{defaults::Non, ShowType, SolveMode, SolverLiteral, Symbol, SymbolType, ToSymbol};
+#[cfg(feature = "clingo")]
 use fallible_iterator::FallibleIterator;
 
-use self::{clingo::Logger, parser::parse_apx_tgf, semantics::ArgumentationFrameworkSemantic};
-
-use crate::{
-    framework::{GenericExtension, IterGuard},
-    Framework,
+use self::interner::Interner;
+use self::parser::{parse_apx_tgf, parse_apx_tgf_iccma};
+use self::updates::UpdateFormat;
+#[cfg(feature = "clingo")]
+use self::{
+    approximate::CountEstimate,
+    certify::Certificate,
+    clingo::Logger,
+    consequences::Consequences,
+    decomposition::Label,
+    extremal::Extremum,
+    gradual::{Aggregation, GradualScores},
+    grounded::GroundedLabelling,
+    profile::{Phase, Profile, Timer},
+    semantics::ArgumentationFrameworkSemantic,
+    statistics::Statistics,
+    updates::{Follow, UpdateLines},
 };
 
+#[cfg(feature = "clingo")]
+use crate::{framework, framework::IterGuard, Error, Framework, Objective};
+
 pub static ID_COUNTER: Counter = Counter::new();
 
 pub struct Counter(AtomicUsize);
@@ -27,15 +53,65 @@ impl Counter {
 }
 
 pub type ArgumentID = String;
+#[cfg(feature = "clingo")]
 type Control = ::clingo::GenericControl<clingo::Logger, Non, Non, Non>;
 
+#[cfg(feature = "clingo")]
+pub use config::{FrameworkConfig, Heuristic, ParallelMode, UndeclaredArguments};
+
+#[cfg(feature = "clingo")]
+pub mod approximate;
+#[cfg(feature = "clingo")]
+pub mod certify;
+#[cfg(feature = "clingo")]
 mod clingo;
+#[cfg(feature = "clingo")]
+mod config;
+#[cfg(feature = "clingo")]
+mod consequences;
+#[cfg(feature = "clingo")]
+pub mod decomposition;
+#[cfg(all(test, feature = "clingo"))]
+mod differential;
+#[cfg(feature = "clingo")]
+pub mod equivalence;
+#[cfg(feature = "clingo")]
+pub mod explanation;
+#[cfg(feature = "clingo")]
+pub mod extremal;
+pub mod gradual;
+#[cfg(feature = "clingo")]
+pub mod grounded;
+mod interner;
+#[cfg(feature = "clingo")]
+pub mod justification;
+#[cfg(feature = "clingo")]
+mod optimal;
 mod parser;
+#[cfg(feature = "clingo")]
+pub mod profile;
+#[cfg(all(test, feature = "clingo"))]
+mod properties;
+pub mod ranking;
+pub mod reference;
+#[cfg(feature = "clingo")]
+pub mod registry;
+#[cfg(feature = "clingo")]
+pub mod sampling;
+#[cfg(feature = "clingo")]
 pub mod semantics;
+#[cfg(feature = "clingo")]
+pub mod statistics;
 pub mod symbols;
-#[cfg(test)]
+#[cfg(all(test, feature = "clingo"))]
 mod tests;
+pub mod updates;
+#[cfg(feature = "clingo")]
+pub mod validation;
+#[cfg(feature = "clingo")]
+pub mod verification;
 
+#[cfg(feature = "clingo")]
 /// Dung's Argumentation Framework
 ///
 /// A simple graph with arguments (vertices) and attacks (edges).
@@ -63,10 +139,48 @@ mod tests;
 /// ```
 pub struct ArgumentationFramework<S: ArgumentationFrameworkSemantic> {
     clingo_ctl: Option<Control>,
-    _initial_file: String,
+    /// Solver literals of all symbolic atoms, to avoid scanning the atoms for every query
+    literals: HashMap<Symbol, SolverLiteral>,
+    /// Solver literals of the optional elements, the only ones patches can change
+    externals: HashMap<Symbol, SolverLiteral>,
+    /// Truth values of externals, assigned before the next solve
+    pending: HashMap<SolverLiteral, bool>,
+    /// Truth values of externals as known to clingo, `false` if missing
+    assigned: HashMap<SolverLiteral, bool>,
+    /// All applied patches that were not undone, latest last
+    history: Vec<Patch>,
+    /// Patches restoring the state before every patch of the history
+    undo_log: Vec<Patch>,
+    /// All arguments, including those added after loading the initial file
+    arguments: Vec<symbols::Argument>,
+    /// All attacks, including those added after loading the initial file
+    attacks: Vec<symbols::Attack>,
+    /// Grounded labelling kept up to date under patches, if [`ArgumentationFrameworkSemantic::GROUNDED`]
+    grounded: Option<GroundedLabelling>,
+    /// Degrees kept up to date under patches, once requested by [`ArgumentationFramework::track_gradual`]
+    gradual: Option<GradualScores>,
+    /// Results by hashed framework state, if [`FrameworkConfig::cache`]
+    cache: HashMap<u64, CachedResult>,
+    /// Timings of all phases, only recorded with the `profiling` feature
+    profile: Profile,
+    /// Argument ids of the extensions of this framework
+    interner: Arc<Interner>,
+    /// Warnings reported by clingo since the last solve
+    warnings: clingo::Collected,
+    /// Statistics of the last solve by clingo, if any
+    last_statistics: Option<Statistics>,
+    config: FrameworkConfig,
     _semantics: PhantomData<S>,
 }
 
+#[cfg(feature = "clingo")]
+/// Results of earlier queries on a framework state.
+#[derive(Debug, Default)]
+struct CachedResult {
+    count: Option<usize>,
+    extensions: Option<Vec<Extension>>,
+}
+
 /// An update to the [`ArgumentationFramework`].
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Patch {
@@ -81,7 +195,7 @@ pub enum Patch {
 }
 
 impl Patch {
-    /// Parse a full update line in APXM or TGFM format.
+    /// Parse a full update line in APXM, TGFM or numeric ICCMA 2023 format.
     ///
     /// # Example
     ///
@@ -120,152 +234,1621 @@ impl Patch {
     ///         Patch::DisableArgument(Argument::new("a3", false)),
     ///     ]
     /// );
+    /// assert_eq!(patches[0].to_string(), "-arg(a3).");
     /// ```
     pub fn parse_line(input: &str) -> Result<Vec<Self>> {
         let patches = parser::parse_apxm_tgfm_patch_line(input)?;
         Ok(patches)
     }
+    /// Parse a full update line like [`Patch::parse_line`], together with its timestamp.
+    ///
+    /// Update lines may start with a timestamp, i.e. `@1234: +arg(a).`, which is ignored
+    /// by [`Patch::parse_line`]. Comment lines, starting with `%` or `#`, contain no patches.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use lib::argumentation_framework::{symbols::Argument, Patch};
+    /// let (timestamp, patches) = Patch::parse_timed_line("@1234: +arg(a).").unwrap();
+    /// assert_eq!(timestamp, Some(1234));
+    /// assert_eq!(patches, vec![Patch::EnableArgument(Argument::new("a", false))]);
+    ///
+    /// assert_eq!(Patch::parse_timed_line("% +arg(a).").unwrap(), (None, vec![]));
+    /// ```
+    pub fn parse_timed_line(input: &str) -> Result<(Option<u64>, Vec<Self>)> {
+        let parsed = parser::parse_timed_patch_line(input)?;
+        Ok(parsed)
+    }
+}
+
+#[cfg(feature = "clingo")]
+impl Patch {
+    /// Patch enabling or disabling the same element.
+    fn with_enabled(&self, enable: bool) -> Patch {
+        match self {
+            Patch::EnableArgument(argument) | Patch::DisableArgument(argument) if enable => {
+                Patch::EnableArgument(argument.clone())
+            }
+            Patch::EnableArgument(argument) | Patch::DisableArgument(argument) => {
+                Patch::DisableArgument(argument.clone())
+            }
+            Patch::EnableAttack(attack) | Patch::DisableAttack(attack) if enable => {
+                Patch::EnableAttack(attack.clone())
+            }
+            Patch::EnableAttack(attack) | Patch::DisableAttack(attack) => {
+                Patch::DisableAttack(attack.clone())
+            }
+        }
+    }
+}
+
+impl Patch {
+    /// Format as update line in the given format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use lib::argumentation_framework::{symbols::Attack, updates::UpdateFormat, Patch};
+    /// let patch = Patch::EnableAttack(Attack::new("a1", "a2", false));
+    /// assert_eq!(patch.format(UpdateFormat::Apxm), "+att(a1,a2).");
+    /// assert_eq!(patch.format(UpdateFormat::Tgfm), "+a1 a2");
+    /// ```
+    pub fn format(&self, format: UpdateFormat) -> String {
+        match format {
+            UpdateFormat::Apxm => self.to_string(),
+            UpdateFormat::Tgfm => match self {
+                Patch::EnableArgument(argument) => format!("+{}", argument.id),
+                Patch::DisableArgument(argument) => format!("-{}", argument.id),
+                Patch::EnableAttack(attack) => format!("+{} {}", attack.from, attack.to),
+                Patch::DisableAttack(attack) => format!("-{} {}", attack.from, attack.to),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for Patch {
+    /// Format as APXM update line, i.e. `+att(a1,a2).`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Patch::EnableArgument(argument) => write!(f, "+{argument}."),
+            Patch::DisableArgument(argument) => write!(f, "-{argument}."),
+            Patch::EnableAttack(attack) => write!(f, "+{attack}."),
+            Patch::DisableAttack(attack) => write!(f, "-{attack}."),
+        }
+    }
+}
+
+/// Whether a [`Patch`] can be applied to the current framework.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchValidity {
+    /// The patch changes the framework
+    Applicable,
+    /// The targeted element is already enabled or disabled as requested
+    Unchanged,
+    /// The patch cannot be applied for the given reason
+    Invalid(String),
+}
+
+#[cfg(feature = "clingo")]
+impl PatchValidity {
+    /// Invalid for the reason given by the error.
+    fn invalid(why: Error) -> Self {
+        match why {
+            Error::Logic(reason) => PatchValidity::Invalid(reason),
+            why => PatchValidity::Invalid(why.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "clingo")]
+/// Version of the linked clingo library as `(major, minor, revision)`.
+pub fn clingo_version() -> (i32, i32, i32) {
+    ::clingo::version()
 }
 
+/// Parse the content of an initial file in APX, TGF or ICCMA 2023 format into its arguments
+/// and attacks.
+///
+/// # Example
+///
+/// ```
+/// # use lib::argumentation_framework::{parse_initial_file, symbols::{Argument, Attack}};
+/// let (args, attacks) = parse_initial_file("a1\na2?\n#\na1 a2\n").unwrap();
+/// assert_eq!(args, vec![Argument::new("a1", false), Argument::new("a2", true)]);
+/// assert_eq!(attacks, vec![Attack::new("a1", "a2", false)]);
+/// ```
+pub fn parse_initial_file(input: &str) -> Result<(Vec<symbols::Argument>, Vec<symbols::Attack>)> {
+    let parsed = parse_apx_tgf(input)?;
+    Ok(parsed)
+}
+
+/// Parse a plain ICCMA competition file, rejecting optional arguments and attacks.
+///
+/// Files accepted here do not use any DASP-specific syntax and should be accepted by
+/// the parsers of other solvers as well.
+///
+/// ```
+/// # use lib::{argumentation_framework::parse_iccma_file, framework::ParserError, Error};
+/// assert!(parse_iccma_file("arg(a). arg(b). att(a,b).").is_ok());
+/// assert!(matches!(
+///     parse_iccma_file("a\nb?\n#\n"),
+///     Err(Error::Parser(ParserError::NotIccmaCompliant { element })) if element == "arg(b)"
+/// ));
+/// ```
+pub fn parse_iccma_file(input: &str) -> Result<(Vec<symbols::Argument>, Vec<symbols::Attack>)> {
+    let parsed = parse_apx_tgf_iccma(input)?;
+    Ok(parsed)
+}
+
+#[cfg(feature = "clingo")]
 /// Iterator over extensions.
 ///
 /// Using a [`::clingo::GenericSolveHandle`] internally. This always needs to be returned,
 /// to recycle the handle and turn it back into the [`::clingo::GenericControl`]
 pub struct ExtensionIter {
-    handle: ::clingo::GenericSolveHandle<Logger, Non, Non, Non, Non>,
+    source: ExtensionSource,
+    /// Started with the solve, `None` for cached extensions
+    timer: Option<Timer>,
+    /// Extensions handed out so far, if [`FrameworkConfig::deduplicate`]
+    seen: Option<Fingerprints>,
+    /// Interner of the framework, for the arguments of the models
+    interner: Arc<Interner>,
+}
+
+#[cfg(feature = "clingo")]
+/// Hashes of the extensions seen by an [`ExtensionIter`], up to a maximum number.
+struct Fingerprints {
+    hashes: HashSet<u64>,
+    cap: usize,
+}
+
+#[cfg(feature = "clingo")]
+impl Fingerprints {
+    fn new(cap: NonZeroUsize) -> Self {
+        Fingerprints {
+            hashes: HashSet::new(),
+            cap: cap.get(),
+        }
+    }
+    /// Remember the extension, returning whether it was seen before.
+    fn insert(&mut self, extension: &Extension) -> bool {
+        let mut hasher = DefaultHasher::new();
+        extension.hash(&mut hasher);
+        let hash = hasher.finish();
+        if self.hashes.contains(&hash) {
+            return false;
+        }
+        if self.hashes.len() < self.cap {
+            self.hashes.insert(hash);
+            if self.hashes.len() == self.cap {
+                log::warn!("Remembered {} extensions, duplicates may follow", self.cap);
+            }
+        }
+        true
+    }
+}
+
+#[cfg(feature = "clingo")]
+enum ExtensionSource {
+    /// Models of a running solve
+    Solving {
+        handle: ::clingo::GenericSolveHandle<Logger, Non, Non, Non, Non>,
+        /// Models yielded so far, including duplicates
+        models: usize,
+        /// Model limit of the solve, `0` if there is none
+        limit: usize,
+    },
+    /// Extensions computed up front, i.e. by [`decomposition::extensions`] or the
+    /// maintained [`GroundedLabelling`]
+    Computed(std::vec::IntoIter<Extension>),
 }
 
 /// An extension of an [`ArgumentationFramework`].
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+///
+/// Stored as a bitset over the indices of the included arguments in the [`Interner`]
+/// of the framework, so that comparing extensions of the same framework compares words
+/// instead of argument ids.
+#[derive(Clone)]
 pub struct Extension {
-    /// Just a list of included arguments
-    atoms: BTreeSet<symbols::Argument>,
+    /// Bit `i` is set if the argument with index `i` is included, without trailing zeros
+    words: Vec<u64>,
+    /// Interner of the indices, `None` only if no argument is included
+    interner: Option<Arc<Interner>>,
 }
 
 impl Extension {
     /// The empty extension
-    pub const EMPTY: Extension = crate::macros::ext!();
-    pub fn from_model(model: &::clingo::Model) -> Result<Self> {
-        log::trace!("Converting clingo model to extension");
-        let atoms = fallible_iterator::convert(
-            model
-                .symbols(ShowType::SHOWN)?
+    pub const EMPTY: Extension = Extension {
+        words: Vec::new(),
+        interner: None,
+    };
+    /// Arguments contained in this extension, in ascending order.
+    pub fn arguments(&self) -> impl Iterator<Item = Arc<symbols::Argument>> {
+        let mut arguments = match &self.interner {
+            Some(interner) => interner.resolve_all(self.indices()),
+            None => vec![],
+        };
+        arguments.sort_unstable();
+        arguments.into_iter()
+    }
+    /// Number of arguments in this extension.
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+    /// Whether this extension contains no arguments.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+    /// Whether every argument of this extension is contained in `other` as well.
+    pub fn is_subset(&self, other: &Extension) -> bool {
+        if !self.shares_interner(other) {
+            return self.arguments().all(|argument| other.contains(&argument));
+        }
+        self.words.len() <= other.words.len()
+            && self
+                .words
                 .iter()
-                .inspect(|symbol| log::trace!("Raw symbol in model: {:?}", symbol.to_string()))
-                .map(Result::<_, Error>::Ok),
-        )
-        .map(|symbol| Ok(symbol.to_string()))
-        .map(|symbol| Ok(symbol.trim_matches('"').to_owned()))
-        .map(|name| {
-            Ok(symbols::Argument {
-                id: name,
-                optional: false,
-            })
+                .zip(&other.words)
+                .all(|(word, other)| word & !other == 0)
+    }
+    /// Include the argument with the given interner index.
+    fn insert(&mut self, idx: usize) {
+        let (word, bit) = (idx / u64::BITS as usize, idx % u64::BITS as usize);
+        if self.words.len() <= word {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << bit;
+    }
+    /// Interner indices of the included arguments, in ascending order.
+    fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(word_idx, &word)| {
+            (0..u64::BITS as usize)
+                .filter(move |bit| word & (1 << bit) != 0)
+                .map(move |bit| word_idx * u64::BITS as usize + bit)
+        })
+    }
+    /// The extension of the given ids, interning them if necessary.
+    fn with_ids(interner: &Arc<Interner>, ids: &[ArgumentID]) -> Self {
+        let mut extension = Extension {
+            words: Vec::new(),
+            interner: Some(interner.clone()),
+        };
+        for id in ids {
+            extension.insert(interner.intern(id));
+        }
+        extension
+    }
+    /// This extension with the indices of the interner of `other`.
+    fn reinterned(&self, other: &Extension) -> Self {
+        match &other.interner {
+            Some(interner) => {
+                let ids: Vec<_> = self.arguments().map(|arg| arg.id.clone()).collect();
+                Extension::with_ids(interner, &ids)
+            }
+            None => self.clone(),
+        }
+    }
+    /// Whether the indices of both extensions refer to the same arguments.
+    fn shares_interner(&self, other: &Extension) -> bool {
+        match (&self.interner, &other.interner) {
+            (Some(interner), Some(other)) => Arc::ptr_eq(interner, other),
+            // Without an interner, the extension is empty
+            _ => true,
+        }
+    }
+    /// Whether all included arguments have an index below `bound`.
+    fn is_below(&self, bound: usize) -> bool {
+        self.words.last().map_or(true, |&last| {
+            let highest =
+                (self.words.len() - 1) * u64::BITS as usize + 63 - last.leading_zeros() as usize;
+            highest < bound
         })
-        .collect()?;
-        Ok(Extension { atoms })
+    }
+    /// Compare the ascending indices lexicographically, word by word.
+    ///
+    /// Below the lowest index contained in only one of the extensions, both agree. The
+    /// extension containing it is smaller, unless the other one ends before.
+    fn cmp_indices(&self, other: &Extension) -> std::cmp::Ordering {
+        use std::cmp::Ordering;
+        let word = |extension: &Extension, idx: usize| extension.words.get(idx).copied();
+        for idx in 0..self.words.len().max(other.words.len()) {
+            let (mine, theirs) = (word(self, idx).unwrap_or(0), word(other, idx).unwrap_or(0));
+            let diff = mine ^ theirs;
+            if diff == 0 {
+                continue;
+            }
+            let bit = diff.trailing_zeros();
+            // Bits above the differing one, in this word or any later one
+            let above = !(2u64 << bit).wrapping_sub(1);
+            let continues = |extension: &Extension, word: u64| {
+                word & above != 0 || extension.words.len() > idx + 1
+            };
+            return if mine & (1 << bit) != 0 {
+                if continues(other, theirs) {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                }
+            } else if continues(self, mine) {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            };
+        }
+        Ordering::Equal
+    }
+    #[cfg(feature = "clingo")]
+    /// Convert the shown symbols of a model, i.e. the arguments in the extension.
+    ///
+    /// Called for every model, so names and strings are interned without allocating.
+    pub(crate) fn from_model(model: &::clingo::Model, interner: &Arc<Interner>) -> Result<Self> {
+        let symbols = model.symbols(ShowType::SHOWN)?;
+        let trace = log::log_enabled!(log::Level::Trace);
+        let mut indices = Vec::with_capacity(symbols.len());
+        for symbol in &symbols {
+            if trace {
+                log::trace!("Raw symbol in model: {:?}", symbol.to_string());
+            }
+            indices.push(intern_symbol(interner, *symbol)?);
+        }
+        let mut extension = Extension {
+            words: Vec::new(),
+            interner: Some(interner.clone()),
+        };
+        if let Some(&max) = indices.iter().max() {
+            extension.words.reserve_exact(max / u64::BITS as usize + 1);
+        }
+        for idx in indices {
+            extension.insert(idx);
+        }
+        Ok(extension)
+    }
+}
+
+#[cfg(feature = "clingo")]
+/// Intern the argument id of a shown symbol, without the quotes of strings.
+fn intern_symbol(interner: &Interner, symbol: Symbol) -> Result<usize> {
+    let idx = match symbol.symbol_type()? {
+        SymbolType::Function if symbol.arguments()?.is_empty() => interner.intern(symbol.name()?),
+        SymbolType::String => interner.intern(symbol.string()?),
+        // Numbers and compound terms are rare, format them
+        _ => interner.intern(&symbol.to_string()),
+    };
+    Ok(idx)
+}
+
+impl std::fmt::Debug for Extension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set()
+            .entries(self.arguments().map(|arg| arg.id.clone()))
+            .finish()
+    }
+}
+
+impl PartialEq for Extension {
+    fn eq(&self, other: &Self) -> bool {
+        if self.shares_interner(other) {
+            return self.words == other.words;
+        }
+        self.len() == other.len() && self.arguments().eq(other.arguments())
+    }
+}
+
+impl Eq for Extension {}
+
+impl std::hash::Hash for Extension {
+    /// Hash the ids, as extensions of different frameworks may be equal.
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for argument in self.arguments() {
+            std::hash::Hash::hash(&argument.id, state);
+        }
+    }
+}
+
+impl PartialOrd for Extension {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Extension {
+    /// Compare the ascending arguments lexicographically, independent of interning order.
+    ///
+    /// Extensions of the same framework are compared word by word, as long as their
+    /// indices are ordered like the ids, see [`Interner::sorted`].
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        if self.shares_interner(other) {
+            let sorted = (self.interner.as_ref())
+                .or(other.interner.as_ref())
+                .map_or(0, |interner| interner.sorted());
+            if self.is_below(sorted) && other.is_below(sorted) {
+                return self.cmp_indices(other);
+            }
+        }
+        self.arguments().cmp(other.arguments())
     }
 }
 
+#[cfg(feature = "clingo")]
 impl<S: ArgumentationFrameworkSemantic> ArgumentationFramework<S> {
+    /// Initialize the framework with the raw initial file content and the given configuration.
+    pub fn new_with_config(input: &str, config: FrameworkConfig) -> Result<Self> {
+        let mut profile = Profile::default();
+        let timer = Timer::start();
+        let (mut args, mut attacks) = if config.iccma_compliant {
+            parse_apx_tgf_iccma(input)?
+        } else {
+            parse_apx_tgf(input)?
+        };
+        validation::resolve_undeclared(&mut args, &mut attacks, config.undeclared)?;
+        for issue in validation::declaration_issues(&args, &attacks) {
+            if config.strict {
+                return Err(issue.into());
+            }
+            log::warn!("{issue}");
+        }
+        profile.record(Phase::Parse, timer);
+        Self::initialize(args, attacks, config, profile)
+    }
+    /// Initialize the framework with already parsed arguments and attacks.
+    ///
+    /// Unlike [`ArgumentationFramework::new_with_config`], the elements are not validated
+    /// and attacks on undeclared arguments are kept.
+    pub fn from_elements(
+        args: Vec<symbols::Argument>,
+        attacks: Vec<symbols::Attack>,
+        config: FrameworkConfig,
+    ) -> Result<Self> {
+        Self::initialize(args, attacks, config, Profile::default())
+    }
+    /// Set up the clingo backend for the parsed elements.
+    fn initialize(
+        args: Vec<symbols::Argument>,
+        attacks: Vec<symbols::Attack>,
+        config: FrameworkConfig,
+        mut profile: Profile,
+    ) -> Result<Self> {
+        if S::base().trim().is_empty() && S::DECOMPOSITION.is_none() {
+            return Err(Error::Logic(String::from(
+                "The semantics has no program, load one before creating a framework",
+            )));
+        }
+        let config = match S::EXTREMUM {
+            Some(extremum) => extremum.configure(config),
+            None => config,
+        };
+        let warnings = clingo::Collected::default();
+        let logger = clingo::Logger::new(&config, warnings.clone());
+        let clingo_ctl =
+            clingo::initialize_backend::<S>(&args, &attacks, &config, logger, &mut profile)?;
+        // Literals are only known after grounding and must be refreshed after regrounding
+        let (literals, externals) = clingo::symbol_literals(&clingo_ctl)?;
+        let grounded = if S::GROUNDED {
+            Some(GroundedLabelling::new(&args, &attacks)?)
+        } else {
+            None
+        };
+        // Indexing the initial arguments in order lets extensions be compared by words
+        let interner = Interner::with_ids(args.iter().map(|arg| arg.id.as_str()));
+        Ok(ArgumentationFramework {
+            _semantics: PhantomData,
+            clingo_ctl: Some(clingo_ctl),
+            literals,
+            externals,
+            pending: HashMap::new(),
+            assigned: HashMap::new(),
+            history: Vec::new(),
+            undo_log: Vec::new(),
+            arguments: args,
+            attacks,
+            grounded,
+            gradual: None,
+            cache: HashMap::new(),
+            interner,
+            profile,
+            warnings,
+            last_statistics: None,
+            config,
+        })
+    }
+    /// All arguments of the framework, whether optional or not.
+    pub fn arguments(&self) -> &[symbols::Argument] {
+        &self.arguments
+    }
+    /// All attacks of the framework, whether optional or not.
+    pub fn attacks(&self) -> &[symbols::Attack] {
+        &self.attacks
+    }
+    /// The grounded labelling of the current framework, for the grounded semantics only.
+    pub fn grounded_labelling(&self) -> Option<&GroundedLabelling> {
+        self.grounded.as_ref()
+    }
+    /// Start keeping the degrees of all arguments up to date under patches, see [`gradual`].
+    ///
+    /// Replaces the degrees tracked before, resetting all weights to 1.
+    pub fn track_gradual(&mut self, aggregation: Aggregation) -> Result<&mut GradualScores> {
+        // Elements disabled right now are given as optional, as those start disabled
+        let mut arguments = self.arguments.clone();
+        for argument in &mut arguments {
+            argument.optional = argument.optional && !self.is_enabled_symbol(&argument.symbol()?);
+        }
+        let mut attacks = self.attacks.clone();
+        for attack in &mut attacks {
+            attack.optional = attack.optional && !self.is_enabled_symbol(&attack.symbol()?);
+        }
+        let scores = GradualScores::new(aggregation, &arguments, &attacks)?;
+        Ok(self.gradual.insert(scores))
+    }
+    /// The degrees of the current framework, if [tracked](Self::track_gradual).
+    pub fn gradual_scores(&self) -> Option<&GradualScores> {
+        self.gradual.as_ref()
+    }
+    /// Statistics of the last solve by clingo, `None` if clingo did not solve yet.
+    ///
+    /// Queries answered without clingo, e.g. from the cache, leave it untouched.
+    pub fn last_statistics(&self) -> Option<&Statistics> {
+        self.last_statistics.as_ref()
+    }
+    /// Remember the statistics of the solve that just finished.
+    fn record_statistics(&mut self, models: usize) {
+        let warnings = std::mem::take(
+            &mut *self
+                .warnings
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+        );
+        self.last_statistics = Some(Statistics { models, warnings });
+    }
+    /// Wall-clock time spent per phase, empty unless the `profiling` feature is enabled.
+    pub fn profile(&self) -> &Profile {
+        &self.profile
+    }
+    /// Add arguments and attacks that were not part of the initial framework.
+    ///
+    /// Like in the initial file, optional elements are added disabled. Grounding the
+    /// new atoms in an additional program part is not possible: rule instances of
+    /// earlier steps, e.g. those with conditional literals over all attackers, would
+    /// not see new attacks, and the base program would redefine atoms of earlier steps,
+    /// e.g. `defeated/1`. Instead, the backend is rebuilt, keeping the truth values of
+    /// all optional elements.
+    pub fn add(&mut self, args: &[symbols::Argument], attacks: &[symbols::Attack]) -> Result {
+        if args.is_empty() && attacks.is_empty() {
+            return Ok(());
+        }
+        for symbol in args
+            .iter()
+            .map(ToSymbol::symbol)
+            .chain(attacks.iter().map(ToSymbol::symbol))
+        {
+            let symbol = symbol?;
+            if self.literals.contains_key(&symbol) {
+                return Err(Error::Logic(format!("{symbol} already exists")));
+            }
+        }
+        if let Some(grounded) = &mut self.grounded {
+            grounded.add(args, attacks)?;
+        }
+        if let Some(gradual) = &mut self.gradual {
+            gradual.add(args, attacks)?;
+        }
+        self.arguments.extend_from_slice(args);
+        self.attacks.extend_from_slice(attacks);
+        self.reinitialize()
+    }
+    /// Rebuild the clingo backend from the stored arguments, attacks and truth values.
+    ///
+    /// Recovers from [`Error::ClingoNotInitialized`], which is returned by all queries
+    /// once the backend was lost, e.g. after a failed solve or a leaked [`IterGuard`].
+    pub fn reinitialize(&mut self) -> Result {
+        // Enabled elements, identified by symbol as literals differ between controls
+        let enabled: Vec<Symbol> = self
+            .externals
+            .iter()
+            .filter(|(_, &literal)| self.is_enabled(literal))
+            .map(|(&symbol, _)| symbol)
+            .collect();
+        let clingo_ctl = clingo::initialize_backend::<S>(
+            &self.arguments,
+            &self.attacks,
+            &self.config,
+            self.logger(),
+            &mut self.profile,
+        )?;
+        (self.literals, self.externals) = clingo::symbol_literals(&clingo_ctl)?;
+        self.clingo_ctl = Some(clingo_ctl);
+        // Cached states are keyed by the literals of the old backend
+        self.cache.clear();
+        self.assigned.clear();
+        self.pending = enabled
+            .iter()
+            .filter_map(|symbol| self.externals.get(symbol))
+            .map(|&literal| (literal, true))
+            .collect();
+        Ok(())
+    }
+    /// Apply a single patch, see [`ArgumentationFramework::apply_patches`].
     pub fn apply_patch(&mut self, patch: &Patch) -> Result {
-        log::trace!("Applying patch {patch:?}");
-        match patch {
-            Patch::EnableArgument(argument) => self.enable_argument(argument),
-            Patch::DisableArgument(argument) => self.disable_argument(argument),
-            Patch::EnableAttack(attack) => self.enable_attack(attack),
-            Patch::DisableAttack(attack) => self.disable_attack(attack),
+        self.apply_patches([patch])
+    }
+    /// Apply all patches as one batch.
+    ///
+    /// The whole batch is checked before the framework is changed: either all patches
+    /// are applied or, if any patch cannot be applied, none. Later patches override
+    /// earlier ones on the same element. The assignments are passed to clingo right
+    /// before the next solve, so that patches of several updates are coalesced if no
+    /// solve happens in between. Applied patches can be reverted by
+    /// [`ArgumentationFramework::undo`].
+    ///
+    /// Enabling arguments and attacks that are not part of the framework yet adds them
+    /// as optional elements, see [`ArgumentationFramework::add`]. Arguments that are not
+    /// optional can be disabled as well, which makes them optional. Both stay in place
+    /// if clingo fails while grounding them. Enabling other elements that are not
+    /// optional changes nothing, disabling them fails.
+    pub fn apply_patches<'p>(&mut self, patches: impl IntoIterator<Item = &'p Patch>) -> Result {
+        let patches: Vec<_> = patches.into_iter().collect();
+        let (args, attacks) = self.enabled_unknowns(&patches)?;
+        if !args.is_empty() || !attacks.is_empty() {
+            log::debug!(
+                "Adding {} new arguments and {} new attacks",
+                args.len(),
+                attacks.len()
+            );
+            self.add(&args, &attacks)?;
         }
+        self.make_disabled_optional(&patches)?;
+        let restore = self.apply_unrecorded(&patches)?;
+        self.history.extend(patches.into_iter().cloned());
+        self.undo_log.extend(restore);
+        Ok(())
     }
-    pub fn enable_argument(&mut self, argument: &symbols::Argument) -> Result {
-        let symbol_needle = argument.symbol()?;
-        let target = self
-            .assume_control()?
-            .symbolic_atoms()?
-            .iter()?
-            .try_find(|x| Result::<_, ::clingo::ClingoError>::Ok(x.symbol()? == symbol_needle))?
-            .ok_or(Error::Logic(format!(
-                "The argument {symbol_needle} was not defined as optional and cannot be enabled now"
-            )))?;
-        clingo::enable_argument(self.assume_control()?, target.literal()?)?;
+    /// All applied patches that were not undone, in the order they were applied.
+    ///
+    /// Hypothetical patches, see [`ArgumentationFramework::query_with_hypothetical_patches`],
+    /// are not part of the history.
+    pub fn history(&self) -> &[Patch] {
+        &self.history
+    }
+    /// The history as update lines in the given format, one patch per line.
+    ///
+    /// Applying these lines to the initial framework yields the current one.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{argumentation_framework::{updates::UpdateFormat, ArgumentationFramework}, semantics, Framework};
+    /// let mut af = ArgumentationFramework::<semantics::Admissible>::new(
+    ///     "arg(a). arg(b). att(a,b). opt(att(a,b)). opt(arg(b)).",
+    /// )
+    /// .unwrap();
+    /// af.update("+arg(b):att(a,b).").unwrap();
+    /// assert_eq!(af.history_to_string(UpdateFormat::Apxm), "+arg(b).\n+att(a,b).\n");
+    /// assert_eq!(af.history_to_string(UpdateFormat::Tgfm), "+b\n+a b\n");
+    /// ```
+    pub fn history_to_string(&self, format: UpdateFormat) -> String {
+        self.history
+            .iter()
+            .map(|patch| patch.format(format) + "\n")
+            .collect()
+    }
+    /// Revert the last `n` applied patches.
+    ///
+    /// Every patch is reverted by restoring the truth value its element had before the
+    /// patch was applied, latest patch first, and removed from the
+    /// [`ArgumentationFramework::history`]. Fails without changing the framework if
+    /// fewer than `n` patches were applied.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{argumentation_framework::ArgumentationFramework, semantics, Framework};
+    /// let mut af = ArgumentationFramework::<semantics::Admissible>::new(
+    ///     "arg(a). arg(b). att(a,b). opt(att(a,b)).",
+    /// )
+    /// .unwrap();
+    /// af.update("+att(a,b).").unwrap();
+    /// assert_eq!(af.count_extensions().unwrap(), 2);
+    /// af.undo(1).unwrap();
+    /// assert_eq!(af.count_extensions().unwrap(), 4);
+    /// ```
+    pub fn undo(&mut self, n: usize) -> Result {
+        let applied = self.undo_log.len();
+        if n > applied {
+            return Err(Error::Logic(format!(
+                "Cannot undo {n} patches, only {applied} were applied"
+            )));
+        }
+        self.history.truncate(applied - n);
+        let restore = self.undo_log.split_off(applied - n);
+        self.apply_unrecorded(&restore.iter().rev().collect::<Vec<_>>())?;
         Ok(())
     }
-    pub fn disable_argument(&mut self, argument: &symbols::Argument) -> Result {
-        let symbol_needle = argument.symbol()?;
-        let target = self
-            .assume_control()?
-            .symbolic_atoms()?
-            .iter()?
-            .try_find(|x| Result::<_, ::clingo::ClingoError>::Ok(x.symbol()? == symbol_needle))?
-            .ok_or(Error::Logic(format!(
-                "The argument {symbol_needle} was not defined as optional and cannot be disabled now"
-            )))?;
-        clingo::disable_argument(self.assume_control()?, target.literal()?)?;
+    /// The arguments and attacks enabled by the patches that are unknown so far.
+    ///
+    /// Checks the whole batch, failing if any other patch cannot be applied, or if a
+    /// new attack involves an argument that is neither known nor added.
+    fn enabled_unknowns(
+        &self,
+        patches: &[&Patch],
+    ) -> Result<(Vec<symbols::Argument>, Vec<symbols::Attack>)> {
+        let mut args: Vec<symbols::Argument> = vec![];
+        let mut attacks: Vec<symbols::Attack> = vec![];
+        for patch in patches {
+            match patch {
+                Patch::EnableArgument(argument)
+                    if !self.arguments.iter().any(|known| known.id == argument.id) =>
+                {
+                    if !args.iter().any(|new| new.id == argument.id) {
+                        args.push(symbols::Argument::new(argument.id.clone(), true));
+                    }
+                }
+                Patch::EnableAttack(attack)
+                    if !self
+                        .attacks
+                        .iter()
+                        .any(|known| known.from == attack.from && known.to == attack.to) =>
+                {
+                    if !attacks
+                        .iter()
+                        .any(|new| new.from == attack.from && new.to == attack.to)
+                    {
+                        attacks.push(symbols::Attack::new(
+                            attack.from.clone(),
+                            attack.to.clone(),
+                            true,
+                        ));
+                    }
+                }
+                // Made optional by `make_disabled_optional`
+                Patch::DisableArgument(argument)
+                    if self
+                        .arguments
+                        .iter()
+                        .any(|known| known.id == argument.id && !known.optional) => {}
+                patch => {
+                    if !self.enables_fact(patch)? {
+                        self.resolve(patch)?;
+                    }
+                }
+            }
+        }
+        for attack in &attacks {
+            for id in [&attack.from, &attack.to] {
+                if !args.iter().any(|new| &new.id == id) {
+                    symbols::find(&self.arguments, id)?;
+                }
+            }
+        }
+        Ok((args, attacks))
+    }
+    /// Make the arguments disabled by the patches optional, if they are not yet.
+    ///
+    /// Arguments that are not optional are facts, which cannot be falsified. Instead,
+    /// the backend is rebuilt with them as externals, which stay enabled until the
+    /// patches are applied. Incident attacks are ignored while an argument is disabled.
+    /// If the rebuild fails, the arguments are not optional either.
+    fn make_disabled_optional(&mut self, patches: &[&Patch]) -> Result {
+        let disabled: HashSet<&ArgumentID> = patches
+            .iter()
+            .filter_map(|patch| match patch {
+                Patch::DisableArgument(argument) => Some(&argument.id),
+                _ => None,
+            })
+            .collect();
+        let mut converted = vec![];
+        let mut symbols = vec![];
+        for (index, argument) in self.arguments.iter().enumerate() {
+            if !argument.optional && disabled.contains(&argument.id) {
+                converted.push(index);
+                symbols.push(argument.symbol()?);
+            }
+        }
+        if converted.is_empty() {
+            return Ok(());
+        }
+        log::debug!("Making {} arguments optional", converted.len());
+        for &index in &converted {
+            self.arguments[index].optional = true;
+        }
+        if let Err(err) = self.reinitialize() {
+            for &index in &converted {
+                self.arguments[index].optional = false;
+            }
+            return Err(err);
+        }
+        for symbol in symbols {
+            if let Some(&literal) = self.externals.get(&symbol) {
+                self.pending.insert(literal, true);
+            }
+        }
         Ok(())
     }
+    /// Apply all patches as one batch, without recording them for [`ArgumentationFramework::undo`].
+    ///
+    /// Returns one patch per applied patch, restoring the state before it.
+    fn apply_unrecorded(&mut self, patches: &[&Patch]) -> Result<Vec<Patch>> {
+        let timer = Timer::start();
+        let assignments = patches
+            .iter()
+            .map(|patch| {
+                log::trace!("Applying patch {patch:?}");
+                if self.enables_fact(patch)? {
+                    return Ok(None);
+                }
+                self.resolve(patch).map(Some)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        // Earlier patches of the batch on the same element determine the previous state
+        let mut staged = HashMap::new();
+        let restore = patches
+            .iter()
+            .zip(&assignments)
+            .map(|(patch, assignment)| match *assignment {
+                Some((literal, enable)) => {
+                    let previous = staged
+                        .insert(literal, enable)
+                        .unwrap_or_else(|| self.is_enabled(literal));
+                    patch.with_enabled(previous)
+                }
+                None => patch.with_enabled(true),
+            })
+            .collect();
+        self.pending.extend(assignments.into_iter().flatten());
+        if let Some(grounded) = &mut self.grounded {
+            for patch in patches {
+                grounded.apply_patch(patch)?;
+            }
+        }
+        if let Some(gradual) = &mut self.gradual {
+            for patch in patches {
+                gradual.apply_patch(patch)?;
+            }
+        }
+        self.profile.record(Phase::Patch, timer);
+        Ok(restore)
+    }
+    pub fn enable_argument(&mut self, argument: &symbols::Argument) -> Result {
+        self.apply_patch(&Patch::EnableArgument(argument.clone()))
+    }
+    pub fn disable_argument(&mut self, argument: &symbols::Argument) -> Result {
+        self.apply_patch(&Patch::DisableArgument(argument.clone()))
+    }
     pub fn enable_attack(&mut self, attack: &symbols::Attack) -> Result {
-        let symbol_needle = attack.symbol()?;
-        let target = self
-            .assume_control()?
-            .symbolic_atoms()?
-            .iter()?
-            .try_find(|x| Result::<_, ::clingo::ClingoError>::Ok(x.symbol()? == symbol_needle))?
-            .ok_or(Error::Logic(format!(
-                "The attack {symbol_needle} was not defined as optional and cannot be enabled now"
-            )))?;
-        clingo::enable_attack(self.assume_control()?, target.literal()?)?;
-        Ok(())
+        self.apply_patch(&Patch::EnableAttack(attack.clone()))
     }
     pub fn disable_attack(&mut self, attack: &symbols::Attack) -> Result {
-        let symbol_needle = attack.symbol()?;
-        let target = self
-            .assume_control()?
-            .symbolic_atoms()?
-            .iter()?
-            .try_find(|x| Result::<_, ::clingo::ClingoError>::Ok(x.symbol()? == symbol_needle))?
-            .ok_or(Error::Logic(format!(
-                "The attack {symbol_needle} was not defined as optional and cannot be disabled now"
-            )))?;
-        clingo::disable_attack(self.assume_control()?, target.literal()?)?;
+        self.apply_patch(&Patch::DisableAttack(attack.clone()))
+    }
+    /// Answer `query` as if the patches were applied, without changing the framework.
+    ///
+    /// The patches are applied like by [`ArgumentationFramework::apply_patches`] and
+    /// reverted afterwards, even if the query fails. This allows previewing the effect
+    /// of an update before committing to it.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{argumentation_framework::{symbols::Attack, ArgumentationFramework, Patch}, semantics, Framework};
+    /// let mut af = ArgumentationFramework::<semantics::Admissible>::new(
+    ///     "arg(a). arg(b). att(a,b). opt(att(a,b)).",
+    /// )
+    /// .unwrap();
+    /// let patches = [Patch::EnableAttack(Attack::new("a", "b", true))];
+    /// let count = af
+    ///     .query_with_hypothetical_patches(&patches, |af| af.count_extensions())
+    ///     .unwrap();
+    /// assert_eq!(count, 2);
+    /// assert_eq!(af.count_extensions().unwrap(), 4);
+    /// ```
+    pub fn query_with_hypothetical_patches<T>(
+        &mut self,
+        patches: &[Patch],
+        query: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        let restore = self.apply_unrecorded(&patches.iter().collect::<Vec<_>>())?;
+        let answer = query(self);
+        // The restoring patch of the first patch of every element must win
+        self.apply_unrecorded(&restore.iter().rev().collect::<Vec<_>>())?;
+        answer
+    }
+    /// Apply the update lines read from `reader` one by one, see [`UpdateLines`].
+    ///
+    /// After every update, `on_answer` is called with the updated framework, the number
+    /// of the update, starting at zero, and its line, e.g. to solve the framework.
+    /// Stops at the first update that cannot be applied or the first failing callback.
+    pub fn apply_updates_from<E: From<Error>>(
+        &mut self,
+        reader: impl BufRead,
+        mut on_answer: impl FnMut(&mut Self, usize, &str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let mut lines = UpdateLines::new(reader, Follow::No).enumerate();
+        while let Some((nr, line)) = lines.next()? {
+            self.update(&line)?;
+            on_answer(self, nr, &line)?;
+        }
         Ok(())
     }
-    fn assume_control(&mut self) -> Result<&mut Control> {
-        self.clingo_ctl.as_mut().ok_or(Error::ClingoNotInitialized)
+    /// Check whether the patch can be applied, without changing the framework.
+    ///
+    /// Like [`ArgumentationFramework::apply_patches`], unknown arguments and attacks
+    /// can be enabled and all arguments can be disabled.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{argumentation_framework::{symbols::Attack, ArgumentationFramework, Patch, PatchValidity}, semantics};
+    /// let af = ArgumentationFramework::<semantics::Admissible>::new(
+    ///     "arg(a). arg(b). att(a,b). opt(att(a,b)).",
+    /// )
+    /// .unwrap();
+    /// let validate = |patch| af.validate_patch(&patch).unwrap();
+    /// assert_eq!(
+    ///     validate(Patch::EnableAttack(Attack::new("a", "b", false))),
+    ///     PatchValidity::Applicable
+    /// );
+    /// assert_eq!(
+    ///     validate(Patch::DisableAttack(Attack::new("a", "b", false))),
+    ///     PatchValidity::Unchanged
+    /// );
+    /// assert!(matches!(
+    ///     validate(Patch::EnableAttack(Attack::new("a", "c", false))),
+    ///     PatchValidity::Invalid(_)
+    /// ));
+    /// ```
+    pub fn validate_patch(&self, patch: &Patch) -> Result<PatchValidity> {
+        let (optional, symbol, enable) = match patch {
+            Patch::EnableArgument(argument) | Patch::DisableArgument(argument) => {
+                let known = self.arguments.iter().find(|known| known.id == argument.id);
+                let enable = matches!(patch, Patch::EnableArgument(_));
+                match known {
+                    None if enable => return Ok(PatchValidity::Applicable),
+                    None => {
+                        let why = symbols::find(&self.arguments, &argument.id).unwrap_err();
+                        return Ok(PatchValidity::invalid(why));
+                    }
+                    // Arguments that are not optional are made optional to disable them
+                    Some(known) if !known.optional && !enable => {
+                        return Ok(PatchValidity::Applicable)
+                    }
+                    Some(known) => (known.optional, argument.symbol()?, enable),
+                }
+            }
+            Patch::EnableAttack(attack) | Patch::DisableAttack(attack) => {
+                let known = self
+                    .attacks
+                    .iter()
+                    .find(|known| known.from == attack.from && known.to == attack.to);
+                let enable = matches!(patch, Patch::EnableAttack(_));
+                match known {
+                    None if enable => {
+                        for id in [&attack.from, &attack.to] {
+                            if let Err(why) = symbols::find(&self.arguments, id) {
+                                return Ok(PatchValidity::invalid(why));
+                            }
+                        }
+                        return Ok(PatchValidity::Applicable);
+                    }
+                    None => {
+                        return Ok(PatchValidity::Invalid(format!(
+                            "The attack {attack} does not exist"
+                        )))
+                    }
+                    // Fails like applying the patch would
+                    Some(known) if !known.optional && !enable => {
+                        return Ok(self
+                            .resolve(patch)
+                            .map_or_else(PatchValidity::invalid, |_| PatchValidity::Applicable))
+                    }
+                    Some(known) => (known.optional, attack.symbol()?, enable),
+                }
+            }
+        };
+        let enabled = !optional || self.is_enabled_symbol(&symbol);
+        Ok(if enabled == enable {
+            PatchValidity::Unchanged
+        } else {
+            PatchValidity::Applicable
+        })
+    }
+    /// Find the literal changed by the patch and its new truth value.
+    fn resolve(&self, patch: &Patch) -> Result<(SolverLiteral, bool)> {
+        let (kind, symbol_needle, enable) = match patch {
+            Patch::EnableArgument(argument) => ("argument", argument.symbol()?, true),
+            Patch::DisableArgument(argument) => ("argument", argument.symbol()?, false),
+            Patch::EnableAttack(attack) => ("attack", attack.symbol()?, true),
+            Patch::DisableAttack(attack) => ("attack", attack.symbol()?, false),
+        };
+        // Facts have literals as well, but only externals can be assigned
+        let target = self.externals.get(&symbol_needle).copied().ok_or_else(|| {
+            Error::Logic(format!(
+                "The {kind} {symbol_needle} was not defined as optional and cannot be {} now",
+                if enable { "enabled" } else { "disabled" }
+            ))
+        })?;
+        Ok((target, enable))
+    }
+    /// Whether the patch enables an element that is not optional, which changes nothing.
+    fn enables_fact(&self, patch: &Patch) -> Result<bool> {
+        let symbol = match patch {
+            Patch::EnableArgument(argument) => argument.symbol()?,
+            Patch::EnableAttack(attack) => attack.symbol()?,
+            Patch::DisableArgument(_) | Patch::DisableAttack(_) => return Ok(false),
+        };
+        Ok(self.literals.contains_key(&symbol) && !self.externals.contains_key(&symbol))
+    }
+    /// Current truth value of the external, including pending assignments.
+    fn is_enabled(&self, literal: SolverLiteral) -> bool {
+        self.pending
+            .get(&literal)
+            .or_else(|| self.assigned.get(&literal))
+            .copied()
+            .unwrap_or(false)
+    }
+    /// Current truth value of the external with the given symbol, `false` if unknown.
+    fn is_enabled_symbol(&self, symbol: &Symbol) -> bool {
+        self.externals
+            .get(symbol)
+            .is_some_and(|&literal| self.is_enabled(literal))
+    }
+    /// Arguments and attacks currently part of the framework.
+    fn active(&self) -> Result<(Vec<symbols::Argument>, Vec<symbols::Attack>)> {
+        let mut arguments = vec![];
+        for argument in &self.arguments {
+            if !argument.optional || self.is_enabled_symbol(&argument.symbol()?) {
+                arguments.push(argument.clone());
+            }
+        }
+        let mut attacks = vec![];
+        for attack in &self.attacks {
+            if !attack.optional || self.is_enabled_symbol(&attack.symbol()?) {
+                attacks.push(attack.clone());
+            }
+        }
+        Ok((arguments, attacks))
+    }
+    /// Hash of the enabled optional elements, identifying the current framework.
+    fn state_key(&self) -> u64 {
+        let mut enabled: Vec<_> = self
+            .externals
+            .values()
+            .filter(|&&literal| self.is_enabled(literal))
+            .map(|literal| literal.get_integer())
+            .collect();
+        enabled.sort_unstable();
+        let mut hasher = DefaultHasher::new();
+        enabled.hash(&mut hasher);
+        hasher.finish()
+    }
+    /// Compute all extensions component by component, if configured and supported.
+    fn decomposed_extensions(&self) -> Result<Option<Vec<Extension>>> {
+        let Some(decomposition) = S::DECOMPOSITION.filter(|_| self.config.decompose) else {
+            return Ok(None);
+        };
+        let (arguments, attacks) = self.active()?;
+        let mut extensions = decomposition::extensions(&arguments, &attacks, decomposition)?;
+        if let Some(models) = self.config.models {
+            extensions.truncate(models.get());
+        }
+        Ok(Some(extensions))
+    }
+    /// Pass all pending assignments that change a truth value to clingo.
+    fn assign_pending(&mut self) -> Result {
+        let ctl = self
+            .clingo_ctl
+            .as_mut()
+            .ok_or(Error::ClingoNotInitialized)?;
+        let assigned = &mut self.assigned;
+        let changes: Vec<_> = self
+            .pending
+            .drain()
+            .filter(|&(literal, value)| assigned.insert(literal, value).unwrap_or(false) != value)
+            .collect();
+        log::trace!("Assigning {} changed externals", changes.len());
+        clingo::assign_externals(ctl, changes)
+    }
+    /// Estimate the number of extensions without enumerating all of them.
+    ///
+    /// The estimate is within a factor of `1 + epsilon` of the true count with
+    /// probability at least `1 - delta`, see [`approximate`]. Only pays off for
+    /// frameworks with far too many extensions to count them one by one. Semantics whose
+    /// extensions are not the models of their program are counted exactly.
+    pub fn estimate_extension_count(&mut self, epsilon: f64, delta: f64) -> Result<CountEstimate> {
+        if !self.models_are_extensions() {
+            return self.count_extensions().map(CountEstimate::exact);
+        }
+        let (arguments, attacks) = self.current_elements()?;
+        approximate::estimate::<S>(
+            &arguments,
+            &attacks,
+            &self.config,
+            (epsilon, delta),
+            &mut self.profile,
+        )
+    }
+    /// Draw an extension of the current framework near-uniformly at random, see [`sampling`].
+    ///
+    /// `rng` returns uniformly distributed random numbers, e.g. `|| rng.gen()` with the
+    /// `rand` crate. Returns `None` if there is no extension. Semantics whose extensions
+    /// are not the models of their program are enumerated and drawn from exactly.
+    pub fn sample_extension_uniform(
+        &mut self,
+        mut rng: impl FnMut() -> u64,
+    ) -> Result<Option<Extension>> {
+        if !self.models_are_extensions() {
+            let extensions = self.enumerate_extensions()?.by_ref().collect()?;
+            return Ok(sampling::pick(extensions, &mut rng));
+        }
+        let (arguments, attacks) = self.current_elements()?;
+        sampling::sample::<S>(
+            &arguments,
+            &attacks,
+            &self.config,
+            &self.interner,
+            &mut self.profile,
+            &mut rng,
+        )
+    }
+    /// Enumerate the ⊆-maximal extensions of the current framework.
+    ///
+    /// Under admissible semantics, these are the preferred extensions.
+    pub fn enumerate_maximal_extensions(&mut self) -> Result<Vec<Extension>> {
+        self.extremal_extensions(Extremum::Maximal)
+    }
+    /// Enumerate the ⊆-minimal extensions of the current framework.
+    ///
+    /// Under complete semantics, this is the grounded extension.
+    pub fn enumerate_minimal_extensions(&mut self) -> Result<Vec<Extension>> {
+        self.extremal_extensions(Extremum::Minimal)
+    }
+    /// Whether the extensions are exactly the models of the program of the semantics,
+    /// so that they can be reasoned about by a single solve of that program.
+    fn models_are_extensions(&self) -> bool {
+        S::EXTREMUM.is_none() && !S::base().trim().is_empty() && self.grounded.is_none()
+    }
+    /// Extensions of the current framework that are extremal by set inclusion, see [`extremal`].
+    fn extremal_extensions(&mut self, extremum: Extremum) -> Result<Vec<Extension>> {
+        if let Some(grounded) = &self.grounded {
+            return Ok(vec![grounded.extension()]);
+        }
+        if let Some(decomposition) = S::DECOMPOSITION.filter(|_| self.config.decompose) {
+            let (arguments, attacks) = self.active()?;
+            let extensions = decomposition::extensions(&arguments, &attacks, decomposition)?;
+            return Ok(extremal::filter(extensions, extremum));
+        }
+        if S::EXTREMUM.is_some() {
+            // Extensions are extrema already, the heuristic is taken
+            let extensions = self.enumerate_extensions()?.by_ref().collect()?;
+            return Ok(extremal::filter(extensions, extremum));
+        }
+        // The heuristic replaces the configured one, models of the current framework
+        // are enumerated on a separate backend
+        let (arguments, attacks) = self.current_elements()?;
+        let config = FrameworkConfig {
+            cache: false,
+            ..extremum.configure(self.config.clone())
+        };
+        let mut af = Self::from_elements(arguments, attacks, config)?;
+        let extensions = af.enumerate_extensions()?.by_ref().collect()?;
+        Ok(extensions)
+    }
+    /// Explain why the argument is not credulously accepted, see [`explanation`].
+    ///
+    /// Returns the smallest sets of enabled optional attacks whose removal would make
+    /// the argument credulously accepted. Fails for semantics whose extensions are the
+    /// ⊆-extremal models of their program, e.g. resolution-based grounded semantics.
+    pub fn explain_rejection(
+        &self,
+        argument: &symbols::Argument,
+    ) -> Result<Vec<explanation::Diagnosis>> {
+        let (mut arguments, attacks) = self.active()?;
+        // Only enabled optional attacks are candidates for removal
+        arguments.iter_mut().for_each(|arg| arg.optional = false);
+        explanation::diagnoses::<S>(&arguments, &attacks, argument, &self.config, self.logger())
+    }
+    /// Certify the answer for the credulous acceptance of the argument, see [`certify`].
+    ///
+    /// An extension containing the argument is certified as [`Certificate::Defended`],
+    /// a rejected argument by the grounded labelling if it is labelled out there.
+    /// `None` if there is no such certificate for the answer.
+    pub fn certify_credulous(
+        &mut self,
+        argument: &symbols::Argument,
+    ) -> Result<Option<Certificate>> {
+        let (arguments, attacks) = self.current_elements()?;
+        let witness = self
+            .enumerate_extensions()?
+            .find(|ext| Ok(ext.contains(argument)))?;
+        if let Some(extension) = witness {
+            return Ok(Certificate::defended(
+                &arguments,
+                &attacks,
+                &extension,
+                &argument.id,
+            ));
+        }
+        let refutation = Certificate::grounded(&arguments, &attacks, &argument.id)?;
+        Ok((refutation.label() == Some(Label::Out)).then_some(refutation))
+    }
+    /// Certify the label of the argument by the grounded labelling of the current framework.
+    ///
+    /// Decides grounded acceptance and skeptical acceptance under complete semantics.
+    pub fn certify_grounded(&self, argument: &symbols::Argument) -> Result<Certificate> {
+        let (arguments, attacks) = self.current_elements()?;
+        Certificate::grounded(&arguments, &attacks, &argument.id)
+    }
+    /// Justify the grounded acceptance of the argument, see [`justification`].
+    ///
+    /// Returns a smallest strongly admissible set containing the argument, `None` if the
+    /// argument is not part of the grounded extension.
+    pub fn justify_grounded(
+        &self,
+        argument: &symbols::Argument,
+    ) -> Result<Option<justification::Justification>> {
+        let (arguments, attacks) = self.current_elements()?;
+        justification::minimum(&arguments, &attacks, argument, &self.config, self.logger())
+    }
+    /// Rank the arguments of the current framework by the h-categoriser, see [`ranking`].
+    ///
+    /// Independent of the semantics of this framework.
+    pub fn ranking(&self) -> Result<ranking::Ranking> {
+        let (arguments, attacks) = self.active()?;
+        Ok(ranking::Ranking::h_categoriser(&arguments, &attacks))
+    }
+    /// Check the known relations between the semantics on the current framework.
+    ///
+    /// Independent of the semantics of this framework, see [`verification`].
+    pub fn verify_relations(&self) -> Result<Vec<verification::Violation>> {
+        let (arguments, attacks) = self.current_elements()?;
+        verification::verify(&arguments, &attacks, &self.config)
+    }
+    /// Arguments and attacks currently part of the framework, none of them optional.
+    ///
+    /// Used to solve the current framework in isolation, where nothing needs to be toggled.
+    fn current_elements(&self) -> Result<(Vec<symbols::Argument>, Vec<symbols::Attack>)> {
+        let (mut arguments, mut attacks) = self.active()?;
+        arguments.iter_mut().for_each(|arg| arg.optional = false);
+        attacks
+            .iter_mut()
+            .for_each(|attack| attack.optional = false);
+        Ok((arguments, attacks))
+    }
+    /// Logger for clingo, collecting the warnings for [`ArgumentationFramework::last_statistics`].
+    fn logger(&self) -> clingo::Logger {
+        clingo::Logger::new(&self.config, self.warnings.clone())
+    }
+    /// Take the control for solving, it has to be put back once the solve is done.
+    fn take_control(&mut self) -> Result<Control> {
+        self.clingo_ctl.take().ok_or(Error::ClingoNotInitialized)
+    }
+    /// Enumerate the extensions, ignoring the cache.
+    fn enumerate_uncached(&mut self) -> Result<IterGuard<'_, Self>> {
+        let timer = Some(Timer::start());
+        if let Some(grounded) = &self.grounded {
+            let source = ExtensionSource::Computed(vec![grounded.extension()].into_iter());
+            let seen = None;
+            let interner = self.interner.clone();
+            return Ok(IterGuard::new(
+                self,
+                ExtensionIter {
+                    source,
+                    timer,
+                    seen,
+                    interner,
+                },
+            ));
+        }
+        if let Some(extensions) = self.decomposed_extensions()? {
+            let source = ExtensionSource::Computed(extensions.into_iter());
+            let seen = None;
+            let interner = self.interner.clone();
+            return Ok(IterGuard::new(
+                self,
+                ExtensionIter {
+                    source,
+                    timer,
+                    seen,
+                    interner,
+                },
+            ));
+        }
+        self.assign_pending()?;
+        let ctl = self.take_control()?;
+        let handle = ctl.solve(SolveMode::YIELD, &[])?;
+        let source = ExtensionSource::Solving {
+            handle,
+            models: 0,
+            limit: self.config.models.map_or(0, NonZeroUsize::get),
+        };
+        let seen = self.config.deduplicate.map(Fingerprints::new);
+        let interner = self.interner.clone();
+        Ok(IterGuard::new(
+            self,
+            ExtensionIter {
+                source,
+                timer,
+                seen,
+                interner,
+            },
+        ))
+    }
+    /// Call `f` with every extension, until it breaks.
+    ///
+    /// Drives the solve internally, a simpler alternative to
+    /// [`enumerate_extensions`](Framework::enumerate_extensions) for callbacks.
+    /// Returns whether `f` broke early. Extensions are taken from the cache if present,
+    /// but, as the enumeration may be incomplete, never added to it.
+    pub fn for_each_extension(
+        &mut self,
+        mut f: impl FnMut(&Extension) -> ControlFlow<()>,
+    ) -> Result<ControlFlow<()>> {
+        let timer = Timer::start();
+        let computed = match &self.grounded {
+            Some(grounded) => Some(vec![grounded.extension()]),
+            None if self.config.cache => self
+                .cache
+                .get(&self.state_key())
+                .and_then(|cached| cached.extensions.clone()),
+            None => None,
+        };
+        let computed = match computed {
+            Some(extensions) => Some(extensions),
+            None => self.decomposed_extensions()?,
+        };
+        if let Some(extensions) = computed {
+            let flow = extensions.iter().try_for_each(&mut f);
+            self.profile.record(Phase::Solve, timer);
+            return Ok(flow);
+        }
+        self.assign_pending()?;
+        let ctl = self.take_control()?;
+        let mut handle = ctl.solve(SolveMode::YIELD, &[])?;
+        let mut seen = self.config.deduplicate.map(Fingerprints::new);
+        let limit = self.config.models.map_or(0, NonZeroUsize::get);
+        let flow = visit_models(&mut handle, &self.interner, limit, seen.as_mut(), &mut f);
+        // Recover the control before reporting errors of the visit
+        let ctl = handle.close()?;
+        let models = clingo::enumerated_models(&ctl)?;
+        self.clingo_ctl = Some(ctl);
+        self.record_statistics(models);
+        self.profile.record(Phase::Solve, timer);
+        flow
+    }
+    /// Count the extensions from the solver statistics, ignoring the cache.
+    fn count_uncached(&mut self) -> Result<usize> {
+        let timer = Timer::start();
+        let count = self.solve_count()?;
+        self.profile.record(Phase::Solve, timer);
+        Ok(count)
+    }
+    /// Check for an extension, without recording the time.
+    fn solve_has_extension(&mut self) -> Result<bool> {
+        if self.grounded.is_some() {
+            return Ok(true);
+        }
+        if let Some(extensions) = self.decomposed_extensions()? {
+            return Ok(!extensions.is_empty());
+        }
+        self.assign_pending()?;
+        let ctl = self.take_control()?;
+        let (ctl, found) = clingo::has_model(ctl, &[])?;
+        self.clingo_ctl = Some(ctl);
+        self.record_statistics(usize::from(found));
+        Ok(found)
+    }
+    /// Check the candidate by a solve assuming exactly its arguments to be in.
+    fn verify_by_assumptions(&mut self, candidate: &Extension) -> Result<bool> {
+        let (arguments, _) = self.active()?;
+        let mut assumptions = Vec::with_capacity(arguments.len());
+        let mut found = 0;
+        for argument in &arguments {
+            let is_in = candidate.contains(argument);
+            found += usize::from(is_in);
+            let symbol = Symbol::create_function("in", &[symbols::id_symbol(&argument.id)?], true)?;
+            match self.literals.get(&symbol) {
+                Some(&literal) if is_in => assumptions.push(literal),
+                Some(&literal) => assumptions.push(literal.negate()),
+                // Arguments without an atom are never in
+                None if is_in => return Ok(false),
+                None => {}
+            }
+        }
+        // Arguments that are not part of the framework make the candidate invalid
+        if found != candidate.len() {
+            return Ok(false);
+        }
+        self.assign_pending()?;
+        let ctl = self.take_control()?;
+        let (ctl, valid) = clingo::has_model(ctl, &assumptions)?;
+        self.clingo_ctl = Some(ctl);
+        Ok(valid)
+    }
+    /// Count the extensions, without recording the time.
+    fn solve_count(&mut self) -> Result<usize> {
+        if self.grounded.is_some() {
+            return Ok(1);
+        }
+        if let Some(decomposition) = S::DECOMPOSITION.filter(|_| self.config.decompose) {
+            let (arguments, attacks) = self.active()?;
+            let count = decomposition::count(&arguments, &attacks, decomposition)?;
+            return Ok(self
+                .config
+                .models
+                .map_or(count, |models| count.min(models.get())));
+        }
+        self.assign_pending()?;
+        let ctl = self.take_control()?;
+        let (ctl, count) = clingo::count_models(ctl)?;
+        self.clingo_ctl = Some(ctl);
+        self.record_statistics(count);
+        Ok(count)
     }
 }
 
+#[cfg(feature = "clingo")]
 impl<S: ArgumentationFrameworkSemantic> Framework for ArgumentationFramework<S> {
     type Extension = Extension;
     type ExtensionIter = ExtensionIter;
 
+    /// Enumerate the extensions.
+    ///
+    /// With [`FrameworkConfig::cache`], all extensions are computed up front and remembered.
     fn enumerate_extensions(&mut self) -> Result<IterGuard<'_, Self>> {
         log::trace!("Solving.. enumerating extensions");
-        let ctl = self.clingo_ctl.take().expect("Clingo control initialized");
-        let handle = ctl.solve(SolveMode::YIELD, &[])?;
-        Ok(IterGuard::new(self, ExtensionIter { handle }))
+        if !self.config.cache {
+            return self.enumerate_uncached();
+        }
+        let key = self.state_key();
+        let extensions = match self
+            .cache
+            .get(&key)
+            .and_then(|cached| cached.extensions.clone())
+        {
+            Some(extensions) => {
+                log::trace!("Found {} cached extensions", extensions.len());
+                extensions
+            }
+            None => {
+                let extensions: Vec<_> = self.enumerate_uncached()?.by_ref().collect()?;
+                self.cache.entry(key).or_default().extensions = Some(extensions.clone());
+                extensions
+            }
+        };
+        let source = ExtensionSource::Computed(extensions.into_iter());
+        let (timer, seen) = (None, None);
+        let interner = self.interner.clone();
+        Ok(IterGuard::new(
+            self,
+            ExtensionIter {
+                source,
+                timer,
+                seen,
+                interner,
+            },
+        ))
+    }
+
+    /// Count the extensions without extracting the symbols of any model.
+    ///
+    /// The count is taken from the solver statistics.
+    fn count_extensions(&mut self) -> Result<usize> {
+        log::trace!("Solving.. counting extensions");
+        if !self.config.cache {
+            return self.count_uncached();
+        }
+        let key = self.state_key();
+        if let Some(cached) = self.cache.get(&key) {
+            if let Some(count) = cached
+                .count
+                .or_else(|| cached.extensions.as_ref().map(Vec::len))
+            {
+                log::trace!("Found cached count {count}");
+                return Ok(count);
+            }
+        }
+        let count = self.count_uncached()?;
+        self.cache.entry(key).or_default().count = Some(count);
+        Ok(count)
     }
 
     fn new(input: &str) -> Result<Self> {
-        let (args, attacks) = parse_apx_tgf(input)?;
-        let clingo_ctl = clingo::initialize_backend::<S>(&args, &attacks)?;
-        Ok(ArgumentationFramework {
-            _semantics: PhantomData,
-            _initial_file: input.to_owned(),
-            clingo_ctl: Some(clingo_ctl),
-        })
+        Self::new_with_config(input, FrameworkConfig::default())
     }
 
-    fn update(&mut self, update_line: &str) -> Result<()> {
-        fallible_iterator::convert(
-            parser::parse_apxm_tgfm_patch_line(update_line)?
-                .into_iter()
-                .map(Ok),
+    /// Check the candidate by a solve with the arguments of the candidate as assumptions.
+    ///
+    /// Semantics whose extensions are not the models of their program are enumerated
+    /// instead.
+    fn verify_extension(&mut self, candidate: &Extension) -> Result<bool> {
+        if !self.models_are_extensions() {
+            return framework::verify_by_enumeration(self, candidate);
+        }
+        let timer = Timer::start();
+        let valid = self.verify_by_assumptions(candidate)?;
+        self.profile.record(Phase::Solve, timer);
+        Ok(valid)
+    }
+
+    /// Check for an extension by a solve bounded to a single model.
+    fn has_extension(&mut self) -> Result<bool> {
+        log::trace!("Solving.. checking for an extension");
+        let timer = Timer::start();
+        let found = self.solve_has_extension()?;
+        self.profile.record(Phase::Solve, timer);
+        Ok(found)
+    }
+
+    /// Find an optimal extension by optimization, see [`optimal`].
+    ///
+    /// Semantics whose extensions are not the models of their program are enumerated
+    /// instead.
+    fn optimal_extension(&mut self, objective: Objective) -> Result<Option<Extension>> {
+        if !self.models_are_extensions() {
+            return framework::optimal_by_enumeration(self, objective);
+        }
+        let (arguments, attacks) = self.current_elements()?;
+        optimal::extension::<S>(&arguments, &attacks, objective, &self.config, self.logger())
+    }
+
+    /// Compute the union of all extensions as brave consequences, see [`consequences`].
+    ///
+    /// Semantics whose extensions are not the models of their program are enumerated
+    /// instead.
+    fn credulously_accepted_arguments(&mut self) -> Result<Option<Extension>> {
+        if !self.models_are_extensions() {
+            return framework::union_by_enumeration(self);
+        }
+        let (arguments, attacks) = self.current_elements()?;
+        consequences::arguments::<S>(
+            &arguments,
+            &attacks,
+            Consequences::Brave,
+            &self.config,
+            self.logger(),
         )
-        .for_each(|patch| self.apply_patch(&patch))
+    }
+
+    /// Compute the intersection of all extensions as cautious consequences, see
+    /// [`consequences`].
+    ///
+    /// Semantics whose extensions are not the models of their program are enumerated
+    /// instead.
+    fn skeptically_accepted_arguments(&mut self) -> Result<Option<Extension>> {
+        if !self.models_are_extensions() {
+            return framework::intersection_by_enumeration(self);
+        }
+        let (arguments, attacks) = self.current_elements()?;
+        consequences::arguments::<S>(
+            &arguments,
+            &attacks,
+            Consequences::Cautious,
+            &self.config,
+            self.logger(),
+        )
+    }
+
+    fn update(&mut self, update_line: &str) -> Result<()> {
+        self.apply_patches(&parser::parse_apxm_tgfm_patch_line(update_line)?)
+    }
+
+    /// Parse all lines first, then apply their patches as one batch.
+    ///
+    /// Either all lines are applied or, if any line fails, none.
+    fn update_many(&mut self, update_lines: &[&str]) -> Result<()> {
+        let mut patches = vec![];
+        for line in update_lines {
+            patches.extend(parser::parse_apxm_tgfm_patch_line(line)?);
+        }
+        self.apply_patches(&patches)
     }
 
     fn drop_extension_iter(&mut self, iter: Self::ExtensionIter) -> Result<()> {
-        self.clingo_ctl = Some(iter.handle.close()?);
+        if let ExtensionSource::Solving { handle, .. } = iter.source {
+            let ctl = handle.close()?;
+            let models = clingo::enumerated_models(&ctl)?;
+            self.clingo_ctl = Some(ctl);
+            self.record_statistics(models);
+        }
+        if let Some(timer) = iter.timer {
+            self.profile.record(Phase::Solve, timer);
+        }
         Ok(())
     }
 }
@@ -274,21 +1857,73 @@ impl GenericExtension for Extension {
     type Arg = symbols::Argument;
 
     fn contains(&self, arg: &Self::Arg) -> bool {
-        self.atoms.contains(arg)
+        let Some(interner) = &self.interner else {
+            return false;
+        };
+        interner.lookup(&arg.id).is_some_and(|idx| {
+            let (word, bit) = (idx / u64::BITS as usize, idx % u64::BITS as usize);
+            self.words
+                .get(word)
+                .is_some_and(|word| word & (1 << bit) != 0)
+        })
     }
 
     fn format(&self) -> String {
         String::from("[")
             + &self
-                .atoms
-                .iter()
+                .arguments()
                 .map(|atom| atom.id.clone())
                 .reduce(|acc, atom| format!("{acc},{atom}"))
                 .unwrap_or_default()
             + "]"
     }
+
+    fn format_witness(&self) -> String {
+        self.arguments()
+            .fold(String::from("w"), |acc, atom| format!("{acc} {}", atom.id))
+    }
+
+    fn len(&self) -> usize {
+        Extension::len(self)
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        if !self.shares_interner(other) {
+            return self.reinterned(other).union(other);
+        }
+        let (longer, shorter) = if self.words.len() >= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut words = longer.words.clone();
+        words
+            .iter_mut()
+            .zip(&shorter.words)
+            .for_each(|(word, other)| *word |= other);
+        let interner = longer.interner.clone().or_else(|| shorter.interner.clone());
+        Extension { words, interner }
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        if !self.shares_interner(other) {
+            return self.reinterned(other).intersection(other);
+        }
+        let mut words: Vec<_> = self
+            .words
+            .iter()
+            .zip(&other.words)
+            .map(|(word, other)| word & other)
+            .collect();
+        while words.last() == Some(&0) {
+            words.pop();
+        }
+        let interner = self.interner.clone();
+        Extension { words, interner }
+    }
 }
 
+#[cfg(feature = "clingo")]
 fn print_model(model: &::clingo::Model) {
     // get model type
     let model_type = model.model_type().unwrap();
@@ -320,37 +1955,84 @@ fn print_model(model: &::clingo::Model) {
     print(model, "-- ~atoms", ShowType::COMPLEMENT | ShowType::ATOMS);
 }
 
+#[cfg(feature = "clingo")]
 impl FallibleIterator for ExtensionIter {
     type Item = Extension;
     type Error = Error;
 
     fn next(&mut self) -> Result<Option<Self::Item>> {
         log::trace!("Fetching next extension from iterator");
-        if let Err(why) = self.handle.resume() {
-            log::warn!("Error while resuming solving");
-            return Err(why.into());
-        }
-        match self.handle.model().map_err(crate::Error::from) {
-            Ok(Some(model)) => {
-                print_model(model);
-                Some(Extension::from_model(model)).transpose()
+        let (handle, models, limit) = match &mut self.source {
+            ExtensionSource::Solving {
+                handle,
+                models,
+                limit,
+            } => (handle, models, *limit),
+            ExtensionSource::Computed(extensions) => return Ok(extensions.next()),
+        };
+        loop {
+            if let Err(why) = handle.resume() {
+                log::warn!("Error while resuming solving");
+                return Err(why.into());
+            }
+            let extension = match handle.model()? {
+                Some(model) => {
+                    if log::log_enabled!(log::Level::Trace) {
+                        print_model(model);
+                    }
+                    *models += 1;
+                    Extension::from_model(model, &self.interner)?
+                }
+                None => {
+                    clingo::check_decided(handle.get()?, *models, limit)?;
+                    return Ok(None);
+                }
+            };
+            match &mut self.seen {
+                Some(seen) if !seen.insert(&extension) => {
+                    log::trace!("Skipping duplicate extension {extension:?}")
+                }
+                _ => return Ok(Some(extension)),
             }
-            Ok(None) => Ok(None),
-            Err(why) => Err(why),
+        }
+    }
+}
+
+#[cfg(feature = "clingo")]
+/// Pass the models of a running solve to `f`, until it breaks or the solve is done.
+fn visit_models(
+    handle: &mut ::clingo::GenericSolveHandle<Logger, Non, Non, Non, Non>,
+    interner: &Arc<Interner>,
+    limit: usize,
+    mut seen: Option<&mut Fingerprints>,
+    f: &mut impl FnMut(&Extension) -> ControlFlow<()>,
+) -> Result<ControlFlow<()>> {
+    let mut models = 0;
+    loop {
+        handle.resume()?;
+        let Some(model) = handle.model()? else {
+            clingo::check_decided(handle.get()?, models, limit)?;
+            return Ok(ControlFlow::Continue(()));
+        };
+        models += 1;
+        let extension = Extension::from_model(model, interner)?;
+        if seen.as_mut().is_some_and(|seen| !seen.insert(&extension)) {
+            continue;
+        }
+        if f(&extension).is_break() {
+            return Ok(ControlFlow::Break(()));
         }
     }
 }
 
 impl FromIterator<ArgumentID> for Extension {
+    /// Collect the ids into an extension with an interner of its own.
     fn from_iter<T: IntoIterator<Item = ArgumentID>>(iter: T) -> Self {
-        Self {
-            atoms: iter
-                .into_iter()
-                .map(|id| symbols::Argument {
-                    id,
-                    optional: false,
-                })
-                .collect(),
+        let ids: Vec<_> = iter.into_iter().collect();
+        if ids.is_empty() {
+            return Extension::EMPTY;
         }
+        let interner = Interner::with_ids(ids.iter().map(String::as_str));
+        Extension::with_ids(&interner, &ids)
     }
 }
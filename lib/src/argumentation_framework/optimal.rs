@@ -0,0 +1,59 @@
+//! Extensions of optimal size
+//!
+//! Instead of enumerating all extensions, the encoding of the semantics is extended by
+//! an optimization statement over the arguments of the extension, so that clingo
+//! searches for the largest or smallest one directly.
+use super::{
+    clingo, semantics::ArgumentationFrameworkSemantic, symbols, Extension, FrameworkConfig,
+};
+use crate::{Error, Objective, Result};
+
+/// Compute an extension of optimal size by optimization.
+///
+/// All elements are considered part of the framework. Of all optimal extensions, the
+/// least one is returned. Only correct if every model of the program of the semantics
+/// is an extension, i.e. without [`ArgumentationFrameworkSemantic::EXTREMUM`].
+pub(super) fn extension<S: ArgumentationFrameworkSemantic>(
+    arguments: &[symbols::Argument],
+    attacks: &[symbols::Attack],
+    objective: Objective,
+    config: &FrameworkConfig,
+    logger: clingo::Logger,
+) -> Result<Option<Extension>> {
+    let program = program::<S>(arguments, attacks, objective);
+    let models = clingo::optimal_models(&program, config, logger)?;
+    let extensions = models
+        .into_iter()
+        .map(|shown| {
+            shown
+                .into_iter()
+                .map(|symbol| match symbol.arguments()?[..] {
+                    [id] => Ok(symbols::symbol_id(id)?),
+                    _ => Err(Error::Logic(format!("Unexpected symbol {symbol}"))),
+                })
+                .collect::<Result<Extension>>()
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(extensions.into_iter().min())
+}
+
+/// Program of the semantics, optimizing the number of arguments in the extension.
+fn program<S: ArgumentationFrameworkSemantic>(
+    arguments: &[symbols::Argument],
+    attacks: &[symbols::Attack],
+    objective: Objective,
+) -> String {
+    let mut program = clingo::fact_program(arguments, attacks);
+    program += S::base();
+    let statement = match objective {
+        Objective::Maximum => "#maximize",
+        Objective::Minimum => "#minimize",
+    };
+    program += &format!(
+        r#"
+            {statement} {{ 1,X : in(X) }}.
+            #show in/1.
+        "#
+    );
+    program
+}
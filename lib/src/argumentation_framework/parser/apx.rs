@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use logos::Logos;
 
 use crate::{argumentation_framework::symbols, framework::ParserError};
@@ -23,13 +25,13 @@ pub enum Token {
     Period,
     #[token(")")]
     RightParen,
-    #[regex(r"[a-z][a-zA-Z0-9_-]*")]
+    #[regex(r"[a-z\p{L}&&[^A-Z]][a-zA-Z0-9_\-\p{L}\p{N}\p{M}]*")]
     Text,
 }
 
-enum ArgOrAttack {
-    Arg(String),
-    Attack(String, String),
+enum ArgOrAttack<'s> {
+    Arg(&'s str),
+    Attack(&'s str, &'s str),
 }
 
 pub fn parse_file(input: &str) -> ParserResult<(Vec<symbols::Argument>, Vec<symbols::Attack>)> {
@@ -56,70 +58,103 @@ pub fn parse_file(input: &str) -> ParserResult<(Vec<symbols::Argument>, Vec<symb
             break;
         }
     }
-    optionals.into_iter().try_for_each(|opt| {
+    if !optionals.is_empty() {
+        mark_optionals(&mut args, &mut attacks, optionals)?;
+    }
+    Ok((args, attacks))
+}
+
+/// Mark the first declaration of every optional element as optional.
+///
+/// Only the few optional elements are hashed, so that large frameworks
+/// are scanned at most once and not indexed as a whole.
+fn mark_optionals(
+    args: &mut [symbols::Argument],
+    attacks: &mut [symbols::Attack],
+    optionals: Vec<ArgOrAttack>,
+) -> ParserResult<()> {
+    let mut missing_args = HashSet::new();
+    let mut missing_attacks = HashSet::new();
+    for opt in &optionals {
+        match *opt {
+            ArgOrAttack::Arg(arg_id) => missing_args.insert(arg_id),
+            ArgOrAttack::Attack(from, to) => missing_attacks.insert((from, to)),
+        };
+    }
+    for arg in args.iter_mut() {
+        if missing_args.is_empty() {
+            break;
+        }
+        if missing_args.remove(arg.id.as_str()) {
+            arg.optional = true;
+        }
+    }
+    for attack in attacks.iter_mut() {
+        if missing_attacks.is_empty() {
+            break;
+        }
+        if missing_attacks.remove(&(attack.from.as_str(), attack.to.as_str())) {
+            attack.optional = true;
+        }
+    }
+    // Report the first optional element that was never declared
+    for opt in optionals {
         match opt {
-            ArgOrAttack::Arg(arg_id) => match args.iter_mut().find(|arg| arg.id == arg_id) {
-                Some(arg) => arg.optional = true,
-                None => return Err(ParserError::OptionalArgumentNotFound { arg_id }),
-            },
-            ArgOrAttack::Attack(from, to) => match attacks
-                .iter_mut()
-                .find(|attack| attack.from == from && attack.to == to)
-            {
-                Some(attack) => attack.optional = true,
-                None => return Err(ParserError::OptionalAttackNotFound { from, to }),
-            },
+            ArgOrAttack::Arg(arg_id) if missing_args.contains(arg_id) => {
+                return Err(ParserError::OptionalArgumentNotFound {
+                    arg_id: arg_id.to_owned(),
+                })
+            }
+            ArgOrAttack::Attack(from, to) if missing_attacks.contains(&(from, to)) => {
+                return Err(ParserError::OptionalAttackNotFound {
+                    from: from.to_owned(),
+                    to: to.to_owned(),
+                })
+            }
+            _ => {}
         }
-        Ok(())
-    })?;
-    Ok((args, attacks))
+    }
+    Ok(())
 }
 
 fn parse_attack(lex: &mut logos::Lexer<Token>) -> ParserResult<symbols::Attack> {
     expect(lex, Token::LeftParen)?;
     expect(lex, Token::Text)?;
-    let from = lex.slice().to_owned();
+    let from = lex.slice();
     expect(lex, Token::Comma)?;
     expect(lex, Token::Text)?;
-    let to = lex.slice().to_owned();
+    let to = lex.slice();
     expect(lex, Token::RightParen)?;
     expect(lex, Token::Period)?;
-    Ok(symbols::Attack {
-        from,
-        to,
-        optional: false,
-    })
+    Ok(symbols::Attack::new(from, to, false))
 }
 
 fn parse_argument(lex: &mut logos::Lexer<Token>) -> ParserResult<symbols::Argument> {
     expect(lex, Token::LeftParen)?;
     expect(lex, Token::Text)?;
-    let id = lex.slice().to_owned();
+    let id = lex.slice();
     expect(lex, Token::RightParen)?;
     expect(lex, Token::Period)?;
-    Ok(symbols::Argument {
-        id,
-        optional: false,
-    })
+    Ok(symbols::Argument::new(id, false))
 }
 
-fn parse_optional(lex: &mut logos::Lexer<Token>) -> ParserResult<ArgOrAttack> {
+fn parse_optional<'s>(lex: &mut logos::Lexer<'s, Token>) -> ParserResult<ArgOrAttack<'s>> {
     expect(lex, Token::LeftParen)?;
     let arg_or_attack = match lex.next() {
         Some(Token::Arg) => {
             expect(lex, Token::LeftParen)?;
             expect(lex, Token::Text)?;
-            let arg = lex.slice().to_owned();
+            let arg = lex.slice();
             expect(lex, Token::RightParen)?;
             Ok(ArgOrAttack::Arg(arg))
         }
         Some(Token::Attack) => {
             expect(lex, Token::LeftParen)?;
             expect(lex, Token::Text)?;
-            let from = lex.slice().to_owned();
+            let from = lex.slice();
             expect(lex, Token::Comma)?;
             expect(lex, Token::Text)?;
-            let to = lex.slice().to_owned();
+            let to = lex.slice();
             expect(lex, Token::RightParen)?;
             Ok(ArgOrAttack::Attack(from, to))
         }
@@ -175,4 +210,20 @@ mod tests {
             )
         }
     }
+
+    #[test]
+    fn optionals_mark_their_first_declaration() {
+        let af = parse_file("arg(a). arg(a). att(a, a). att(a, a). opt(att(a, a)). opt(arg(a)).")
+            .unwrap();
+        assert_eq! {
+            af,
+            ( vec![arg!("a" opt), arg!("a")],
+              vec![att!("a", "a" opt), att!("a", "a")],
+            )
+        }
+        assert!(matches!(
+            parse_file("arg(a). opt(att(a, b)). opt(arg(b))."),
+            Err(ParserError::OptionalAttackNotFound { from, to }) if from == "a" && to == "b"
+        ));
+    }
 }
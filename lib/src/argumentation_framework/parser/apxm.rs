@@ -29,7 +29,7 @@ pub enum Token {
     Plus,
     #[token(")")]
     RightParen,
-    #[regex(r"[a-z][a-zA-Z0-9_-]*")]
+    #[regex(r"[a-z\p{L}&&[^A-Z]][a-zA-Z0-9_\-\p{L}\p{N}\p{M}]*")]
     Text,
 }
 
@@ -0,0 +1,153 @@
+//! The numeric format of ICCMA 2023.
+//!
+//! A file starts with the header `p af <n>`, declaring the arguments `1` to `n`,
+//! followed by one attack `<from> <to>` per line. Lines starting with `#` are comments.
+//! Update lines work like TGFM, with numeric ids, i.e. `+4:4 1:2 4` or `-1 2`.
+use std::{fmt::Debug, ops::Range};
+
+use crate::argumentation_framework::{symbols, updates::is_comment, Patch};
+
+use super::{ParserError, ParserResult};
+
+/// Whether the input starts with the `p af` header, ignoring comments.
+pub fn has_header(input: &str) -> bool {
+    input
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !is_comment(line))
+        .is_some_and(|line| line.split_whitespace().take(2).eq(["p", "af"]))
+}
+
+pub fn parse_file(input: &str) -> ParserResult<(Vec<symbols::Argument>, Vec<symbols::Attack>)> {
+    let mut lines = significant_lines(input);
+    let Some((header, position)) = lines.next() else {
+        let expected: Box<dyn Debug + Send + Sync> = Box::new("p af <n>");
+        return Err(ParserError::UnexpectedEndOfInput {
+            expected: vec![expected],
+        });
+    };
+    let count = match header.split_whitespace().collect::<Vec<_>>()[..] {
+        ["p", "af", count] => count.parse::<usize>().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| unexpected(header, "p af <n>", position))?;
+    let args = (1..=count)
+        .map(|id| symbols::Argument::new(id.to_string(), false))
+        .collect();
+    let mut attacks = vec![];
+    for (line, position) in lines {
+        let (from, to) = match line.split_whitespace().collect::<Vec<_>>()[..] {
+            [from, to] => (from, to),
+            _ => return Err(unexpected(line, "<from> <to>", position)),
+        };
+        for id in [from, to] {
+            if !id
+                .parse::<usize>()
+                .is_ok_and(|id| (1..=count).contains(&id))
+            {
+                return Err(ParserError::UndeclaredArgument {
+                    arg_id: id.to_owned(),
+                    from: from.to_owned(),
+                    to: to.to_owned(),
+                });
+            }
+        }
+        attacks.push(symbols::Attack::new(from, to, false));
+    }
+    Ok((args, attacks))
+}
+
+pub fn parse_line(input: &str) -> ParserResult<Vec<Patch>> {
+    let line = input.trim();
+    let (enable, rest) = match (line.strip_prefix('+'), line.strip_prefix('-')) {
+        (Some(rest), _) => (true, rest),
+        (_, Some(rest)) => (false, rest),
+        _ => return Err(unexpected(line, "+ or -", 0..line.len())),
+    };
+    rest.split(':')
+        .map(|element| {
+            let ids: Vec<_> = element.split_whitespace().collect();
+            if ids.is_empty() || ids.iter().any(|id| id.parse::<usize>().is_err()) {
+                return Err(unexpected(element, "numeric ids", 0..line.len()));
+            }
+            match (ids.as_slice(), enable) {
+                (&[id], true) => Ok(Patch::EnableArgument(symbols::Argument::new(id, false))),
+                (&[id], false) => Ok(Patch::DisableArgument(symbols::Argument::new(id, false))),
+                (&[from, to], true) => {
+                    Ok(Patch::EnableAttack(symbols::Attack::new(from, to, false)))
+                }
+                (&[from, to], false) => {
+                    Ok(Patch::DisableAttack(symbols::Attack::new(from, to, false)))
+                }
+                _ => Err(unexpected(element, "<id> or <from> <to>", 0..line.len())),
+            }
+        })
+        .collect()
+}
+
+/// Trimmed lines that are neither empty nor comments, with their position in the input.
+fn significant_lines(input: &str) -> impl Iterator<Item = (&str, Range<usize>)> {
+    input
+        .split_inclusive('\n')
+        .scan(0, |start, line| {
+            let position = *start..*start + line.len();
+            *start += line.len();
+            Some((line.trim(), position))
+        })
+        .filter(|(line, _)| !line.is_empty() && !is_comment(line))
+}
+
+fn unexpected(found: &str, expected: &'static str, position: Range<usize>) -> ParserError {
+    let expected: Box<dyn Debug + Send + Sync> = Box::new(expected);
+    ParserError::UnexpectedToken {
+        found: Box::new(found.to_owned()),
+        expected: vec![expected],
+        position,
+        text: found.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::macros::{arg, att};
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn numeric_files() {
+        let input = "# generated\np af 3\n1 2\n\n# comment\n2 3\n";
+        assert!(has_header(input));
+        let af = parse_file(input).unwrap();
+        assert_eq!(
+            af,
+            (
+                vec![arg!("1"), arg!("2"), arg!("3")],
+                vec![att!("1", "2"), att!("2", "3")]
+            )
+        );
+        assert!(!has_header("a\n#\n"));
+        assert!(matches!(
+            parse_file("p af 2\n1 3\n"),
+            Err(ParserError::UndeclaredArgument { arg_id, .. }) if arg_id == "3"
+        ));
+        assert!(parse_file("p af two\n").is_err());
+    }
+
+    #[test]
+    fn numeric_patches() {
+        assert_eq!(
+            parse_line("+4:4 1:2 4").unwrap(),
+            vec![
+                Patch::EnableArgument(arg!("4")),
+                Patch::EnableAttack(att!("4", "1")),
+                Patch::EnableAttack(att!("2", "4"))
+            ]
+        );
+        assert_eq!(
+            parse_line("-1 2").unwrap(),
+            vec![Patch::DisableAttack(att!("1", "2"))]
+        );
+        assert!(parse_line("-a1").is_err());
+    }
+}
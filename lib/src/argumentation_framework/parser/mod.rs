@@ -72,6 +72,9 @@
 //! Name     ::= [a-z] [a-zA-Z_0-9#x2D]*
 //! ```
 //!
+//! Additionally, non-ASCII letters may start a name and non-ASCII letters, digits and
+//! marks may continue it.
+//!
 //! referenced by:
 //!
 //! * ASPArgument
@@ -144,23 +147,77 @@
 
 use crate::{framework::ParserError, Result};
 
-use super::{symbols, ArgumentID, Patch};
+use super::{symbols, updates::is_comment, ArgumentID, Patch};
 
 mod apx;
 mod apxm;
+mod iccma23;
 mod tgf;
 mod tgfm;
 type ParserResult<T> = Result<T, ParserError>;
 
 pub fn parse_apx_tgf(input: &str) -> ParserResult<(Vec<symbols::Argument>, Vec<symbols::Attack>)> {
+    if iccma23::has_header(input) {
+        return iccma23::parse_file(input);
+    }
     apx::parse_file(input).or_else(|why| {
         log::trace!("Not an APX file: {why}");
         tgf::parse_file(input)
     })
 }
 
+/// Parse like [`parse_apx_tgf`], but reject the DASP-specific `opt(..)` and `?` markers.
+pub fn parse_apx_tgf_iccma(
+    input: &str,
+) -> ParserResult<(Vec<symbols::Argument>, Vec<symbols::Attack>)> {
+    let (args, attacks) = parse_apx_tgf(input)?;
+    let optional_arg = args
+        .iter()
+        .find(|arg| arg.optional)
+        .map(ToString::to_string);
+    let optional_attack = || {
+        attacks
+            .iter()
+            .find(|attack| attack.optional)
+            .map(ToString::to_string)
+    };
+    match optional_arg.or_else(optional_attack) {
+        Some(element) => Err(ParserError::NotIccmaCompliant { element }),
+        None => Ok((args, attacks)),
+    }
+}
+
 pub fn parse_apxm_tgfm_patch_line(input: &str) -> ParserResult<Vec<Patch>> {
-    apxm::parse_line(input).or_else(|_| tgfm::parse_line(input))
+    let (_, patches) = parse_timed_patch_line(input)?;
+    Ok(patches)
+}
+
+/// Parse an update line in APXM, TGFM or numeric ICCMA 2023 format with an optional
+/// leading timestamp, i.e. `@1234: +arg(a).`. Comment lines contain no patches.
+pub fn parse_timed_patch_line(input: &str) -> ParserResult<(Option<u64>, Vec<Patch>)> {
+    if is_comment(input) {
+        return Ok((None, vec![]));
+    }
+    let (timestamp, input) = split_timestamp(input)?;
+    let patches = apxm::parse_line(input)
+        .or_else(|_| tgfm::parse_line(input))
+        .or_else(|why| iccma23::parse_line(input).map_err(|_| why))?;
+    Ok((timestamp, patches))
+}
+
+/// Split the leading `@<timestamp>:` from the update line, if any.
+fn split_timestamp(input: &str) -> ParserResult<(Option<u64>, &str)> {
+    let Some(stamped) = input.trim_start().strip_prefix('@') else {
+        return Ok((None, input));
+    };
+    let (text, rest) = stamped.split_once(':').unwrap_or((stamped, ""));
+    let timestamp = text
+        .trim()
+        .parse()
+        .map_err(|_| ParserError::InvalidTimestamp {
+            text: text.to_owned(),
+        })?;
+    Ok((Some(timestamp), rest.trim_start()))
 }
 
 #[derive(Debug)]
@@ -177,7 +234,12 @@ pub struct RawAttack {
 /// Expect the given Token and fail if it's not present
 fn expect<'l, T>(lex: &mut logos::Lexer<'l, T>, expected: T) -> ParserResult<T>
 where
-    T: logos::Logos<'l, Source = str> + std::cmp::PartialEq + std::fmt::Debug + 'static,
+    T: logos::Logos<'l, Source = str>
+        + std::cmp::PartialEq
+        + std::fmt::Debug
+        + Send
+        + Sync
+        + 'static,
 {
     let next = lex.next();
     match next {
@@ -215,4 +277,32 @@ a2 a1"#,
         .unwrap();
         assert_eq!(af, (vec![arg!("a1"), arg!("a2")], vec![att!("a2", "a1")],));
     }
+
+    #[test]
+    fn timestamps_and_comments_in_update_lines() {
+        assert_eq!(
+            parse_timed_patch_line("@1234: +arg(a).").unwrap(),
+            (Some(1234), vec![Patch::EnableArgument(arg!("a"))])
+        );
+        assert_eq!(
+            parse_timed_patch_line("@7:-a b").unwrap(),
+            (Some(7), vec![Patch::DisableAttack(att!("a", "b"))])
+        );
+        assert_eq!(
+            parse_timed_patch_line("-arg(a).").unwrap(),
+            (None, vec![Patch::DisableArgument(arg!("a"))])
+        );
+        assert_eq!(
+            parse_timed_patch_line("% +arg(a).").unwrap(),
+            (None, vec![])
+        );
+        assert_eq!(
+            parse_timed_patch_line("# recorded at noon").unwrap(),
+            (None, vec![])
+        );
+        assert!(matches!(
+            parse_timed_patch_line("@noon: +arg(a)."),
+            Err(ParserError::InvalidTimestamp { text }) if text == "noon"
+        ));
+    }
 }
@@ -15,7 +15,7 @@ pub enum Token {
     Minus,
     #[token("+")]
     Plus,
-    #[regex(r"[a-z][a-zA-Z0-9_-]*")]
+    #[regex(r"[a-z\p{L}&&[^A-Z]][a-zA-Z0-9_\-\p{L}\p{N}\p{M}]*")]
     Text,
     #[regex(" +")]
     Whitespace,
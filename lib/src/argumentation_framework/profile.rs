@@ -0,0 +1,126 @@
+//! Wall-clock timings of the solving phases
+//!
+//! Timings are only recorded with the `profiling` feature. Without it, [`Timer`]s are
+//! zero-sized and recording is a no-op, so the [`Profile`] stays empty.
+use std::{fmt, time::Duration};
+
+/// Phase of working on an [`ArgumentationFramework`](super::ArgumentationFramework).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Parsing the initial file
+    Parse,
+    /// Passing arguments and attacks to clingo
+    Facts,
+    /// Grounding the programs
+    Ground,
+    /// A single solve, until the extension iterator is dropped
+    Solve,
+    /// Applying the patches of a single update
+    Patch,
+}
+
+impl Phase {
+    /// All phases, in the order they first occur.
+    pub const ALL: [Phase; 5] = [
+        Phase::Parse,
+        Phase::Facts,
+        Phase::Ground,
+        Phase::Solve,
+        Phase::Patch,
+    ];
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Phase::Parse => "parse",
+            Phase::Facts => "facts",
+            Phase::Ground => "ground",
+            Phase::Solve => "solve",
+            Phase::Patch => "patch",
+        };
+        f.pad(name)
+    }
+}
+
+/// A single measurement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub phase: Phase,
+    pub duration: Duration,
+}
+
+/// All measurements taken so far, in chronological order.
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    timings: Vec<Timing>,
+}
+
+impl Profile {
+    /// Every single measurement, in chronological order.
+    pub fn timings(&self) -> &[Timing] {
+        &self.timings
+    }
+    /// Number of measurements of the phase.
+    pub fn count(&self, phase: Phase) -> usize {
+        self.of(phase).count()
+    }
+    /// Time spent in the phase over all measurements.
+    pub fn total(&self, phase: Phase) -> Duration {
+        self.of(phase).map(|timing| timing.duration).sum()
+    }
+    /// Record the time elapsed since the timer was started.
+    pub(crate) fn record(&mut self, phase: Phase, timer: Timer) {
+        #[cfg(feature = "profiling")]
+        self.timings.push(Timing {
+            phase,
+            duration: timer.start.elapsed(),
+        });
+        #[cfg(not(feature = "profiling"))]
+        let _ = (phase, timer);
+    }
+
+    fn of(&self, phase: Phase) -> impl Iterator<Item = &Timing> {
+        self.timings
+            .iter()
+            .filter(move |timing| timing.phase == phase)
+    }
+}
+
+impl fmt::Display for Profile {
+    /// Table with count, total and mean duration per phase.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<8}{:>8}{:>14}{:>14}",
+            "phase", "count", "total", "mean"
+        )?;
+        for phase in Phase::ALL {
+            let count = self.count(phase);
+            let total = self.total(phase);
+            let mean = total.checked_div(count as u32).unwrap_or_default();
+            writeln!(
+                f,
+                "{phase:<8}{count:>8}{:>14}{:>14}",
+                format!("{total:.3?}"),
+                format!("{mean:.3?}")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Measurement started by [`Timer::start`] and finished by [`Profile::record`].
+pub(crate) struct Timer {
+    #[cfg(feature = "profiling")]
+    start: std::time::Instant,
+}
+
+impl Timer {
+    pub(crate) fn start() -> Self {
+        Timer {
+            #[cfg(feature = "profiling")]
+            start: std::time::Instant::now(),
+        }
+    }
+}
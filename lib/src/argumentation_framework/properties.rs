@@ -0,0 +1,181 @@
+//! Property-based tests on small random frameworks and update sequences
+use std::collections::{BTreeMap, BTreeSet};
+
+use proptest::{prelude::*, sample::Index};
+
+use crate::semantics::{Admissible, ConflictFree};
+
+use super::*;
+
+/// Maximum number of arguments, keeping the number of extensions manageable.
+const MAX_ARGUMENTS: usize = 6;
+
+/// A small framework with some optional arguments and attacks.
+#[derive(Debug, Clone)]
+pub(super) struct ArbitraryAf {
+    pub(super) arguments: Vec<symbols::Argument>,
+    pub(super) attacks: Vec<symbols::Attack>,
+}
+
+impl ArbitraryAf {
+    /// APX program declaring the framework.
+    pub(super) fn program(&self) -> String {
+        let mut program = String::new();
+        for arg in &self.arguments {
+            program += &format!("{arg}. ");
+            if arg.optional {
+                program += &format!("opt({arg}). ");
+            }
+        }
+        for attack in &self.attacks {
+            program += &format!("{attack}. ");
+            if attack.optional {
+                program += &format!("opt({attack}). ");
+            }
+        }
+        program
+    }
+    /// Patches enabling the optional elements, which start disabled.
+    fn enabling_patches(&self) -> Vec<Patch> {
+        let args = self.arguments.iter().filter(|arg| arg.optional);
+        let attacks = self.attacks.iter().filter(|attack| attack.optional);
+        args.cloned()
+            .map(Patch::EnableArgument)
+            .chain(attacks.cloned().map(Patch::EnableAttack))
+            .collect()
+    }
+}
+
+/// Frameworks with up to `max_arguments` arguments.
+pub(super) fn frameworks(max_arguments: usize) -> BoxedStrategy<ArbitraryAf> {
+    (1..=max_arguments)
+        .prop_flat_map(|size| {
+            let optional_args = proptest::collection::vec(any::<bool>(), size);
+            let attacks =
+                proptest::collection::vec((0..size, 0..size, any::<bool>()), 0..=2 * size);
+            (optional_args, attacks)
+        })
+        .prop_map(|(optional_args, attacks)| {
+            let arguments = optional_args
+                .into_iter()
+                .enumerate()
+                .map(|(idx, optional)| symbols::Argument::new(format!("a{idx}"), optional))
+                .collect();
+            // Every attack is declared once
+            let attacks: BTreeMap<_, _> = attacks
+                .into_iter()
+                .map(|(from, to, optional)| ((from, to), optional))
+                .collect();
+            let attacks = attacks
+                .into_iter()
+                .map(|((from, to), optional)| {
+                    symbols::Attack::new(format!("a{from}"), format!("a{to}"), optional)
+                })
+                .collect();
+            ArbitraryAf { arguments, attacks }
+        })
+        .boxed()
+}
+
+impl Arbitrary for ArbitraryAf {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        frameworks(MAX_ARGUMENTS)
+    }
+}
+
+/// A framework and a sequence of updates toggling its optional elements.
+fn af_with_updates() -> impl Strategy<Value = (ArbitraryAf, Vec<(Index, bool)>)> {
+    (
+        any::<ArbitraryAf>(),
+        proptest::collection::vec((any::<Index>(), any::<bool>()), 0..8),
+    )
+}
+
+/// Resolve the random updates to patches, enabling or disabling an optional element.
+pub(super) fn patches(af: &ArbitraryAf, updates: &[(Index, bool)]) -> Vec<Patch> {
+    let optional = af.enabling_patches();
+    if optional.is_empty() {
+        return vec![];
+    }
+    updates
+        .iter()
+        .map(|(idx, enable)| match (idx.get(&optional), enable) {
+            (patch, true) => patch.clone(),
+            (Patch::EnableArgument(arg), false) => Patch::DisableArgument(arg.clone()),
+            (Patch::EnableAttack(attack), false) => Patch::DisableAttack(attack.clone()),
+            (patch, false) => unreachable!("Only enabling patches are generated, not {patch}"),
+        })
+        .collect()
+}
+
+pub(super) fn all_extensions<S: ArgumentationFrameworkSemantic>(
+    af: &mut ArgumentationFramework<S>,
+) -> BTreeSet<Extension> {
+    af.enumerate_extensions()
+        .expect("Enumerating extensions")
+        .by_ref()
+        .collect()
+        .expect("Collecting extensions")
+}
+
+fn config() -> ProptestConfig {
+    // Every case grounds and solves with clingo, keep the number small for CI
+    ProptestConfig::with_cases(32)
+}
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn admissible_extensions_are_conflict_free((af, updates) in af_with_updates()) {
+        let mut admissible = ArgumentationFramework::<Admissible>::new(&af.program()).unwrap();
+        let mut conflict_free = ArgumentationFramework::<ConflictFree>::new(&af.program()).unwrap();
+        let patches = patches(&af, &updates);
+        // Check the initial framework and the one after every update
+        for patch in [None].into_iter().chain(patches.iter().map(Some)) {
+            if let Some(patch) = patch {
+                admissible.apply_patch(patch).unwrap();
+                conflict_free.apply_patch(patch).unwrap();
+            }
+            let admissible_sets = all_extensions(&mut admissible);
+            prop_assert!(admissible_sets.is_subset(&all_extensions(&mut conflict_free)));
+            // The empty set is always admissible
+            prop_assert!(admissible_sets.contains(&Extension::EMPTY));
+        }
+    }
+
+    #[test]
+    fn semantics_relations_hold((af, updates) in af_with_updates()) {
+        let mut framework = ArgumentationFramework::<Admissible>::new(&af.program()).unwrap();
+        prop_assert_eq!(framework.verify_relations().unwrap(), vec![]);
+        for patch in patches(&af, &updates) {
+            framework.apply_patch(&patch).unwrap();
+            prop_assert_eq!(framework.verify_relations().unwrap(), vec![]);
+        }
+    }
+
+    #[test]
+    fn enable_followed_by_disable_restores_the_extensions(
+        (af, updates) in af_with_updates(),
+        toggled in any::<Index>(),
+    ) {
+        let mut framework = ArgumentationFramework::<Admissible>::new(&af.program()).unwrap();
+        for patch in patches(&af, &updates) {
+            framework.apply_patch(&patch).unwrap();
+        }
+        // Disable first, the element may be enabled by the updates already
+        let Some(enable) = patches(&af, &[(toggled, true)]).pop() else {
+            return Ok(());
+        };
+        let disable = patches(&af, &[(toggled, false)]).remove(0);
+        framework.apply_patch(&disable).unwrap();
+        let before = all_extensions(&mut framework);
+        framework.apply_patch(&enable).unwrap();
+        all_extensions(&mut framework);
+        framework.apply_patch(&disable).unwrap();
+        prop_assert_eq!(all_extensions(&mut framework), before);
+    }
+}
@@ -0,0 +1,128 @@
+//! Ranking-based semantics
+//!
+//! Instead of accepting sets of arguments, a ranking-based semantics orders all
+//! arguments from the most to the least acceptable. This computes the h-categoriser of
+//! Besnard and Hunter, scoring every argument by
+//!
+//! ```text
+//! Cat(a) = 1 / (1 + Σ Cat(b) for all attackers b of a)
+//! ```
+//!
+//! The scores are the unique fixpoint of this equation, approximated by iterating it
+//! starting from 1 for all arguments. Arguments are ranked by decreasing score.
+//!
+//! # Example
+//! ```
+//! use lib::argumentation_framework::{parse_initial_file, ranking::Ranking};
+//!
+//! let (args, attacks) = parse_initial_file("arg(a). arg(b). arg(c). att(a,b). att(b,c).").unwrap();
+//! let ranking = Ranking::h_categoriser(&args, &attacks);
+//! assert_eq!(ranking.levels(), [["a"], ["c"], ["b"]]);
+//! ```
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+};
+
+use super::{symbols, ArgumentID};
+
+/// Largest change of a score between two iterations at which they are considered final
+const TOLERANCE: f64 = 1e-12;
+/// Iterations after which the scores are returned, even if they did not converge yet
+const MAX_ITERATIONS: usize = 10_000;
+/// Difference up to which two scores rank the same
+const EQUALITY: f64 = 1e-9;
+
+/// Total preorder on the arguments of a framework.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ranking {
+    scores: BTreeMap<ArgumentID, f64>,
+}
+
+impl Ranking {
+    /// Rank the arguments by the h-categoriser.
+    ///
+    /// All given arguments and attacks are considered part of the framework, whether
+    /// they are optional or not. Attacks between undeclared arguments are ignored.
+    pub fn h_categoriser(arguments: &[symbols::Argument], attacks: &[symbols::Attack]) -> Self {
+        let index: HashMap<&str, usize> = arguments
+            .iter()
+            .enumerate()
+            .map(|(idx, arg)| (arg.id.as_str(), idx))
+            .collect();
+        let mut attackers = vec![vec![]; arguments.len()];
+        for attack in attacks {
+            if let (Some(&from), Some(&to)) = (
+                index.get(attack.from.as_str()),
+                index.get(attack.to.as_str()),
+            ) {
+                attackers[to].push(from);
+            }
+        }
+        let mut scores = vec![1.0; arguments.len()];
+        for iteration in 0.. {
+            let next: Vec<f64> = attackers
+                .iter()
+                .map(|from| 1.0 / (1.0 + from.iter().map(|&idx| scores[idx]).sum::<f64>()))
+                .collect();
+            let change = scores
+                .iter()
+                .zip(&next)
+                .map(|(old, new)| (old - new).abs())
+                .fold(0.0, f64::max);
+            scores = next;
+            if change < TOLERANCE {
+                break;
+            }
+            if iteration == MAX_ITERATIONS {
+                log::warn!("The h-categoriser did not converge, the largest change is {change}");
+                break;
+            }
+        }
+        let scores = arguments
+            .iter()
+            .map(|arg| arg.id.clone())
+            .zip(scores)
+            .collect();
+        Ranking { scores }
+    }
+
+    /// Score of the argument in (0, 1], higher is better.
+    pub fn score(&self, id: &str) -> Option<f64> {
+        self.scores.get(id).copied()
+    }
+
+    /// Compare the rank of two arguments, [`Ordering::Greater`] if `a` is ranked above
+    /// `b`.
+    ///
+    /// `None` if one of them is not an argument.
+    pub fn compare(&self, a: &str, b: &str) -> Option<Ordering> {
+        let (a, b) = (self.score(a)?, self.score(b)?);
+        if (a - b).abs() <= EQUALITY {
+            Some(Ordering::Equal)
+        } else {
+            a.partial_cmp(&b)
+        }
+    }
+
+    /// Arguments of equal rank, from the highest to the lowest rank.
+    pub fn levels(&self) -> Vec<Vec<ArgumentID>> {
+        let mut ranked: Vec<(&ArgumentID, f64)> =
+            self.scores.iter().map(|(id, &score)| (id, score)).collect();
+        ranked.sort_by(|(a, a_score), (b, b_score)| b_score.total_cmp(a_score).then(a.cmp(b)));
+        let mut levels: Vec<(f64, Vec<ArgumentID>)> = vec![];
+        for (id, score) in ranked {
+            match levels.last_mut() {
+                Some((first, level)) if *first - score <= EQUALITY => level.push(id.clone()),
+                _ => levels.push((score, vec![id.clone()])),
+            }
+        }
+        levels
+            .into_iter()
+            .map(|(_, mut level)| {
+                level.sort();
+                level
+            })
+            .collect()
+    }
+}
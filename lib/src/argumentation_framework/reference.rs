@@ -0,0 +1,319 @@
+//! Brute-force reference solver for small frameworks
+//!
+//! Enumerates every subset of the arguments and checks the definitions of the semantics
+//! directly, without clingo. Only feasible for a handful of arguments, but simple enough
+//! to serve as an oracle for the encodings.
+//!
+//! # Example
+//! ```
+//! use lib::argumentation_framework::reference::{self, ReferenceSemantics};
+//!
+//! let extensions = reference::solve("arg(a). arg(b). att(a,b).", ReferenceSemantics::Stable)
+//!     .expect("Solving");
+//! let ids: Vec<_> = extensions[0].arguments().map(|arg| arg.id.clone()).collect();
+//! assert_eq!(extensions.len(), 1);
+//! assert_eq!(ids, ["a"]);
+//! ```
+use std::collections::HashMap;
+
+use super::{parse_initial_file, symbols, Extension};
+use crate::{Error, GenericExtension, Result};
+
+/// Maximum number of arguments, as every subset is checked.
+pub const MAX_ARGUMENTS: usize = 20;
+
+/// Semantics known to the reference solver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceSemantics {
+    ConflictFree,
+    Admissible,
+    Complete,
+    Stable,
+    Grounded,
+    Preferred,
+    Cf2,
+    StronglyAdmissible,
+    ResolutionGrounded,
+}
+
+/// Framework with arguments as bit positions, the attackers of an argument as bitmask.
+struct Bitmasks {
+    attackers: Vec<u32>,
+}
+
+impl Bitmasks {
+    fn new(arguments: &[symbols::Argument], attacks: &[symbols::Attack]) -> Result<Self> {
+        if arguments.len() > MAX_ARGUMENTS {
+            return Err(Error::Logic(format!(
+                "The reference solver supports at most {MAX_ARGUMENTS} arguments, got {}",
+                arguments.len()
+            )));
+        }
+        let index: HashMap<&str, usize> = arguments
+            .iter()
+            .enumerate()
+            .map(|(idx, arg)| (arg.id.as_str(), idx))
+            .collect();
+        let mut attackers = vec![0; arguments.len()];
+        for attack in attacks {
+            if let (Some(&from), Some(&to)) = (
+                index.get(attack.from.as_str()),
+                index.get(attack.to.as_str()),
+            ) {
+                attackers[to] |= 1 << from;
+            }
+        }
+        Ok(Bitmasks { attackers })
+    }
+    fn all(&self) -> u32 {
+        ((1u64 << self.attackers.len()) - 1) as u32
+    }
+    fn members(&self, set: u32) -> impl Iterator<Item = usize> + '_ {
+        (0..self.attackers.len()).filter(move |arg| set & (1 << arg) != 0)
+    }
+    /// Arguments attacked by some member of `set`.
+    fn attacked_by(&self, set: u32) -> u32 {
+        self.members(self.all())
+            .filter(|&arg| self.attackers[arg] & set != 0)
+            .fold(0, |acc, arg| acc | 1 << arg)
+    }
+    /// Arguments all of whose attackers are attacked by `set`.
+    fn defended_by(&self, set: u32) -> u32 {
+        let attacked = self.attacked_by(set);
+        self.members(self.all())
+            .filter(|&arg| self.attackers[arg] & !attacked == 0)
+            .fold(0, |acc, arg| acc | 1 << arg)
+    }
+    fn is_conflict_free(&self, set: u32) -> bool {
+        self.attacked_by(set) & set == 0
+    }
+    fn is_extension(&self, set: u32, semantics: ReferenceSemantics) -> bool {
+        let conflict_free = self.is_conflict_free(set);
+        match semantics {
+            ReferenceSemantics::ConflictFree => conflict_free,
+            ReferenceSemantics::Admissible => conflict_free && set & !self.defended_by(set) == 0,
+            ReferenceSemantics::Complete => conflict_free && self.defended_by(set) == set,
+            ReferenceSemantics::Stable => {
+                conflict_free && self.attacked_by(set) | set == self.all()
+            }
+            ReferenceSemantics::Grounded => set == self.grounded(),
+            ReferenceSemantics::Cf2 => self.is_cf2(set, self.all()),
+            ReferenceSemantics::ResolutionGrounded => self.resolution_grounded().contains(&set),
+            ReferenceSemantics::StronglyAdmissible => {
+                // Every member is derived from members derived before
+                let mut derived = 0;
+                loop {
+                    let next = set & self.defended_by(derived);
+                    if next == derived {
+                        break derived == set;
+                    }
+                    derived = next;
+                }
+            }
+            ReferenceSemantics::Preferred => {
+                let admissible = |set| self.is_extension(set, ReferenceSemantics::Admissible);
+                // Visit the strict supersets until an admissible one is found
+                let outside = self.all() & !set;
+                let mut added = outside;
+                admissible(set)
+                    && loop {
+                        if added == 0 {
+                            break true;
+                        }
+                        if admissible(set | added) {
+                            break false;
+                        }
+                        added = (added - 1) & outside;
+                    }
+            }
+        }
+    }
+    /// Arguments of `within` connected to `arg` in both directions, i.e. its component.
+    fn component(&self, arg: usize, within: u32) -> u32 {
+        let closure = |step: &dyn Fn(u32) -> u32| {
+            let mut reached = 1 << arg;
+            loop {
+                let next = (reached | step(reached)) & within;
+                if next == reached {
+                    return reached;
+                }
+                reached = next;
+            }
+        };
+        let reaching = closure(&|set| {
+            self.members(set)
+                .fold(0, |acc, member| acc | self.attackers[member])
+        });
+        let reached = closure(&|set| self.attacked_by(set));
+        reaching & reached
+    }
+    /// Whether `set` is a CF2 extension of the framework restricted to `within`.
+    ///
+    /// Follows the recursive definition: a single component needs a maximal
+    /// conflict-free set, otherwise every component needs an extension of its part
+    /// not attacked from the other components.
+    fn is_cf2(&self, set: u32, within: u32) -> bool {
+        if set & !within != 0 || !self.is_conflict_free(set) {
+            return false;
+        }
+        let Some(first) = self.members(within).next() else {
+            return true;
+        };
+        if self.component(first, within) == within {
+            return self.members(within & !set).all(|arg| {
+                let arg_set = 1 << arg;
+                !self.is_conflict_free(set | arg_set)
+            });
+        }
+        let mut remaining = within;
+        while let Some(arg) = self.members(remaining).next() {
+            let component = self.component(arg, within);
+            remaining &= !component;
+            let undefeated = component & !self.attacked_by(set & !component);
+            if !self.is_cf2(set & component, undefeated) {
+                return false;
+            }
+        }
+        true
+    }
+    /// Pairs of distinct arguments attacking each other, smaller index first.
+    fn mutual_attacks(&self) -> Vec<(usize, usize)> {
+        self.members(self.all())
+            .flat_map(|to| {
+                self.members(self.attackers[to])
+                    .filter(move |&from| from < to && self.attackers[from] & 1 << to != 0)
+                    .map(move |from| (from, to))
+            })
+            .collect()
+    }
+
+    /// Fail if the semantics cannot be solved by brute force on this framework.
+    fn supports(&self, semantics: ReferenceSemantics) -> Result<()> {
+        let mutual = self.mutual_attacks().len();
+        if semantics == ReferenceSemantics::ResolutionGrounded && mutual > MAX_ARGUMENTS {
+            return Err(Error::Logic(format!(
+                "The reference solver supports at most {MAX_ARGUMENTS} mutual attacks, got {mutual}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Minimal grounded extensions over all full resolutions.
+    ///
+    /// A full resolution removes one direction of every mutual attack between distinct
+    /// arguments.
+    fn resolution_grounded(&self) -> Vec<u32> {
+        let mutual = self.mutual_attacks();
+        let mut grounded: Vec<u32> = (0..1u64 << mutual.len())
+            .map(|resolution| {
+                let mut attackers = self.attackers.clone();
+                for (bit, &(from, to)) in mutual.iter().enumerate() {
+                    if resolution & 1 << bit == 0 {
+                        attackers[to] &= !(1 << from);
+                    } else {
+                        attackers[from] &= !(1 << to);
+                    }
+                }
+                Bitmasks { attackers }.grounded()
+            })
+            .collect();
+        grounded.sort_unstable();
+        grounded.dedup();
+        grounded
+            .iter()
+            .copied()
+            .filter(|&set| {
+                !grounded
+                    .iter()
+                    .any(|&other| other != set && other & set == other)
+            })
+            .collect()
+    }
+    /// Least fixpoint of the characteristic function.
+    fn grounded(&self) -> u32 {
+        let mut set = 0;
+        loop {
+            let next = self.defended_by(set);
+            if next == set {
+                return set;
+            }
+            set = next;
+        }
+    }
+}
+
+/// All extensions of the framework under the given semantics, in no particular order.
+///
+/// Attacks from or to arguments that are not part of the framework are ignored.
+/// Fails for frameworks with more than [`MAX_ARGUMENTS`] arguments.
+pub fn extensions(
+    arguments: &[symbols::Argument],
+    attacks: &[symbols::Attack],
+    semantics: ReferenceSemantics,
+) -> Result<Vec<Extension>> {
+    let masks = Bitmasks::new(arguments, attacks)?;
+    masks.supports(semantics)?;
+    let candidates: Vec<u32> = match semantics {
+        ReferenceSemantics::Grounded => vec![masks.grounded()],
+        ReferenceSemantics::ResolutionGrounded => masks.resolution_grounded(),
+        ReferenceSemantics::Preferred => {
+            let admissible: Vec<u32> = (0..=masks.all())
+                .filter(|&set| masks.is_extension(set, ReferenceSemantics::Admissible))
+                .collect();
+            admissible
+                .iter()
+                .copied()
+                .filter(|&set| {
+                    !admissible
+                        .iter()
+                        .any(|&other| other != set && other & set == set)
+                })
+                .collect()
+        }
+        _ => (0..=masks.all())
+            .filter(|&set| masks.is_extension(set, semantics))
+            .collect(),
+    };
+    Ok(candidates
+        .into_iter()
+        .map(|set| {
+            masks
+                .members(set)
+                .map(|arg| arguments[arg].id.clone())
+                .collect()
+        })
+        .collect())
+}
+
+/// Whether the given extension is an extension of the framework under the semantics.
+///
+/// Arguments of the extension that are not part of the framework make it invalid.
+pub fn is_extension(
+    arguments: &[symbols::Argument],
+    attacks: &[symbols::Attack],
+    extension: &Extension,
+    semantics: ReferenceSemantics,
+) -> Result<bool> {
+    let masks = Bitmasks::new(arguments, attacks)?;
+    masks.supports(semantics)?;
+    let mut set = 0;
+    let mut found = 0;
+    for (idx, arg) in arguments.iter().enumerate() {
+        if extension.contains(arg) {
+            set |= 1 << idx;
+            found += 1;
+        }
+    }
+    Ok(found == extension.len() && masks.is_extension(set, semantics))
+}
+
+/// All extensions of the initial framework in APX or TGF format.
+///
+/// Optional arguments and attacks are disabled, like in a freshly loaded
+/// [`ArgumentationFramework`](super::ArgumentationFramework).
+pub fn solve(input: &str, semantics: ReferenceSemantics) -> Result<Vec<Extension>> {
+    let (mut arguments, mut attacks) = parse_initial_file(input)?;
+    arguments.retain(|arg| !arg.optional);
+    attacks.retain(|attack| !attack.optional);
+    extensions(&arguments, &attacks, semantics)
+}
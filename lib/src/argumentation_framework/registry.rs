@@ -0,0 +1,219 @@
+//! Selecting the semantics at runtime
+//!
+//! [`ArgumentationFramework`] is generic over its semantics. Where the semantics is only
+//! known at runtime, e.g. from a command line argument, a [`SemanticsId`] creates the
+//! framework behind a [`DynFramework`] instead.
+//!
+//! # Example
+//! ```
+//! use lib::argumentation_framework::registry::SemanticsId;
+//!
+//! let semantics: SemanticsId = "preferred".parse().unwrap();
+//! let mut af = semantics
+//!     .framework("arg(a). arg(b). att(a,b). att(b,a).", Default::default())
+//!     .unwrap();
+//! assert_eq!(af.count_extensions().unwrap(), 2);
+//! ```
+use std::{fmt, str::FromStr};
+
+use fallible_iterator::FallibleIterator;
+
+use super::{
+    semantics::ArgumentationFrameworkSemantic, symbols, ArgumentationFramework, Extension,
+    FrameworkConfig,
+};
+use crate::{semantics, Error, Framework, Objective, Result};
+
+/// Semantics selectable at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SemanticsId {
+    Admissible,
+    Cf2,
+    Complete,
+    ConflictFree,
+    Custom,
+    Grounded,
+    Preferred,
+    ResolutionGrounded,
+    Stable,
+    StronglyAdmissible,
+}
+
+impl SemanticsId {
+    /// All semantics, ordered by name.
+    pub const ALL: [SemanticsId; 10] = [
+        SemanticsId::Admissible,
+        SemanticsId::Cf2,
+        SemanticsId::Complete,
+        SemanticsId::ConflictFree,
+        SemanticsId::Custom,
+        SemanticsId::Grounded,
+        SemanticsId::Preferred,
+        SemanticsId::ResolutionGrounded,
+        SemanticsId::Stable,
+        SemanticsId::StronglyAdmissible,
+    ];
+
+    /// Name of the semantics in kebab case, i.e. `strongly-admissible`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            SemanticsId::Admissible => "admissible",
+            SemanticsId::Cf2 => "cf2",
+            SemanticsId::Complete => "complete",
+            SemanticsId::ConflictFree => "conflict-free",
+            SemanticsId::Custom => "custom",
+            SemanticsId::Grounded => "grounded",
+            SemanticsId::Preferred => "preferred",
+            SemanticsId::ResolutionGrounded => "resolution-grounded",
+            SemanticsId::Stable => "stable",
+            SemanticsId::StronglyAdmissible => "strongly-admissible",
+        }
+    }
+
+    /// Abbreviation used by ICCMA task names, if the semantics is part of them.
+    pub const fn abbreviation(self) -> Option<&'static str> {
+        match self {
+            SemanticsId::Admissible => Some("AD"),
+            SemanticsId::Complete => Some("CO"),
+            SemanticsId::Grounded => Some("GR"),
+            SemanticsId::Preferred => Some("PR"),
+            SemanticsId::Stable => Some("ST"),
+            _ => None,
+        }
+    }
+
+    /// Create a framework under this semantics from the initial file.
+    pub fn framework(self, input: &str, config: FrameworkConfig) -> Result<Box<dyn DynFramework>> {
+        fn boxed<S: ArgumentationFrameworkSemantic + 'static>(
+            input: &str,
+            config: FrameworkConfig,
+        ) -> Result<Box<dyn DynFramework>> {
+            Ok(Box::new(ArgumentationFramework::<S>::new_with_config(
+                input, config,
+            )?))
+        }
+        match self {
+            SemanticsId::Admissible => boxed::<semantics::Admissible>(input, config),
+            SemanticsId::Cf2 => boxed::<semantics::Cf2>(input, config),
+            SemanticsId::Complete => boxed::<semantics::Complete>(input, config),
+            SemanticsId::ConflictFree => boxed::<semantics::ConflictFree>(input, config),
+            SemanticsId::Custom => boxed::<semantics::Custom>(input, config),
+            SemanticsId::Grounded => boxed::<semantics::Ground>(input, config),
+            SemanticsId::Preferred => boxed::<semantics::Preferred>(input, config),
+            SemanticsId::ResolutionGrounded => {
+                boxed::<semantics::ResolutionGrounded>(input, config)
+            }
+            SemanticsId::Stable => boxed::<semantics::Stable>(input, config),
+            SemanticsId::StronglyAdmissible => {
+                boxed::<semantics::StronglyAdmissible>(input, config)
+            }
+        }
+    }
+}
+
+impl fmt::Display for SemanticsId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for SemanticsId {
+    type Err = Error;
+
+    /// Parse the name or ICCMA abbreviation of a semantics, ignoring case.
+    fn from_str(s: &str) -> Result<Self> {
+        SemanticsId::ALL
+            .into_iter()
+            .find(|id| {
+                id.name().eq_ignore_ascii_case(s)
+                    || id
+                        .abbreviation()
+                        .is_some_and(|abbreviation| abbreviation.eq_ignore_ascii_case(s))
+            })
+            .ok_or_else(|| {
+                let names: Vec<_> = SemanticsId::ALL.iter().map(|id| id.name()).collect();
+                Error::Logic(format!(
+                    "Unknown semantics {s:?}, expected one of {}",
+                    names.join(", ")
+                ))
+            })
+    }
+}
+
+/// Framework whose semantics is chosen at runtime, see [`SemanticsId::framework`].
+pub trait DynFramework {
+    /// Apply the update line, see [`Framework::update`].
+    fn update(&mut self, update_line: &str) -> Result;
+    /// All extensions of the current framework.
+    fn extensions(&mut self) -> Result<Vec<Extension>>;
+    /// Number of extensions of the current framework.
+    fn count_extensions(&mut self) -> Result<usize>;
+    /// Whether the current framework has any extension.
+    fn has_extension(&mut self) -> Result<bool>;
+    /// Any extension of the current framework.
+    fn sample_extension(&mut self) -> Result<Option<Extension>>;
+    /// An extension of optimal size, see [`Framework::optimal_extension`].
+    fn optimal_extension(&mut self, objective: Objective) -> Result<Option<Extension>>;
+    /// Arguments contained in some extension, see [`Framework::credulously_accepted_arguments`].
+    fn credulously_accepted_arguments(&mut self) -> Result<Option<Extension>>;
+    /// Arguments contained in every extension, see [`Framework::skeptically_accepted_arguments`].
+    fn skeptically_accepted_arguments(&mut self) -> Result<Option<Extension>>;
+    /// Whether some extension contains the argument.
+    fn is_credulous_accepted(&mut self, arg: &symbols::Argument) -> Result<bool>;
+    /// Whether all extensions contain the argument.
+    fn is_skeptical_accepted(&mut self, arg: &symbols::Argument) -> Result<bool>;
+    /// All arguments of the framework, whether optional or not.
+    fn arguments(&self) -> &[symbols::Argument];
+    /// All attacks of the framework, whether optional or not.
+    fn attacks(&self) -> &[symbols::Attack];
+}
+
+impl<S: ArgumentationFrameworkSemantic> DynFramework for ArgumentationFramework<S> {
+    fn update(&mut self, update_line: &str) -> Result {
+        Framework::update(self, update_line)
+    }
+
+    fn extensions(&mut self) -> Result<Vec<Extension>> {
+        Framework::enumerate_extensions(self)?.by_ref().collect()
+    }
+
+    fn count_extensions(&mut self) -> Result<usize> {
+        Framework::count_extensions(self)
+    }
+
+    fn has_extension(&mut self) -> Result<bool> {
+        Framework::has_extension(self)
+    }
+
+    fn sample_extension(&mut self) -> Result<Option<Extension>> {
+        Framework::sample_extension(self)
+    }
+
+    fn optimal_extension(&mut self, objective: Objective) -> Result<Option<Extension>> {
+        Framework::optimal_extension(self, objective)
+    }
+
+    fn credulously_accepted_arguments(&mut self) -> Result<Option<Extension>> {
+        Framework::credulously_accepted_arguments(self)
+    }
+
+    fn skeptically_accepted_arguments(&mut self) -> Result<Option<Extension>> {
+        Framework::skeptically_accepted_arguments(self)
+    }
+
+    fn is_credulous_accepted(&mut self, arg: &symbols::Argument) -> Result<bool> {
+        Framework::is_credulous_accepted(self, arg)
+    }
+
+    fn is_skeptical_accepted(&mut self, arg: &symbols::Argument) -> Result<bool> {
+        Framework::is_skeptical_accepted(self, arg)
+    }
+
+    fn arguments(&self) -> &[symbols::Argument] {
+        ArgumentationFramework::arguments(self)
+    }
+
+    fn attacks(&self) -> &[symbols::Attack] {
+        ArgumentationFramework::attacks(self)
+    }
+}
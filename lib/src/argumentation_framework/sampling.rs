@@ -0,0 +1,112 @@
+//! Near-uniform sampling of extensions with random XOR constraints
+//!
+//! Follows UniGen by Chakraborty, Meel and Vardi, reusing the constraints of
+//! [`approximate`](super::approximate). Random XOR constraints over the arguments
+//! partition the extensions into cells of roughly equal size. Constraints are added
+//! until a cell holds at most [`MAX_CELL`] extensions. Like UniGen, cells with fewer than
+//! [`MIN_CELL`] extensions are rejected, any other cell is drawn from uniformly. Every
+//! extension is thus drawn with the same probability up to a factor of
+//! `MAX_CELL / MIN_CELL`, unlike the first model of a solve, which depends on the
+//! heuristic of the solver.
+use std::{num::NonZeroUsize, sync::Arc};
+
+use ::clingo::SolveMode;
+
+use super::{
+    approximate::{self, Xor, XorShift},
+    interner::Interner,
+    profile::Profile,
+    semantics::ArgumentationFrameworkSemantic,
+    symbols, Extension, FrameworkConfig,
+};
+use crate::{Error, Result};
+
+/// Largest cell to draw from.
+///
+/// Larger cells bring the samples closer to uniform, but every sample enumerates up to
+/// this many extensions.
+pub const MAX_CELL: usize = 64;
+
+/// Smallest cell to draw from, smaller cells are rejected.
+///
+/// Bounds the bias towards extensions in small cells.
+pub const MIN_CELL: usize = MAX_CELL / 2;
+
+/// Number of rejected cells after which sampling fails.
+const MAX_REJECTIONS: usize = 64;
+
+/// Draw an extension of the given framework, `None` if there is none.
+///
+/// `rng` returns uniformly distributed random numbers. Only correct if every model of
+/// the program of the semantics is an extension, i.e. without
+/// [`ArgumentationFrameworkSemantic::EXTREMUM`]. Fails if no cell of the right size is
+/// found within [`MAX_REJECTIONS`] tries.
+pub(super) fn sample<S: ArgumentationFrameworkSemantic>(
+    arguments: &[symbols::Argument],
+    attacks: &[symbols::Attack],
+    config: &FrameworkConfig,
+    interner: &Arc<Interner>,
+    profile: &mut Profile,
+    rng: &mut impl FnMut() -> u64,
+) -> Result<Option<Extension>> {
+    let config = FrameworkConfig {
+        // Larger cells are split further, so enumerating beyond is never needed
+        models: NonZeroUsize::new(MAX_CELL + 1),
+        random_seed: None,
+        ..config.clone()
+    };
+    let mut cell = |xors: &[Xor]| -> Result<Vec<Extension>> {
+        let ctl = approximate::cell::<S>(arguments, attacks, &config, profile, xors)?;
+        let mut handle = ctl.solve(SolveMode::YIELD, &[])?;
+        let mut extensions = vec![];
+        loop {
+            handle.resume()?;
+            match handle.model()? {
+                Some(model) => extensions.push(Extension::from_model(model, interner)?),
+                None => break,
+            }
+        }
+        handle.close()?;
+        Ok(extensions)
+    };
+    let all = cell(&[])?;
+    if all.len() <= MAX_CELL {
+        return Ok(pick(all, rng));
+    }
+    let mut hash_rng = XorShift::new(rng());
+    let mut xors = 1;
+    let mut rejected = 0;
+    while rejected < MAX_REJECTIONS {
+        let hash: Vec<_> = (0..xors)
+            .map(|_| Xor::random(arguments.len(), &mut hash_rng))
+            .collect();
+        let extensions = cell(&hash)?;
+        log::trace!("{} extensions in one of 2^{xors} cells", extensions.len());
+        match extensions.len() {
+            len if len > MAX_CELL && xors < arguments.len() => xors += 1,
+            len if (MIN_CELL..=MAX_CELL).contains(&len) => return Ok(pick(extensions, rng)),
+            len => {
+                // Too many constraints, or an unlucky hash
+                rejected += 1;
+                if len < MIN_CELL {
+                    xors = (xors - 1).max(1);
+                }
+            }
+        }
+    }
+    Err(Error::Logic(format!(
+        "No cell with {MIN_CELL} to {MAX_CELL} extensions found in {MAX_REJECTIONS} tries"
+    )))
+}
+
+/// Draw one of the extensions uniformly, `None` if there is none.
+pub(super) fn pick(
+    mut extensions: Vec<Extension>,
+    rng: &mut impl FnMut() -> u64,
+) -> Option<Extension> {
+    if extensions.is_empty() {
+        return None;
+    }
+    let idx = rng() % extensions.len() as u64;
+    Some(extensions.swap_remove(idx as usize))
+}
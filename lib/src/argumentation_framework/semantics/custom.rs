@@ -0,0 +1,124 @@
+//! Semantics given by a program loaded at runtime
+//!
+//! The program of [`Custom`](crate::semantics::Custom) is loaded once per process,
+//! before creating the first framework under it. It is checked to use the predicates
+//! the solver relies on, see [`validate`].
+use std::{path::Path, sync::OnceLock};
+
+use crate::{Error, Result};
+
+/// The loaded program
+static PROGRAM: OnceLock<String> = OnceLock::new();
+
+/// Predicates every program needs to use, by name and arity
+const REQUIRED: [(&str, usize); 3] = [("argument", 1), ("attack", 2), ("in", 1)];
+
+/// Program of the custom semantics, empty until one is loaded.
+pub(super) fn program() -> &'static str {
+    PROGRAM.get().map_or("", String::as_str)
+}
+
+/// Load the program of the custom semantics from a file, see [`load`].
+pub fn load_file(path: impl AsRef<Path>) -> Result {
+    let program = std::fs::read_to_string(path)?;
+    load(program)
+}
+
+/// Validate and load the program of the custom semantics.
+///
+/// Fails if another program was loaded before.
+pub fn load(program: String) -> Result {
+    validate(&program)?;
+    let loaded = PROGRAM.get_or_init(|| program.clone());
+    if *loaded != program {
+        return Err(Error::Logic(String::from(
+            "Another program was loaded for the custom semantics already",
+        )));
+    }
+    Ok(())
+}
+
+/// Check that the program uses `argument/1`, `attack/2` and `in/1`, and none of them
+/// with another arity.
+///
+/// This is a syntactic check only, the program is not grounded.
+pub fn validate(program: &str) -> Result {
+    let program = strip_comments(program);
+    for (name, arity) in REQUIRED {
+        let arities = arities(&program, name);
+        if let Some(other) = arities.iter().find(|&&other| other != arity) {
+            return Err(Error::Logic(format!(
+                "The program uses {name}/{other}, expected {name}/{arity}"
+            )));
+        }
+        if arities.is_empty() {
+            return Err(Error::Logic(format!(
+                "The program never uses {name}/{arity}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Remove line comments `% ...` and block comments `%* ... *%`.
+fn strip_comments(program: &str) -> String {
+    let mut stripped = String::with_capacity(program.len());
+    let mut rest = program;
+    while let Some(start) = rest.find('%') {
+        stripped += &rest[..start];
+        let comment = &rest[start..];
+        let end = if comment.starts_with("%*") {
+            comment.find("*%").map_or(comment.len(), |end| end + 2)
+        } else {
+            comment.find('\n').unwrap_or(comment.len())
+        };
+        // Keep the separation of the surrounding tokens
+        stripped.push(' ');
+        rest = &comment[end..];
+    }
+    stripped += rest;
+    stripped
+}
+
+/// Arities of all occurrences of the predicate, as atom `name(...)` or signature `name/N`.
+fn arities(program: &str, name: &str) -> Vec<usize> {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '\'';
+    let mut arities = vec![];
+    for (start, _) in program.match_indices(name) {
+        let before = program[..start].chars().next_back();
+        if before.is_some_and(|c| is_ident(c) || c == '#') {
+            continue;
+        }
+        let after = program[start + name.len()..].trim_start();
+        if after.starts_with(is_ident) {
+            continue;
+        }
+        if let Some(args) = after.strip_prefix('(') {
+            arities.push(count_arguments(args));
+        } else if let Some(signature) = after.strip_prefix('/') {
+            let digits: String = signature.chars().take_while(char::is_ascii_digit).collect();
+            if let Ok(arity) = digits.parse() {
+                arities.push(arity);
+            }
+        } else {
+            arities.push(0);
+        }
+    }
+    arities
+}
+
+/// Number of arguments up to the closing parenthesis, ignoring nested terms.
+fn count_arguments(args: &str) -> usize {
+    let mut depth = 0;
+    let mut count = 1;
+    for c in args.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => break,
+            ')' => depth -= 1,
+            ',' if depth == 0 => count += 1,
+            _ => {}
+        }
+    }
+    count
+}
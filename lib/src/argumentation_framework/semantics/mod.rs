@@ -1,7 +1,10 @@
 //! Semantics supported by the argumentation framework solver
 
+use super::{decomposition::Decomposition, extremal::Extremum};
 use crate::semantics::Semantics;
 
+pub mod custom;
+
 /// A semantics for Dung's Argumentation Frameworks.
 ///
 /// # Predicates
@@ -19,18 +22,29 @@ pub trait ArgumentationFrameworkSemantic: Semantics {
     ///
     /// `#program base.`
     const BASE: &'static str;
-}
+    /// How to solve the semantics component by component, if it is SCC-recursive
+    ///
+    /// Used if [`FrameworkConfig::decompose`](super::FrameworkConfig::decompose) is set.
+    const DECOMPOSITION: Option<Decomposition> = None;
+    /// Whether this is the grounded semantics
+    ///
+    /// Its unique extension is then maintained incrementally under updates,
+    /// see [`GroundedLabelling`](super::grounded::GroundedLabelling), instead of solving.
+    const GROUNDED: bool = false;
+    /// Whether only the extrema of the models of [`BASE`](Self::BASE) are extensions
+    ///
+    /// These are enumerated with the domain heuristic of the [`Extremum`], replacing the
+    /// configured one, see [`extremal`](super::extremal).
+    const EXTREMUM: Option<Extremum> = None;
 
-macro_rules! impl_program {
-    ($name:path, $path:literal) => {
-        impl ArgumentationFrameworkSemantic for $name {
-            const BASE: &'static str = r#""#;
-        }
-    };
+    /// The program to solve, [`BASE`](Self::BASE) unless it is only known at runtime
+    fn base() -> &'static str {
+        Self::BASE
+    }
 }
 
-impl ArgumentationFrameworkSemantic for crate::semantics::Admissible {
-    const BASE: &'static str = r#"
+/// Admissible sets, shared by admissible and preferred semantics
+const ADMISSIBLE: &str = r#"
         %% Guess a set S \subseteq A
         in(X) :- not out(X), argument(X).
         out(X) :- not in(X), argument(X).
@@ -41,12 +55,20 @@ impl ArgumentationFrameworkSemantic for crate::semantics::Admissible {
         %% The argument x is defeated by the set S
         defeated(X) :- in(Y), attack(Y,X).
 
-        %% The argument x is not defended by S
-        not_defended(X) :- attack(Y,X), not defeated(Y).
+        %% The argument x is not defended by S, disabled attackers are ignored
+        not_defended(X) :- attack(Y,X), argument(Y), not defeated(Y).
 
         %% All arguments x \in S need to be defended by S
         :- in(X), not_defended(X).
     "#;
+
+impl ArgumentationFrameworkSemantic for crate::semantics::Admissible {
+    const BASE: &'static str = ADMISSIBLE;
+}
+
+impl ArgumentationFrameworkSemantic for crate::semantics::Preferred {
+    const BASE: &'static str = ADMISSIBLE;
+    const EXTREMUM: Option<Extremum> = Some(Extremum::Maximal);
 }
 
 impl ArgumentationFrameworkSemantic for crate::semantics::ConflictFree {
@@ -60,6 +82,40 @@ impl ArgumentationFrameworkSemantic for crate::semantics::ConflictFree {
     "#;
 }
 
-impl_program!(crate::semantics::Complete, "./complete.dl");
-impl_program!(crate::semantics::Stable, "./stable.dl");
-impl_program!(crate::semantics::Ground, "./ground.dl");
+impl ArgumentationFrameworkSemantic for crate::semantics::Complete {
+    const BASE: &'static str = include_str!("./complete.dl");
+    const DECOMPOSITION: Option<Decomposition> = Some(Decomposition::Complete);
+}
+
+impl ArgumentationFrameworkSemantic for crate::semantics::Stable {
+    const BASE: &'static str = include_str!("./stable.dl");
+    const DECOMPOSITION: Option<Decomposition> = Some(Decomposition::Stable);
+}
+
+impl ArgumentationFrameworkSemantic for crate::semantics::Ground {
+    const BASE: &'static str = include_str!("./ground.dl");
+    const DECOMPOSITION: Option<Decomposition> = Some(Decomposition::Grounded);
+    const GROUNDED: bool = true;
+}
+
+impl ArgumentationFrameworkSemantic for crate::semantics::Cf2 {
+    const BASE: &'static str = include_str!("./cf2.dl");
+    const DECOMPOSITION: Option<Decomposition> = Some(Decomposition::Cf2);
+}
+
+impl ArgumentationFrameworkSemantic for crate::semantics::Custom {
+    const BASE: &'static str = r#""#;
+
+    fn base() -> &'static str {
+        custom::program()
+    }
+}
+
+impl ArgumentationFrameworkSemantic for crate::semantics::ResolutionGrounded {
+    const BASE: &'static str = include_str!("./resolution_grounded.dl");
+    const EXTREMUM: Option<Extremum> = Some(Extremum::Minimal);
+}
+
+impl ArgumentationFrameworkSemantic for crate::semantics::StronglyAdmissible {
+    const BASE: &'static str = include_str!("./strongly_admissible.dl");
+}
@@ -0,0 +1,31 @@
+//! Statistics of the last solve, including the warnings reported by clingo
+use std::fmt;
+
+/// A warning reported by clingo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Kind of the warning, e.g. `AtomUndefined`
+    pub code: String,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.code, self.message)
+    }
+}
+
+/// Statistics of the last solve, see
+/// [`ArgumentationFramework::last_statistics`](super::ArgumentationFramework::last_statistics).
+#[derive(Debug, Clone, Default)]
+pub struct Statistics {
+    /// Number of models enumerated by clingo
+    pub models: usize,
+    /// Warnings reported by clingo since the solve before, including grounding warnings
+    ///
+    /// At most [`MAX_COLLECTED_WARNINGS`] are kept, later ones are dropped.
+    pub warnings: Vec<Diagnostic>,
+}
+
+/// Maximum number of warnings collected between two solves.
+pub const MAX_COLLECTED_WARNINGS: usize = 1000;
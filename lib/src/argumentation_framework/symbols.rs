@@ -1,6 +1,10 @@
-use clingo::{Symbol, ToSymbol};
+use std::{borrow::Cow, fmt};
+
+#[cfg(feature = "clingo")]
+use clingo::{Symbol, SymbolType, ToSymbol};
 
 use super::ArgumentID;
+use crate::{Error, Result};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Argument {
@@ -8,7 +12,7 @@ pub struct Argument {
     pub optional: bool,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Attack {
     pub from: ArgumentID,
     pub to: ArgumentID,
@@ -34,20 +38,125 @@ impl Attack {
     }
 }
 
+impl fmt::Display for Argument {
+    /// Format as APX atom, i.e. `arg(a1)`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "arg({})", self.id)
+    }
+}
+
+impl fmt::Display for Attack {
+    /// Format as APX atom, i.e. `att(a1,a2)`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "att({},{})", self.from, self.to)
+    }
+}
+
+/// The argument with the given id.
+///
+/// Fails if there is none, naming the ids that differ from `id` in at most a third
+/// of their characters, as they were likely meant instead.
+pub fn find<'a>(arguments: &'a [Argument], id: &str) -> Result<&'a Argument> {
+    if let Some(argument) = arguments.iter().find(|argument| argument.id == id) {
+        return Ok(argument);
+    }
+    let max_distance = id.chars().count().div_ceil(3);
+    let mut near_misses: Vec<_> = arguments
+        .iter()
+        .map(|argument| (edit_distance(&argument.id, id), &argument.id))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .collect();
+    near_misses.sort_unstable();
+    let mut message = format!("Unknown argument {id:?}");
+    if !near_misses.is_empty() {
+        let names: Vec<_> = near_misses
+            .iter()
+            .take(3)
+            .map(|(_, id)| format!("{id:?}"))
+            .collect();
+        message += &format!(", did you mean {}?", names.join(" or "));
+    }
+    Err(Error::Logic(message))
+}
+
+/// Levenshtein distance between the two ids, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<_> = b.chars().collect();
+    let mut previous: Vec<_> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Whether clingo reads the id as a constant, i.e. it needs no quoting.
+fn is_clingo_constant(id: &str) -> bool {
+    let mut chars = id.chars();
+    chars.next().is_some_and(|first| first.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && id != "not"
+}
+
+/// The id as clingo term, for use in programs.
+///
+/// Ids that are no clingo constants, e.g. because they contain non-ASCII letters or
+/// dashes, are quoted as strings. Use [`id_symbol`] for the corresponding [`Symbol`].
+pub fn clingo_term(id: &str) -> Cow<'_, str> {
+    if is_clingo_constant(id) {
+        return Cow::Borrowed(id);
+    }
+    let mut term = String::with_capacity(id.len() + 2);
+    term.push('"');
+    for c in id.chars() {
+        match c {
+            '"' => term.push_str("\\\""),
+            '\\' => term.push_str("\\\\"),
+            '\n' => term.push_str("\\n"),
+            c => term.push(c),
+        }
+    }
+    term.push('"');
+    Cow::Owned(term)
+}
+
+#[cfg(feature = "clingo")]
+/// The symbol of the id, matching the term of [`clingo_term`].
+pub fn id_symbol(id: &str) -> Result<Symbol, clingo::ClingoError> {
+    if is_clingo_constant(id) {
+        Symbol::create_id(id, true)
+    } else {
+        Symbol::create_string(id)
+    }
+}
+
+#[cfg(feature = "clingo")]
+/// The id of a symbol created by [`id_symbol`], other symbols are formatted.
+pub fn symbol_id(symbol: Symbol) -> Result<String, clingo::ClingoError> {
+    match symbol.symbol_type()? {
+        SymbolType::Function if symbol.arguments()?.is_empty() => Ok(symbol.name()?.to_owned()),
+        SymbolType::String => Ok(symbol.string()?.to_owned()),
+        _ => Ok(symbol.to_string()),
+    }
+}
+
+#[cfg(feature = "clingo")]
 impl ToSymbol for Argument {
     fn symbol(&self) -> Result<clingo::Symbol, clingo::ClingoError> {
-        Symbol::create_function("argument", &[Symbol::create_id(&self.id, true)?], true)
+        Symbol::create_function("argument", &[id_symbol(&self.id)?], true)
     }
 }
 
+#[cfg(feature = "clingo")]
 impl ToSymbol for Attack {
     fn symbol(&self) -> Result<Symbol, clingo::ClingoError> {
         Symbol::create_function(
             "attack",
-            &[
-                Symbol::create_id(&self.from, true)?,
-                Symbol::create_id(&self.to, true)?,
-            ],
+            &[id_symbol(&self.from)?, id_symbol(&self.to)?],
             true,
         )
     }
@@ -1,8 +1,18 @@
+use std::{
+    collections::BTreeSet,
+    num::{NonZeroU64, NonZeroUsize},
+};
+
 use pretty_assertions::assert_eq;
 
 use crate::{
-    macros::{ext, set},
-    semantics::{Admissible, Complete, ConflictFree, Ground, Stable},
+    framework::ParserError,
+    macros::{arg, att, ext, set},
+    semantics::{
+        Admissible, Cf2, Complete, ConflictFree, Custom, Ground, Preferred, ResolutionGrounded,
+        Stable, StronglyAdmissible,
+    },
+    Objective,
 };
 
 use super::*;
@@ -59,7 +69,6 @@ fn simple_admissible_af() {
     )
 }
 
-#[ignore = "complete is not adjusted yet"]
 #[test]
 fn simple_complete_af() {
     let extensions = extensions::<Complete>(
@@ -75,7 +84,25 @@ fn simple_complete_af() {
     assert_eq!(extensions, set![ext!("1", "2")])
 }
 
-#[ignore = "ground is not adjusted yet"]
+#[test]
+fn complete_af_follows_updates() {
+    let mut af = ArgumentationFramework::<Complete>::new(
+        "arg(a). arg(b). arg(c). att(a,b). att(b,a). att(c,a). opt(arg(c)).",
+    )
+    .expect("Creating AF");
+    assert_eq!(
+        extensions_of(&mut af),
+        set![Extension::EMPTY, ext!("a"), ext!("b")]
+    );
+    af.update("+arg(c).").expect("Updating");
+    assert_eq!(extensions_of(&mut af), set![ext!("b", "c")]);
+    af.update("-arg(c).").expect("Updating");
+    assert_eq!(
+        extensions_of(&mut af),
+        set![Extension::EMPTY, ext!("a"), ext!("b")]
+    );
+}
+
 #[test]
 fn simple_ground_af() {
     let exts = extensions::<Ground>(
@@ -108,7 +135,25 @@ fn simple_ground_af() {
     assert_eq!(exts, set![Extension::EMPTY]);
 }
 
-#[ignore = "stable is not adjusted yet"]
+#[test]
+fn ground_af_follows_patches() {
+    let mut af = ArgumentationFramework::<Ground>::new(
+        "arg(a). arg(b). arg(c). att(a,b). att(b,c). att(c,a). opt(att(c,a)).",
+    )
+    .expect("Creating AF");
+    assert_eq!(extensions_of(&mut af), set![ext!("a", "c")]);
+    af.apply_patch(&Patch::EnableAttack(att!("c", "a")))
+        .expect("Applying patch");
+    assert_eq!(extensions_of(&mut af), set![Extension::EMPTY]);
+    // The encoding agrees with the maintained labelling
+    let diagnoses = af.explain_rejection(&arg!("a")).expect("Explaining");
+    assert_eq!(diagnoses.len(), 1);
+    assert_eq!(diagnoses[0].update_line().as_deref(), Some("-att(c,a)."));
+    af.apply_patch(&Patch::DisableAttack(att!("c", "a")))
+        .expect("Applying patch");
+    assert_eq!(extensions_of(&mut af), set![ext!("a", "c")]);
+}
+
 #[test]
 fn simple_stable_af() {
     let exts = extensions::<Stable>(
@@ -454,3 +499,1420 @@ fn simple_conflict_free_af_with_general_updates() {
         set![ext!(), ext!("b"), ext!("c"), ext!("b", "c")]
     )
 }
+
+#[test]
+fn model_limit_restricts_enumeration() {
+    let config = FrameworkConfig {
+        models: NonZeroUsize::new(2),
+        ..Default::default()
+    };
+    let mut af =
+        ArgumentationFramework::<Admissible>::new_with_config("arg(a). arg(b). arg(c).", config)
+            .expect("Creating AF");
+    assert_eq!(extensions_of(&mut af).len(), 2);
+}
+
+#[test]
+fn randomized_enumeration_finds_distinct_extensions() {
+    let config = FrameworkConfig {
+        models: NonZeroUsize::new(3),
+        random_seed: Some(42),
+        ..Default::default()
+    };
+    let mut af =
+        ArgumentationFramework::<Admissible>::new_with_config("arg(a). arg(b). arg(c).", config)
+            .expect("Creating AF");
+    assert_eq!(extensions_of(&mut af).len(), 3);
+}
+
+#[test]
+fn patch_batches_are_applied_atomically() {
+    let mut af = ArgumentationFramework::<Admissible>::new(
+        "arg(a). arg(b). att(a,b). opt(att(a,b)). opt(arg(b)).",
+    )
+    .expect("Creating AF");
+    let enable_b = Patch::EnableArgument(arg!("b"));
+    let batch = [enable_b.clone(), Patch::DisableAttack(att!("b", "a"))];
+    assert!(af.apply_patches(&batch).is_err());
+    assert_eq!(extensions_of(&mut af), set![Extension::EMPTY, ext!("a")]);
+    // Later patches override earlier ones
+    let batch = [
+        enable_b,
+        Patch::EnableAttack(att!("a", "b")),
+        Patch::DisableAttack(att!("a", "b")),
+    ];
+    af.apply_patches(&batch).expect("Applying batch");
+    assert_eq!(
+        extensions_of(&mut af),
+        set![Extension::EMPTY, ext!("a"), ext!("b"), ext!("a", "b")]
+    );
+}
+
+#[test]
+fn coalesced_updates_only_keep_the_final_state() {
+    let mut af = ArgumentationFramework::<Admissible>::new(
+        "arg(a). arg(b). att(a,b). opt(att(a,b)). opt(arg(b)).",
+    )
+    .expect("Creating AF");
+    for _ in 0..1000 {
+        af.update("+arg(b).").expect("Enabling b");
+        af.update("-arg(b).").expect("Disabling b");
+    }
+    af.update("+att(a,b).").expect("Enabling attack");
+    assert_eq!(extensions_of(&mut af), set![Extension::EMPTY, ext!("a")]);
+    af.update("+arg(b).").expect("Enabling b");
+    assert_eq!(extensions_of(&mut af), set![Extension::EMPTY, ext!("a")]);
+    af.update("-att(a,b).").expect("Disabling attack");
+    assert_eq!(
+        extensions_of(&mut af),
+        set![Extension::EMPTY, ext!("a"), ext!("b"), ext!("a", "b")]
+    );
+}
+
+#[test]
+fn arguments_and_attacks_can_be_added_later() {
+    let mut af = ArgumentationFramework::<Admissible>::new("arg(a). arg(b). att(a,b).")
+        .expect("Creating AF");
+    assert_eq!(extensions_of(&mut af), set![Extension::EMPTY, ext!("a")]);
+    af.add(
+        &[arg!("c"), arg!("d" opt)],
+        &[att!("c", "a"), att!("d", "c" opt)],
+    )
+    .expect("Adding arguments");
+    assert_eq!(
+        extensions_of(&mut af),
+        set![Extension::EMPTY, ext!("c"), ext!("b", "c")]
+    );
+    af.update("+arg(d).").expect("Enabling d");
+    af.update("+att(d,c).").expect("Enabling attack");
+    assert_eq!(
+        extensions_of(&mut af),
+        set![Extension::EMPTY, ext!("d"), ext!("a", "d")]
+    );
+    assert!(af.add(&[arg!("a")], &[]).is_err());
+    assert_eq!(af.arguments().len(), 4);
+    assert_eq!(af.attacks().len(), 3);
+}
+
+#[test]
+fn counting_matches_enumeration() {
+    let mut af = ArgumentationFramework::<Admissible>::new(
+        "arg(a). arg(b). arg(c). att(a,b). att(b,c). opt(att(a,b)). opt(arg(c)).",
+    )
+    .expect("Creating AF");
+    for update in ["+arg(c).", "+att(a,b).", "-arg(c)."] {
+        let count = af.count_extensions().expect("Counting extensions");
+        assert_eq!(count, extensions_of(&mut af).len());
+        af.update(update).expect("Updating AF");
+    }
+}
+
+#[test]
+fn heuristics_do_not_change_extensions() {
+    const PROGRAM: &str = "arg(a). arg(b). arg(c). arg(d). att(a,b). att(b,a). att(c,d).";
+    let expected = extensions::<Admissible>(PROGRAM);
+    for heuristic in [Heuristic::PreferOut, Heuristic::AttackDegree] {
+        let config = FrameworkConfig {
+            heuristic,
+            ..Default::default()
+        };
+        let mut af = ArgumentationFramework::<Admissible>::new_with_config(PROGRAM, config)
+            .expect("Creating AF");
+        assert_eq!(extensions_of(&mut af), expected);
+    }
+}
+
+#[test]
+fn strongly_connected_components_are_ordered() {
+    // Edges into finished components do not merge components
+    assert_eq!(
+        decomposition::strongly_connected_components(&[vec![1], vec![0], vec![0, 3], vec![2]]),
+        vec![vec![1, 0], vec![3, 2]]
+    );
+}
+
+#[test]
+fn decomposition_solves_scc_recursive_semantics() {
+    fn decomposed<S: ArgumentationFrameworkSemantic>(program: &str) -> BTreeSet<Extension> {
+        let config = FrameworkConfig {
+            decompose: true,
+            ..Default::default()
+        };
+        let mut af =
+            ArgumentationFramework::<S>::new_with_config(program, config).expect("Creating AF");
+        extensions_of(&mut af)
+    }
+    const DEFENDED: &str = "arg(a). arg(b). arg(c). att(a, c). att(b, c). att(c, b).";
+    const CYCLE: &str = "arg(a). arg(b). arg(c). att(a, b). att(b, a). att(b, c).";
+    const ODD: &str = "arg(a). arg(b). arg(c). arg(d). att(a, b). att(b, c). att(c, a). att(c, d).";
+    assert_eq!(decomposed::<Complete>(DEFENDED), set![ext!("a", "b")]);
+    assert_eq!(decomposed::<Ground>(DEFENDED), set![ext!("a", "b")]);
+    assert_eq!(decomposed::<Stable>(DEFENDED), set![ext!("a", "b")]);
+    assert_eq!(
+        decomposed::<Complete>(CYCLE),
+        set![Extension::EMPTY, ext!("a", "c"), ext!("b")]
+    );
+    assert_eq!(decomposed::<Ground>(CYCLE), set![Extension::EMPTY]);
+    assert_eq!(decomposed::<Stable>(CYCLE), set![ext!("a", "c"), ext!("b")]);
+    assert_eq!(decomposed::<Complete>(ODD), set![Extension::EMPTY]);
+    assert_eq!(decomposed::<Stable>(ODD), set![]);
+    assert_eq!(decomposed::<Stable>("arg(a). att(a, a)."), set![]);
+    assert_eq!(decomposed::<Ground>(""), set![Extension::EMPTY]);
+}
+
+#[test]
+fn decomposition_respects_updates() {
+    let config = FrameworkConfig {
+        decompose: true,
+        ..Default::default()
+    };
+    let mut af = ArgumentationFramework::<Complete>::new_with_config(
+        "arg(a). arg(b). arg(c). att(a, b). att(b, a). att(c, a). opt(arg(c)).",
+        config,
+    )
+    .expect("Creating AF");
+    assert_eq!(
+        extensions_of(&mut af),
+        set![Extension::EMPTY, ext!("a"), ext!("b")]
+    );
+    assert_eq!(af.count_extensions().expect("Counting extensions"), 3);
+    af.update("+arg(c).").expect("Updating AF");
+    assert_eq!(extensions_of(&mut af), set![ext!("b", "c")]);
+    assert_eq!(af.count_extensions().expect("Counting extensions"), 1);
+}
+
+#[test]
+fn grounded_labelling_is_maintained_under_updates() {
+    use decomposition::Label;
+    let mut af = ArgumentationFramework::<Ground>::new(
+        "arg(a). arg(b). arg(c). arg(d). att(a, b). att(b, c). att(c, d). att(d, c). opt(arg(a)).",
+    )
+    .expect("Creating AF");
+    let labelling = af.grounded_labelling().expect("Grounded labelling");
+    assert_eq!(labelling.label("a"), None);
+    assert_eq!(labelling.label("b"), Some(Label::In));
+    assert_eq!(labelling.label("c"), Some(Label::Out));
+    assert_eq!(labelling.label("d"), Some(Label::In));
+    af.update("+arg(a).").expect("Updating AF");
+    let labelling = af.grounded_labelling().expect("Grounded labelling");
+    assert_eq!(labelling.label("b"), Some(Label::Out));
+    assert_eq!(labelling.label("c"), Some(Label::Undec));
+    assert_eq!(labelling.label("d"), Some(Label::Undec));
+    assert_eq!(extensions_of(&mut af), set![ext!("a")]);
+    af.update("-arg(a).").expect("Updating AF");
+    assert_eq!(extensions_of(&mut af), set![ext!("b", "d")]);
+    assert_eq!(af.count_extensions().expect("Counting extensions"), 1);
+    af.add(&[arg!("e")], &[att!("e", "b")])
+        .expect("Adding arguments");
+    assert_eq!(extensions_of(&mut af), set![ext!("e")]);
+    assert!(ArgumentationFramework::<Complete>::new("arg(a).")
+        .expect("Creating AF")
+        .grounded_labelling()
+        .is_none());
+}
+
+#[test]
+fn cached_results_follow_the_framework_state() {
+    let config = FrameworkConfig {
+        cache: true,
+        ..Default::default()
+    };
+    let mut af = ArgumentationFramework::<Admissible>::new_with_config(
+        "arg(a). arg(b). att(b, a). opt(att(b, a)).",
+        config,
+    )
+    .expect("Creating AF");
+    let unattacked = set![Extension::EMPTY, ext!("a"), ext!("b"), ext!("a", "b")];
+    let attacked = set![Extension::EMPTY, ext!("b")];
+    assert_eq!(extensions_of(&mut af), unattacked);
+    af.update("+att(b, a).").expect("Updating AF");
+    assert_eq!(af.count_extensions().expect("Counting extensions"), 2);
+    assert_eq!(extensions_of(&mut af), attacked);
+    af.update("-att(b, a).").expect("Updating AF");
+    assert_eq!(af.count_extensions().expect("Counting extensions"), 4);
+    assert_eq!(extensions_of(&mut af), unattacked);
+    af.update("+att(b, a).").expect("Updating AF");
+    assert_eq!(extensions_of(&mut af), attacked);
+    af.add(&[arg!("c")], &[]).expect("Adding arguments");
+    assert_eq!(af.count_extensions().expect("Counting extensions"), 4);
+}
+
+#[test]
+fn large_frameworks_are_added_in_chunks() {
+    // Far more facts than fit into a single chunk, only the last argument is acceptable
+    let program: String = (0..5000)
+        .map(|idx| format!("arg(a{idx}). att(a{idx}, a{idx}). "))
+        .chain([String::from("arg(z).")])
+        .collect();
+    assert_eq!(
+        extensions::<Admissible>(&program),
+        set![Extension::EMPTY, ext!("z")]
+    );
+}
+
+#[test]
+fn phases_are_profiled() {
+    use profile::Phase;
+    let mut af =
+        ArgumentationFramework::<Admissible>::new("arg(a). arg(b). att(b, a). opt(att(b, a)).")
+            .expect("Creating AF");
+    extensions_of(&mut af);
+    af.update("+att(b, a).").expect("Updating AF");
+    af.count_extensions().expect("Counting extensions");
+    let profile = af.profile();
+    if cfg!(feature = "profiling") {
+        let phases: Vec<_> = profile
+            .timings()
+            .iter()
+            .map(|timing| timing.phase)
+            .collect();
+        assert_eq!(
+            phases,
+            vec![
+                Phase::Parse,
+                Phase::Facts,
+                Phase::Ground,
+                Phase::Solve,
+                Phase::Patch,
+                Phase::Solve
+            ]
+        );
+        assert_eq!(profile.count(Phase::Solve), 2);
+    } else {
+        assert!(profile.timings().is_empty());
+    }
+    assert_eq!(profile.to_string().lines().count(), Phase::ALL.len() + 1);
+}
+
+#[test]
+fn lost_backend_is_reported_and_recovered() {
+    let mut af =
+        ArgumentationFramework::<Admissible>::new("arg(a). arg(b). att(b, a). opt(att(b, a)).")
+            .expect("Creating AF");
+    af.update("+att(b, a).").expect("Updating AF");
+    // A leaked iterator never returns the control
+    std::mem::forget(af.enumerate_extensions().expect("Enumerating extensions"));
+    assert!(matches!(
+        af.enumerate_extensions().err(),
+        Some(Error::ClingoNotInitialized)
+    ));
+    assert!(matches!(
+        af.count_extensions(),
+        Err(Error::ClingoNotInitialized)
+    ));
+    af.reinitialize().expect("Reinitializing AF");
+    assert_eq!(extensions_of(&mut af), set![Extension::EMPTY, ext!("b")]);
+    af.update("-att(b, a).").expect("Updating AF");
+    assert_eq!(af.count_extensions().expect("Counting extensions"), 4);
+}
+
+#[test]
+fn extension_counts_are_estimated() {
+    // Few extensions are counted exactly
+    let mut af = ArgumentationFramework::<Admissible>::new("arg(a). arg(b). att(a, b).")
+        .expect("Creating AF");
+    let estimate = af.estimate_extension_count(0.8, 0.2).expect("Estimating");
+    assert!(estimate.exact);
+    assert_eq!(estimate.estimate, 2.0);
+    // Every subset of 24 unrelated arguments is conflict-free
+    let program: String = (0..24).map(|idx| format!("arg(a{idx}). ")).collect();
+    let mut af = ArgumentationFramework::<ConflictFree>::new(&program).expect("Creating AF");
+    let estimate = af.estimate_extension_count(0.8, 0.2).expect("Estimating");
+    assert!(!estimate.exact);
+    let count = f64::from(1 << 24);
+    assert!(
+        estimate.lower <= count && count <= estimate.upper,
+        "{count} not within {estimate:?}"
+    );
+    // Pairs of mutual attackers, each pair is either empty or contributes one argument
+    let program: String = (0..12)
+        .map(|idx| format!("arg(a{idx}). arg(b{idx}). att(a{idx}, b{idx}). att(b{idx}, a{idx}). "))
+        .collect();
+    let mut af = ArgumentationFramework::<Admissible>::new(&program).expect("Creating AF");
+    let estimate = af.estimate_extension_count(0.8, 0.2).expect("Estimating");
+    let count = 3f64.powi(12);
+    assert!(
+        estimate.lower <= count && count <= estimate.upper,
+        "{count} not within {estimate:?}"
+    );
+    assert!(af.estimate_extension_count(0.0, 0.2).is_err());
+    // Cells of preferred extensions would only hold the maximal sets within the cell
+    let mut af = ArgumentationFramework::<Preferred>::new(&program).expect("Creating AF");
+    let estimate = af.estimate_extension_count(0.8, 0.2).expect("Estimating");
+    assert!(estimate.exact);
+    let count = af.count_extensions().expect("Counting extensions");
+    assert_eq!(estimate.estimate, count as f64);
+    assert_eq!(count, 1 << 12);
+}
+
+#[test]
+fn truncated_solves_exceed_the_conflict_limit() {
+    // Every a{idx} is attacked from an odd cycle, so choosing it leads to a conflict
+    let program: String = (0..10)
+        .map(|idx| {
+            format!(
+                "arg(a{idx}). arg(b{idx}). arg(c{idx}). arg(d{idx}). arg(e{idx}). \
+                 att(a{idx}, b{idx}). att(b{idx}, a{idx}). att(c{idx}, a{idx}). \
+                 att(c{idx}, d{idx}). att(d{idx}, e{idx}). att(e{idx}, c{idx}). "
+            )
+        })
+        .collect();
+    let config = FrameworkConfig {
+        conflict_limit: NonZeroU64::new(1),
+        ..Default::default()
+    };
+    let mut af = ArgumentationFramework::<Admissible>::new_with_config(&program, config)
+        .expect("Creating AF");
+    assert!(matches!(af.count_extensions(), Err(Error::LimitExceeded)));
+    af.reinitialize().expect("Reinitializing AF");
+    let extensions = af
+        .enumerate_extensions()
+        .expect("Enumerating extensions")
+        .by_ref()
+        .collect::<Vec<_>>();
+    assert!(matches!(extensions, Err(Error::LimitExceeded)));
+    // Without the limit, all 2^10 extensions are found
+    let mut af = ArgumentationFramework::<Admissible>::new(&program).expect("Creating AF");
+    assert_eq!(af.count_extensions().expect("Counting extensions"), 1 << 10);
+}
+
+#[test]
+fn updates_are_streamed_until_an_empty_line() {
+    let mut af =
+        ArgumentationFramework::<Admissible>::new("arg(a). arg(b). att(b, a). opt(att(b, a)).")
+            .expect("Creating AF");
+    let input = "+att(b, a).\n  -att(b, a).  \r\n+att(b, a).\n\n-att(b, a).\n";
+    let mut answers = vec![];
+    af.apply_updates_from(input.as_bytes(), |af, nr, line| {
+        answers.push((nr, line.to_owned(), extensions_of(af).len()));
+        Ok::<_, Error>(())
+    })
+    .expect("Applying updates");
+    assert_eq!(
+        answers,
+        vec![
+            (0, String::from("+att(b, a)."), 2),
+            (1, String::from("-att(b, a)."), 4),
+            (2, String::from("+att(b, a)."), 2),
+        ]
+    );
+    assert!(af
+        .apply_updates_from(&b"-att(a, b).\n"[..], |_, _, _| Ok::<_, Error>(()))
+        .is_err());
+}
+
+#[test]
+fn followed_streams_skip_empty_lines_until_closed() {
+    let input = "+att(b, a).\n\n-att(b, a).\n\n\n+att(b, a).";
+    let lines: Vec<_> = UpdateLines::new(input.as_bytes(), Follow::Stream)
+        .collect()
+        .expect("Reading lines");
+    assert_eq!(lines, ["+att(b, a).", "-att(b, a).", "+att(b, a)."]);
+}
+
+#[test]
+fn parallel_modes_do_not_change_extensions() {
+    const PROGRAM: &str = "arg(a). arg(b). arg(c). arg(d). att(a,b). att(b,a). att(c,d).";
+    let expected = extensions::<Admissible>(PROGRAM);
+    for parallel_mode in [ParallelMode::Compete, ParallelMode::Split] {
+        let config = FrameworkConfig {
+            threads: 2.try_into().unwrap(),
+            parallel_mode,
+            ..Default::default()
+        };
+        let mut af = ArgumentationFramework::<Admissible>::new_with_config(PROGRAM, config)
+            .expect("Creating AF");
+        assert_eq!(extensions_of(&mut af), expected);
+    }
+}
+
+#[test]
+fn warm_starts_do_not_change_extensions_after_updates() {
+    const PROGRAM: &str =
+        "arg(a). arg(b). arg(c). att(a,b). att(b,c). att(c,a). opt(att(c,a)). opt(arg(c)).";
+    let mut cold = ArgumentationFramework::<Admissible>::new(PROGRAM).expect("Creating AF");
+    let config = FrameworkConfig {
+        warm_start: true,
+        ..Default::default()
+    };
+    let mut warm = ArgumentationFramework::<Admissible>::new_with_config(PROGRAM, config)
+        .expect("Creating AF");
+    for update in ["+arg(c).", "+att(c,a).", "-arg(c).", "-att(c,a)."] {
+        cold.update(update).expect("Updating AF");
+        warm.update(update).expect("Updating AF");
+        assert_eq!(extensions_of(&mut warm), extensions_of(&mut cold));
+    }
+}
+
+#[test]
+fn extensions_compare_as_sets_of_arguments() {
+    // Interning order differs from the order of the ids
+    let (b, ab, abc) = (
+        ext!("ext_b"),
+        ext!("ext_a", "ext_b"),
+        ext!("ext_c", "ext_b", "ext_a"),
+    );
+    assert!(b.is_subset(&ab) && ab.is_subset(&abc) && !abc.is_subset(&ab));
+    assert!(Extension::EMPTY.is_subset(&b) && Extension::EMPTY.is_empty());
+    assert_eq!(abc.len(), 3);
+    assert_eq!(abc, ext!("ext_a", "ext_b", "ext_c"));
+    assert!(ab < abc && abc < b);
+    assert_eq!(abc.format(), "[ext_a,ext_b,ext_c]");
+    assert!(abc.contains(&arg!("ext_c")) && !ab.contains(&arg!("ext_c")));
+    assert!(!ab.contains(&arg!("never_interned")));
+}
+
+#[test]
+fn extensions_of_one_framework_compare_by_words() {
+    let mut af = ArgumentationFramework::<Admissible>::new("arg(c). arg(b).").expect("Creating AF");
+    let sorted: Vec<_> = extensions_of(&mut af).into_iter().collect();
+    assert_eq!(sorted, [ext!(), ext!("b"), ext!("b", "c"), ext!("c")]);
+    // Added arguments are interned after the initial ones, out of order
+    af.update("+arg(a).").expect("Adding a");
+    let sorted: Vec<_> = extensions_of(&mut af).into_iter().collect();
+    assert_eq!(
+        sorted,
+        [
+            ext!(),
+            ext!("a"),
+            ext!("a", "b"),
+            ext!("a", "b", "c"),
+            ext!("a", "c"),
+            ext!("b"),
+            ext!("b", "c"),
+            ext!("c")
+        ]
+    );
+}
+
+#[test]
+fn duplicate_extensions_are_skipped_up_to_the_cap() {
+    let mut seen = Fingerprints::new(NonZeroUsize::new(1).unwrap());
+    assert!(seen.insert(&ext!("a")));
+    assert!(!seen.insert(&ext!("a")));
+    // Beyond the cap, extensions are no longer remembered
+    assert!(seen.insert(&ext!("b")));
+    assert!(seen.insert(&ext!("b")));
+}
+
+#[test]
+fn deduplication_replaces_projection() {
+    const PROGRAM: &str = "arg(a). arg(b). arg(c). att(a,b). att(b,a). att(c,c).";
+    let config = FrameworkConfig {
+        clingo_args: vec![String::from("--project=no")],
+        deduplicate: NonZeroUsize::new(100),
+        ..Default::default()
+    };
+    let mut af = ArgumentationFramework::<Admissible>::new_with_config(PROGRAM, config)
+        .expect("Creating AF");
+    assert_eq!(extensions_of(&mut af), extensions::<Admissible>(PROGRAM));
+}
+
+#[test]
+fn clingo_warnings_are_collected_per_solve() {
+    // Without attacks, `attack/2` never occurs in a rule head
+    let mut af = ArgumentationFramework::<Admissible>::new("arg(a).").expect("Creating AF");
+    assert!(af.last_statistics().is_none());
+    assert_eq!(af.count_extensions().expect("Counting extensions"), 2);
+    let statistics = af.last_statistics().expect("Solved by clingo");
+    assert_eq!(statistics.models, 2);
+    assert!(statistics
+        .warnings
+        .iter()
+        .any(|warning| warning.message.contains("attack/2")));
+    // Grounding warnings are only reported once
+    extensions_of(&mut af);
+    let statistics = af.last_statistics().expect("Solved by clingo");
+    assert_eq!((statistics.models, statistics.warnings.len()), (2, 0));
+
+    let config = FrameworkConfig {
+        clingo_log_level: None,
+        ignored_warnings: vec![::clingo::Warning::AtomUndefined],
+        ..Default::default()
+    };
+    let mut af = ArgumentationFramework::<Admissible>::new_with_config("arg(a).", config)
+        .expect("Creating AF");
+    af.count_extensions().expect("Counting extensions");
+    assert!(af.last_statistics().unwrap().warnings.is_empty());
+    // Warnings of solves on separate controls are reported with the next solve
+    let config = FrameworkConfig {
+        cache: false,
+        ..Default::default()
+    };
+    let mut af = ArgumentationFramework::<Admissible>::new_with_config("arg(a).", config)
+        .expect("Creating AF");
+    af.count_extensions().expect("Counting extensions");
+    assert_eq!(
+        af.optimal_extension(Objective::Maximum)
+            .expect("Optimizing"),
+        Some(ext!("a"))
+    );
+    af.count_extensions().expect("Counting extensions");
+    assert!(af
+        .last_statistics()
+        .unwrap()
+        .warnings
+        .iter()
+        .any(|warning| warning.message.contains("attack/2")));
+}
+
+#[test]
+fn extensions_are_visited_until_break() {
+    const PROGRAM: &str = "arg(a). arg(b). arg(c). att(a,b). att(b,a).";
+    let mut af = ArgumentationFramework::<Admissible>::new(PROGRAM).expect("Creating AF");
+    let mut visited = BTreeSet::new();
+    let flow = af
+        .for_each_extension(|ext| {
+            visited.insert(ext.clone());
+            ControlFlow::Continue(())
+        })
+        .expect("Visiting extensions");
+    assert_eq!(flow, ControlFlow::Continue(()));
+    assert_eq!(visited, extensions::<Admissible>(PROGRAM));
+
+    let mut visits = 0;
+    let flow = af
+        .for_each_extension(|_| {
+            visits += 1;
+            ControlFlow::Break(())
+        })
+        .expect("Visiting extensions");
+    assert_eq!((flow, visits), (ControlFlow::Break(()), 1));
+    // The control is available again afterwards
+    assert_eq!(
+        af.count_extensions().expect("Counting extensions"),
+        visited.len()
+    );
+}
+
+#[test]
+fn strict_frameworks_reject_declaration_issues() {
+    let strict = || FrameworkConfig {
+        strict: true,
+        ..Default::default()
+    };
+    for program in [
+        "arg(a). arg(a).",
+        "arg(a). arg(b). att(a,b). att(a,b).",
+        "a\na?\n#\n",
+    ] {
+        assert!(ArgumentationFramework::<Admissible>::new(program).is_ok());
+        assert!(matches!(
+            ArgumentationFramework::<Admissible>::new_with_config(program, strict()),
+            Err(Error::Parser(_))
+        ));
+    }
+    assert!(ArgumentationFramework::<Admissible>::new_with_config(
+        "arg(a). arg(b). att(a,b). opt(att(a,b)).",
+        strict()
+    )
+    .is_ok());
+}
+
+#[test]
+fn attacks_on_undeclared_arguments_follow_the_policy() {
+    const PROGRAM: &str = "arg(a). att(a,b). att(c,a).";
+    let with_policy = |undeclared| {
+        let config = FrameworkConfig {
+            undeclared,
+            ..Default::default()
+        };
+        ArgumentationFramework::<Admissible>::new_with_config(PROGRAM, config)
+    };
+    assert!(matches!(
+        with_policy(UndeclaredArguments::Reject),
+        Err(Error::Parser(ParserError::UndeclaredArgument { arg_id, .. })) if arg_id == "b"
+    ));
+    let mut af = with_policy(UndeclaredArguments::Ignore).expect("Creating AF");
+    assert!(af.attacks().is_empty());
+    assert_eq!(extensions_of(&mut af), set![ext!(), ext!("a")]);
+    let mut af = with_policy(UndeclaredArguments::Declare).expect("Creating AF");
+    assert_eq!(af.arguments(), [arg!("a"), arg!("b"), arg!("c")]);
+    assert_eq!(
+        extensions_of(&mut af),
+        set![ext!(), ext!("c"), ext!("b", "c")]
+    );
+}
+
+#[test]
+fn reference_solver_checks_extensions() {
+    use reference::ReferenceSemantics;
+    let (args, attacks) = parse_initial_file("arg(a). arg(b). arg(c). att(a,b). att(b,c).")
+        .expect("Parsing framework");
+    let is_extension = |extension, semantics| {
+        reference::is_extension(&args, &attacks, &extension, semantics).expect("Checking")
+    };
+    assert!(is_extension(ext!("a", "c"), ReferenceSemantics::Grounded));
+    assert!(is_extension(ext!("a"), ReferenceSemantics::Admissible));
+    assert!(!is_extension(ext!("a"), ReferenceSemantics::Complete));
+    assert!(is_extension(ext!("b"), ReferenceSemantics::ConflictFree));
+    assert!(!is_extension(
+        ext!("a", "d"),
+        ReferenceSemantics::ConflictFree
+    ));
+    let too_many = (0..=reference::MAX_ARGUMENTS)
+        .map(|idx| symbols::Argument::new(format!("a{idx}"), false))
+        .collect::<Vec<_>>();
+    assert!(reference::extensions(&too_many, &[], ReferenceSemantics::Stable).is_err());
+}
+
+#[test]
+fn iccma_compliant_frameworks_reject_optional_elements() {
+    let compliant = || FrameworkConfig {
+        iccma_compliant: true,
+        ..Default::default()
+    };
+    assert!(matches!(
+        ArgumentationFramework::<Admissible>::new_with_config(
+            "arg(a). arg(b). att(a,b). opt(att(a,b)).",
+            compliant()
+        ),
+        Err(Error::Parser(ParserError::NotIccmaCompliant { element })) if element == "att(a,b)"
+    ));
+    let mut af =
+        ArgumentationFramework::<Admissible>::new_with_config("a\nb\n#\na b\n", compliant())
+            .expect("Creating AF");
+    assert_eq!(extensions_of(&mut af), set![ext!(), ext!("a")]);
+}
+
+#[test]
+fn unicode_identifiers_are_quoted_for_clingo() {
+    assert_eq!(
+        extensions::<Admissible>(
+            "arg(größe). arg(b-c). arg(日本). att(größe, b-c). att(b-c, 日本)."
+        ),
+        set![ext!(), ext!("größe"), ext!("größe", "日本")]
+    );
+    let mut af = ArgumentationFramework::<Complete>::new_with_config(
+        "éa\nnot\n#\néa not?\n",
+        FrameworkConfig {
+            decompose: true,
+            ..Default::default()
+        },
+    )
+    .expect("Creating AF");
+    assert_eq!(extensions_of(&mut af), set![ext!("not", "éa")]);
+    af.update("+éa not").expect("Updating");
+    assert_eq!(extensions_of(&mut af), set![ext!("éa")]);
+}
+
+#[test]
+fn maximal_and_minimal_extensions() {
+    let program = "arg(a). arg(b). arg(c). arg(d). att(a,b). att(b,a). att(c,d). opt(att(c,d)).";
+    let mut af = ArgumentationFramework::<Admissible>::new(program).expect("Creating AF");
+    let maximal = |af: &mut ArgumentationFramework<Admissible>| {
+        let extensions = af.enumerate_maximal_extensions().expect("Enumerating");
+        extensions.into_iter().collect::<BTreeSet<_>>()
+    };
+    assert_eq!(
+        maximal(&mut af),
+        set![ext!("a", "c", "d"), ext!("b", "c", "d")]
+    );
+    af.update("+att(c,d).").expect("Updating");
+    assert_eq!(maximal(&mut af), set![ext!("a", "c"), ext!("b", "c")]);
+    assert_eq!(
+        af.enumerate_minimal_extensions().expect("Enumerating"),
+        vec![Extension::EMPTY]
+    );
+    let decomposed = FrameworkConfig {
+        decompose: true,
+        ..Default::default()
+    };
+    let mut complete = ArgumentationFramework::<Complete>::new_with_config(program, decomposed)
+        .expect("Creating AF");
+    assert_eq!(
+        complete
+            .enumerate_minimal_extensions()
+            .expect("Enumerating"),
+        vec![ext!("c", "d")]
+    );
+}
+
+#[test]
+fn preferred_extensions_follow_updates() {
+    let program = "arg(a). arg(b). arg(c). arg(d). att(a,b). att(b,a). att(c,d). opt(att(c,d)).";
+    let mut af = ArgumentationFramework::<Preferred>::new(program).expect("Creating AF");
+    assert_eq!(
+        extensions_of(&mut af),
+        set![ext!("a", "c", "d"), ext!("b", "c", "d")]
+    );
+    af.update("+att(c,d).").expect("Updating");
+    assert_eq!(extensions_of(&mut af), set![ext!("a", "c"), ext!("b", "c")]);
+    assert!(!af.is_skeptical_accepted(&arg!("a")).expect("Deciding"));
+    assert!(af.is_skeptical_accepted(&arg!("c")).expect("Deciding"));
+    assert_eq!(
+        af.enumerate_maximal_extensions()
+            .expect("Enumerating")
+            .into_iter()
+            .collect::<BTreeSet<_>>(),
+        set![ext!("a", "c"), ext!("b", "c")]
+    );
+}
+
+#[test]
+fn cf2_extensions_of_odd_cycles() {
+    let program = "arg(a). arg(b). arg(c). arg(d). att(a,b). att(b,c). att(c,a). att(c,d).";
+    let expected = set![ext!("a", "d"), ext!("b", "d"), ext!("c")];
+    assert_eq!(extensions::<Cf2>(program), expected);
+    let decomposed = FrameworkConfig {
+        decompose: true,
+        ..Default::default()
+    };
+    let mut af =
+        ArgumentationFramework::<Cf2>::new_with_config(program, decomposed).expect("Creating AF");
+    assert_eq!(extensions_of(&mut af), expected);
+}
+
+#[test]
+fn resolution_grounded_breaks_mutual_attacks() {
+    let program = "arg(a). arg(b). arg(c). arg(d). att(a,b). att(b,a). att(b,c). att(c,d).";
+    assert_eq!(
+        extensions::<ResolutionGrounded>(program),
+        set![ext!("a", "c"), ext!("b", "d")]
+    );
+    // Without mutual attacks, the only resolution is the framework itself
+    assert_eq!(
+        extensions::<ResolutionGrounded>("arg(a). arg(b). arg(c). att(a,b). att(b,c). att(c,a)."),
+        set![Extension::EMPTY]
+    );
+}
+
+#[test]
+fn strongly_admissible_sets_and_justifications() {
+    let program = "arg(a). arg(b). arg(c). arg(d). arg(e). att(a,b). att(b,c). att(d,e). att(e,d).";
+    assert_eq!(
+        extensions::<StronglyAdmissible>(program),
+        set![Extension::EMPTY, ext!("a"), ext!("a", "c")]
+    );
+    let af = ArgumentationFramework::<StronglyAdmissible>::new(program).expect("Creating AF");
+    let justification = af
+        .justify_grounded(&arg!("c"))
+        .expect("Justifying")
+        .expect("c is grounded");
+    assert_eq!(
+        justification.levels,
+        vec![vec![String::from("a")], vec![String::from("c")]]
+    );
+    // d is admissible, but not strongly admissible
+    assert_eq!(af.justify_grounded(&arg!("d")).expect("Justifying"), None);
+}
+
+#[test]
+fn custom_semantics_are_validated_and_loaded() {
+    use super::semantics::custom;
+    let naive = r#"
+        %* Subset-maximal conflict-free sets, attack/3 is not used *%
+        { in(X) : argument(X) }.
+        :- in(X), in(Y), attack(X,Y).
+        :- argument(X), not in(X), not attack(X,X), not in(Y) : attack(X,Y); not in(Y) : attack(Y,X).
+    "#;
+    assert!(custom::validate(naive).is_ok());
+    assert!(custom::validate("{ in(X) : argument(X) }.").is_err());
+    assert!(custom::validate("{ in(X) : argument(X) }. :- in(X), attack(X,X,1).").is_err());
+    assert!(custom::validate("in(X, 1) :- argument(X), not attack(X, X).").is_err());
+    assert!(ArgumentationFramework::<Custom>::new("arg(a).").is_err());
+
+    custom::load(naive.to_owned()).expect("Loading program");
+    assert!(custom::load(ConflictFree::BASE.to_owned()).is_err());
+    assert_eq!(
+        extensions::<Custom>("arg(a). arg(b). arg(c). att(a,b). att(b,a). att(c,c)."),
+        set![ext!("a"), ext!("b")]
+    );
+}
+
+#[test]
+fn semantics_are_selected_at_runtime() {
+    use super::registry::SemanticsId;
+    use reference::ReferenceSemantics as Reference;
+    assert_eq!(
+        "preferred".parse::<SemanticsId>().ok(),
+        Some(SemanticsId::Preferred)
+    );
+    assert_eq!(
+        "GR".parse::<SemanticsId>().ok(),
+        Some(SemanticsId::Grounded)
+    );
+    assert!("naive".parse::<SemanticsId>().is_err());
+    const PROGRAM: &str = "arg(a). arg(b). arg(c). att(a,b). att(b,a). att(b,c).";
+    let (arguments, attacks) = parse_initial_file(PROGRAM).expect("Parsing framework");
+    for id in SemanticsId::ALL {
+        assert_eq!(id.name().parse::<SemanticsId>().ok(), Some(id));
+        let semantics = match id {
+            SemanticsId::Admissible => Reference::Admissible,
+            SemanticsId::Cf2 => Reference::Cf2,
+            SemanticsId::Complete => Reference::Complete,
+            SemanticsId::ConflictFree => Reference::ConflictFree,
+            SemanticsId::Custom => continue,
+            SemanticsId::Grounded => Reference::Grounded,
+            SemanticsId::Preferred => Reference::Preferred,
+            SemanticsId::ResolutionGrounded => Reference::ResolutionGrounded,
+            SemanticsId::Stable => Reference::Stable,
+            SemanticsId::StronglyAdmissible => Reference::StronglyAdmissible,
+        };
+        let mut af = id
+            .framework(PROGRAM, Default::default())
+            .expect("Creating AF");
+        let expected =
+            reference::extensions(&arguments, &attacks, semantics).expect("Solving by brute force");
+        let extensions: BTreeSet<_> = af.extensions().expect("Enumerating").into_iter().collect();
+        assert_eq!(extensions, expected.into_iter().collect(), "{id}");
+    }
+}
+
+#[test]
+fn arguments_are_ranked_by_the_h_categoriser() {
+    let mut af = ArgumentationFramework::<Admissible>::new(
+        "arg(a). arg(b). arg(c). arg(d). att(a,b). att(b,c). att(c,d). att(d,c). opt(att(a,b)).",
+    )
+    .expect("Creating AF");
+    let ranking = af.ranking().expect("Ranking");
+    // c is attacked by b and d, d by the weaker c only
+    assert_eq!(ranking.levels(), [vec!["a", "b"], vec!["d"], vec!["c"]]);
+    af.update("+att(a,b).").expect("Updating");
+    let ranking = af.ranking().expect("Ranking");
+    assert_eq!(ranking.score("b"), Some(0.5));
+    assert_eq!(ranking.compare("a", "b"), Some(std::cmp::Ordering::Greater));
+    assert_eq!(ranking.compare("e", "b"), None);
+    assert_eq!(
+        ranking.levels(),
+        [vec!["a"], vec!["d"], vec!["b"], vec!["c"]]
+    );
+}
+
+#[test]
+fn gradual_scores_follow_patches() {
+    use super::gradual::Aggregation;
+    let close = |af: &ArgumentationFramework<Admissible>, id, expected: f64| {
+        let score = af.gradual_scores().and_then(|scores| scores.score(id));
+        assert!(
+            score.is_some_and(|score| (score - expected).abs() < 1e-9),
+            "{id}: {score:?}"
+        );
+    };
+    let mut af = ArgumentationFramework::<Admissible>::new(
+        "arg(a). arg(b). arg(c). arg(d). att(a,b). att(b,c). att(d,c). opt(att(a,b)). opt(arg(d)).",
+    )
+    .expect("Creating AF");
+    let scores = af.track_gradual(Aggregation::Max).expect("Scoring");
+    assert_eq!(scores.score("b"), Some(1.0));
+    assert_eq!(scores.score("c"), Some(0.5));
+    assert_eq!(scores.score("d"), None);
+    af.update("+att(a,b).").expect("Updating");
+    close(&af, "b", 0.5);
+    close(&af, "c", 2.0 / 3.0);
+    af.update("+arg(d).").expect("Updating");
+    close(&af, "c", 0.5);
+    // Card-based semantics only counts the attackers of positive degree
+    let scores = af.track_gradual(Aggregation::Card).expect("Scoring");
+    scores.set_weight("d", 0.0).expect("Weighting");
+    assert!(scores.set_weight("d", 2.0).is_err());
+    close(&af, "b", 1.0 / 3.0);
+    close(&af, "c", 3.0 / 7.0);
+}
+
+#[test]
+fn optimal_extensions_are_found() {
+    const PROGRAM: &str =
+        "arg(a). arg(b). arg(c). arg(d). att(a,b). att(b,a). att(b,c). att(c,d). opt(att(c,d)).";
+    let mut af = ArgumentationFramework::<Admissible>::new(PROGRAM).expect("Creating AF");
+    assert_eq!(
+        af.optimal_extension(Objective::Maximum)
+            .expect("Optimizing"),
+        Some(ext!("a", "c", "d"))
+    );
+    assert_eq!(
+        af.optimal_extension(Objective::Minimum)
+            .expect("Optimizing"),
+        Some(Extension::EMPTY)
+    );
+    af.update("+att(c,d).").expect("Updating");
+    assert_eq!(
+        af.optimal_extension(Objective::Maximum)
+            .expect("Optimizing"),
+        Some(ext!("a", "c"))
+    );
+    // Preferred extensions are enumerated, as not every admissible set is one
+    let mut af = ArgumentationFramework::<Preferred>::new(PROGRAM).expect("Creating AF");
+    assert_eq!(
+        af.optimal_extension(Objective::Minimum)
+            .expect("Optimizing"),
+        Some(ext!("b", "d"))
+    );
+    // Stable extensions are optimized by clingo directly
+    let mut af = ArgumentationFramework::<Stable>::new(
+        "arg(a). arg(b). arg(c). att(a,b). att(b,a). att(b,c).",
+    )
+    .expect("Creating AF");
+    assert_eq!(
+        af.optimal_extension(Objective::Maximum)
+            .expect("Optimizing"),
+        Some(ext!("a", "c"))
+    );
+    assert_eq!(
+        af.optimal_extension(Objective::Minimum)
+            .expect("Optimizing"),
+        Some(ext!("b"))
+    );
+    let mut af = ArgumentationFramework::<Stable>::new("arg(a). att(a,a).").expect("Creating AF");
+    assert_eq!(
+        af.optimal_extension(Objective::Maximum)
+            .expect("Optimizing"),
+        None
+    );
+}
+
+#[test]
+fn rejections_are_explained_by_removable_attacks() {
+    let mut af = ArgumentationFramework::<Admissible>::new(
+        "arg(a). arg(b). arg(c). arg(d). att(b,a). att(c,a). att(d,c). opt(att(b,a)). opt(att(c,a)).",
+    )
+    .expect("Creating AF");
+    af.update("+att(b,a):att(c,a).").expect("Updating");
+    let diagnoses = af.explain_rejection(&arg!("a")).expect("Explaining");
+    assert_eq!(diagnoses.len(), 1);
+    assert_eq!(diagnoses[0].update_line().as_deref(), Some("-att(b,a)."));
+    let diagnoses = af.explain_rejection(&arg!("d")).expect("Explaining");
+    assert_eq!(diagnoses[0].update_line(), None);
+    assert!(af.explain_rejection(&arg!("e")).is_err());
+    let af = ArgumentationFramework::<ResolutionGrounded>::new(
+        "arg(a). arg(b). att(b,a). opt(att(b,a)).",
+    )
+    .expect("Creating AF");
+    assert!(af.explain_rejection(&arg!("a")).is_err());
+}
+
+#[test]
+fn hypothetical_patches_are_reverted() {
+    let mut af = ArgumentationFramework::<Admissible>::new(
+        "arg(a). arg(b). arg(c). att(a,b). att(b,c). opt(att(a,b)). opt(att(b,c)).",
+    )
+    .expect("Creating AF");
+    af.update("+att(b,c).").expect("Updating");
+    let before = extensions_of(&mut af);
+    let patches = [
+        Patch::EnableAttack(att!("a", "b")),
+        Patch::DisableAttack(att!("b", "c")),
+        Patch::EnableAttack(att!("b", "c")),
+    ];
+    let hypothetical = af
+        .query_with_hypothetical_patches(&patches, |af| Ok(extensions_of(af)))
+        .expect("Querying");
+    assert_eq!(hypothetical, set![ext!(), ext!("a"), ext!("a", "c")]);
+    assert_eq!(extensions_of(&mut af), before);
+    let failing = af.query_with_hypothetical_patches(&patches[..1], |_| -> Result<()> {
+        Err(Error::Logic(String::from("Query failed")))
+    });
+    assert!(failing.is_err());
+    assert_eq!(extensions_of(&mut af), before);
+}
+
+#[test]
+fn equivalence_is_reported_with_witnesses() {
+    use equivalence::{Kernel, Side, Witness};
+    let parse = |input| parse_initial_file(input).expect("Parsing framework");
+    let (args_a, attacks_a) = parse("arg(a). arg(b). att(a,b). att(b,a).");
+    let (args_b, attacks_b) = parse("arg(a). arg(b). att(a,b).");
+    let witness = equivalence::standard::<Admissible>(
+        (&args_a, &attacks_a),
+        (&args_b, &attacks_b),
+        &FrameworkConfig::default(),
+    )
+    .expect("Solving");
+    assert_eq!(
+        witness,
+        Some(Witness::Extension {
+            extension: ext!("b"),
+            only_in: Side::First
+        })
+    );
+    // Without self-attacks, every attack is part of every kernel
+    assert_eq!(
+        equivalence::strong(
+            (&args_a, &attacks_a),
+            (&args_b, &attacks_b),
+            Kernel::Complete
+        ),
+        Some(Witness::Attack {
+            attack: att!("b", "a"),
+            only_in: Side::First
+        })
+    );
+    let (args_c, attacks_c) = parse("arg(a). arg(b). att(a,a). att(b,b). att(a,b).");
+    let (args_d, attacks_d) = parse("arg(a). arg(b). att(a,a). att(b,b). att(b,a).");
+    assert_eq!(
+        equivalence::strong(
+            (&args_c, &attacks_c),
+            (&args_d, &attacks_d),
+            Kernel::Complete
+        ),
+        None
+    );
+    // Attacks of self-attacking arguments are irrelevant under stable semantics only
+    let (args_e, attacks_e) = parse("arg(a). arg(b). att(a,a). att(a,b).");
+    let (args_f, attacks_f) = parse("arg(a). arg(b). att(a,a).");
+    assert_eq!(
+        equivalence::strong((&args_e, &attacks_e), (&args_f, &attacks_f), Kernel::Stable),
+        None
+    );
+    assert!(equivalence::strong(
+        (&args_e, &attacks_e),
+        (&args_f, &attacks_f),
+        Kernel::Grounded
+    )
+    .is_some());
+}
+
+#[test]
+fn certificates_are_created_and_validated() {
+    use certify::{Certificate, Defense};
+    let parse = |input| parse_initial_file(input).expect("Parsing framework");
+    let program = "arg(a). arg(b). arg(c). arg(d). att(a,b). att(b,c). att(d,d). att(d,c).";
+    let (args, attacks) = parse(program);
+    let mut af = ArgumentationFramework::<Admissible>::new(program).expect("Creating AF");
+    let certificate = af
+        .certify_credulous(&arg!("a"))
+        .expect("Certifying")
+        .expect("Certificate for a");
+    assert_eq!(certificate.validate(&args, &attacks), Ok(()));
+    // b is labelled out by the grounded labelling
+    let refutation = af
+        .certify_credulous(&arg!("b"))
+        .expect("Certifying")
+        .expect("Refutation for b");
+    assert_eq!(refutation.label(), Some(Label::Out));
+    assert_eq!(refutation.validate(&args, &attacks), Ok(()));
+    // c is undecided, nothing refutes it in the grounded labelling
+    assert_eq!(af.certify_credulous(&arg!("c")).expect("Certifying"), None);
+    // Tampered certificates are detected
+    let undefended = Certificate::Defended {
+        argument: String::from("c"),
+        extension: vec![String::from("a"), String::from("c")],
+        defenses: vec![Defense {
+            attacker: String::from("b"),
+            defender: String::from("a"),
+        }],
+    };
+    assert!(undefended.validate(&args, &attacks).is_err());
+    let Certificate::Labelling { mut labels, .. } = refutation else {
+        panic!("Expected a labelling");
+    };
+    labels.insert(String::from("c"), Label::Out);
+    let wrong = Certificate::Labelling {
+        argument: String::from("b"),
+        labels,
+    };
+    assert!(wrong.validate(&args, &attacks).is_err());
+}
+
+#[test]
+fn query_arguments_are_looked_up_with_near_misses() {
+    let af = ArgumentationFramework::<Admissible>::new("arg(a1). arg(a2). arg(b12). att(a1,a2).")
+        .expect("Creating AF");
+    assert_eq!(
+        symbols::find(af.arguments(), "a2").expect("Finding a2"),
+        &arg!("a2")
+    );
+    let Err(Error::Logic(message)) = symbols::find(af.arguments(), "a3") else {
+        panic!("a3 does not exist");
+    };
+    assert_eq!(
+        message,
+        r#"Unknown argument "a3", did you mean "a1" or "a2"?"#
+    );
+    let Err(Error::Logic(message)) = symbols::find(af.arguments(), "xyz") else {
+        panic!("xyz does not exist");
+    };
+    assert_eq!(message, r#"Unknown argument "xyz""#);
+}
+
+/// Stable extensions `{a, c, e}` and `{b, e}`
+const STABLE_PROGRAM: &str = "arg(a). arg(b). arg(c). arg(e). att(a,b). att(b,a). att(b,c).";
+
+#[test]
+fn credulously_accepted_arguments_are_brave_consequences() {
+    const PROGRAM: &str =
+        "arg(a). arg(b). arg(c). arg(d). att(a,b). att(b,a). att(b,c). att(d,d). opt(att(b,a)).";
+    let mut af = ArgumentationFramework::<Admissible>::new(PROGRAM).expect("Creating AF");
+    assert_eq!(
+        af.credulously_accepted_arguments().expect("Solving"),
+        Some(ext!("a", "c"))
+    );
+    af.update("+att(b,a).").expect("Updating");
+    assert_eq!(
+        af.credulously_accepted_arguments().expect("Solving"),
+        Some(ext!("a", "b", "c"))
+    );
+    // Preferred extensions are enumerated, as not every admissible set is one
+    let mut af = ArgumentationFramework::<Preferred>::new(PROGRAM).expect("Creating AF");
+    af.update("+att(b,a).").expect("Updating");
+    assert_eq!(
+        af.credulously_accepted_arguments().expect("Solving"),
+        Some(ext!("a", "b", "c"))
+    );
+    // The self-attacking d prevents any stable extension
+    let mut af = ArgumentationFramework::<Stable>::new(PROGRAM).expect("Creating AF");
+    assert_eq!(af.credulously_accepted_arguments().expect("Solving"), None);
+    let mut af = ArgumentationFramework::<Stable>::new(STABLE_PROGRAM).expect("Creating AF");
+    assert_eq!(
+        af.credulously_accepted_arguments().expect("Solving"),
+        Some(ext!("a", "b", "c", "e"))
+    );
+}
+
+#[test]
+fn skeptically_accepted_arguments_are_cautious_consequences() {
+    const PROGRAM: &str = "arg(a). arg(b). arg(c). arg(d). arg(e). \
+        att(a,b). att(b,a). att(b,c). att(d,d). opt(att(b,a)).";
+    let mut af = ArgumentationFramework::<Complete>::new(PROGRAM).expect("Creating AF");
+    assert_eq!(
+        af.skeptically_accepted_arguments().expect("Solving"),
+        Some(ext!("a", "c", "e"))
+    );
+    af.update("+att(b,a).").expect("Updating");
+    assert_eq!(
+        af.skeptically_accepted_arguments().expect("Solving"),
+        Some(ext!("e"))
+    );
+    // Preferred extensions are enumerated, as not every admissible set is one
+    let mut af = ArgumentationFramework::<Preferred>::new(PROGRAM).expect("Creating AF");
+    af.update("+att(b,a).").expect("Updating");
+    assert_eq!(
+        af.skeptically_accepted_arguments().expect("Solving"),
+        Some(ext!("e"))
+    );
+    // The self-attacking d prevents any stable extension
+    let mut af = ArgumentationFramework::<Stable>::new(PROGRAM).expect("Creating AF");
+    assert_eq!(af.skeptically_accepted_arguments().expect("Solving"), None);
+    let mut af = ArgumentationFramework::<Stable>::new(STABLE_PROGRAM).expect("Creating AF");
+    assert_eq!(
+        af.skeptically_accepted_arguments().expect("Solving"),
+        Some(ext!("e"))
+    );
+}
+
+#[test]
+fn existence_of_stable_extensions_is_decided() {
+    let mut af =
+        ArgumentationFramework::<Stable>::new("arg(a). arg(b). att(a,a). att(b,a). opt(att(b,a)).")
+            .expect("Creating AF");
+    assert!(!af.has_extension().expect("Solving"));
+    af.update("+att(b,a).").expect("Updating");
+    assert!(af.has_extension().expect("Solving"));
+    // The model limit is restored after the bounded solve
+    assert_eq!(af.count_extensions().expect("Counting"), 1);
+    af.update("-att(b,a).").expect("Updating");
+    assert!(!af.has_extension().expect("Solving"));
+}
+
+#[test]
+fn extensions_are_sampled_near_uniformly() {
+    let mut state = 0x9E37_79B9_7F4A_7C15_u64;
+    let mut rng = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    // Every pair of mutual attackers gives three choices, 3^5 admissible sets in total
+    let program = (0..5)
+        .map(|idx| format!("arg(a{idx}). arg(b{idx}). att(a{idx},b{idx}). att(b{idx},a{idx})."))
+        .collect::<String>();
+    let mut af = ArgumentationFramework::<Admissible>::new(&program).expect("Creating AF");
+    let all = extensions_of(&mut af);
+    assert!(all.len() > sampling::MAX_CELL);
+    let samples = (0..20)
+        .map(|_| {
+            af.sample_extension_uniform(&mut rng)
+                .expect("Sampling")
+                .expect("Some extension")
+        })
+        .collect::<BTreeSet<_>>();
+    assert!(samples.is_subset(&all));
+    assert!(samples.len() > 1);
+    let mut af = ArgumentationFramework::<Stable>::new(STABLE_PROGRAM).expect("Creating AF");
+    let stable = extensions_of(&mut af);
+    assert_eq!(stable.len(), 2);
+    let sample = af.sample_extension_uniform(&mut rng).expect("Sampling");
+    assert!(sample.is_some_and(|sample| stable.contains(&sample)));
+    let mut af = ArgumentationFramework::<Stable>::new("arg(a). att(a,a).").expect("Creating AF");
+    assert_eq!(
+        af.sample_extension_uniform(&mut rng).expect("Sampling"),
+        None
+    );
+}
+
+#[test]
+fn extensions_are_verified_under_the_current_framework() {
+    const PROGRAM: &str =
+        "arg(a). arg(b). arg(c). arg(d). att(a,b). att(b,a). att(b,c). opt(att(b,a)). opt(arg(d)).";
+    let mut af = ArgumentationFramework::<Stable>::new(PROGRAM).expect("Creating AF");
+    let verify = |af: &mut ArgumentationFramework<Stable>, candidate| {
+        af.verify_extension(&candidate).expect("Verifying")
+    };
+    assert!(verify(&mut af, ext!("a", "c")));
+    assert!(!verify(&mut af, ext!("b")));
+    // Admissible sets that leave arguments undefeated are rejected
+    assert!(!verify(&mut af, ext!()));
+    assert!(!verify(&mut af, ext!("a")));
+    // Arguments that are not part of the framework are never accepted
+    assert!(!verify(&mut af, ext!("a", "c", "d")));
+    af.update("+att(b,a).").expect("Updating");
+    af.update("+arg(d).").expect("Updating");
+    assert!(verify(&mut af, ext!("b", "d")));
+    assert!(!verify(&mut af, ext!("a", "c")));
+    // Preferred extensions are enumerated, as not every admissible set is one
+    let mut af = ArgumentationFramework::<Preferred>::new(PROGRAM).expect("Creating AF");
+    assert!(af.verify_extension(&ext!("a", "c")).expect("Verifying"));
+    assert!(!af.verify_extension(&ext!("a")).expect("Verifying"));
+}
+
+#[test]
+fn update_lines_are_applied_as_one_batch() {
+    let mut af = ArgumentationFramework::<Admissible>::new(
+        "arg(a). arg(b). att(a,b). att(b,a). opt(att(a,b)). opt(att(b,a)).",
+    )
+    .expect("Creating AF");
+    af.update_many(&["+att(a,b).", "+att(b,a).", "-att(a,b)."])
+        .expect("Updating");
+    assert_eq!(extensions_of(&mut af), set![ext!(), ext!("b")]);
+    // A failing line prevents all others
+    assert!(af.update_many(&["+att(a,b).", "+att(c,a)."]).is_err());
+    assert_eq!(extensions_of(&mut af), set![ext!(), ext!("b")]);
+}
+
+#[test]
+fn undo_restores_the_previous_truth_values() {
+    let mut af = ArgumentationFramework::<Admissible>::new(
+        "arg(a). arg(b). att(a,b). opt(att(a,b)). opt(arg(b)).",
+    )
+    .expect("Creating AF");
+    af.update("+arg(b).").expect("Enabling b");
+    af.update_many(&["+att(a,b).", "-att(a,b).", "+att(a,b)."])
+        .expect("Updating");
+    assert_eq!(extensions_of(&mut af), set![ext!(), ext!("a")]);
+    // Hypothetical patches are not recorded
+    af.query_with_hypothetical_patches(&[Patch::DisableArgument(arg!("b"))], |_| Ok(()))
+        .expect("Querying");
+    assert!(af.undo(5).is_err());
+    af.undo(1).expect("Undoing");
+    assert_eq!(
+        extensions_of(&mut af),
+        set![ext!(), ext!("a"), ext!("b"), ext!("a", "b")]
+    );
+    af.undo(3).expect("Undoing");
+    assert_eq!(extensions_of(&mut af), set![ext!(), ext!("a")]);
+    assert!(af.undo(1).is_err());
+}
+
+#[test]
+fn history_replays_to_the_current_framework() {
+    const PROGRAM: &str =
+        "arg(a). arg(b). arg(c). att(a,b). att(b,c). opt(arg(c)). opt(att(a,b)). opt(att(b,c)).";
+    let mut af = ArgumentationFramework::<Complete>::new(PROGRAM).expect("Creating AF");
+    af.update("+arg(c):att(b,c).").expect("Updating");
+    af.update("+att(a,b).").expect("Updating");
+    af.update("-att(b,c).").expect("Updating");
+    af.undo(1).expect("Undoing");
+    assert_eq!(
+        af.history(),
+        [
+            Patch::EnableArgument(arg!("c")),
+            Patch::EnableAttack(att!("b", "c")),
+            Patch::EnableAttack(att!("a", "b")),
+        ]
+    );
+    for format in [UpdateFormat::Apxm, UpdateFormat::Tgfm] {
+        let mut replayed = ArgumentationFramework::<Complete>::new(PROGRAM).expect("Creating AF");
+        for line in af.history_to_string(format).lines() {
+            replayed.update(line).expect("Replaying");
+        }
+        assert_eq!(extensions_of(&mut replayed), extensions_of(&mut af));
+    }
+}
+
+#[test]
+fn enabling_unknown_elements_adds_them() {
+    let mut af =
+        ArgumentationFramework::<Ground>::new("arg(a). arg(b). att(a,b).").expect("Creating AF");
+    af.update("+arg(c):att(c,a).").expect("Adding c");
+    assert_eq!(extensions_of(&mut af), set![ext!("b", "c")]);
+    af.update("-arg(c).").expect("Disabling c");
+    assert_eq!(extensions_of(&mut af), set![ext!("a")]);
+    // Attacks must involve known arguments
+    assert!(af.update("+att(d,a).").is_err());
+    af.undo(1).expect("Undoing");
+    af.undo(2).expect("Undoing");
+    assert_eq!(extensions_of(&mut af), set![ext!("a")]);
+    assert_eq!(af.arguments().len(), 3);
+}
+
+#[test]
+fn new_attackers_are_seen_by_all_rules() {
+    use reference::ReferenceSemantics as Reference;
+    fn check<S: ArgumentationFrameworkSemantic>(semantics: Reference) {
+        let expected = |program| -> BTreeSet<_> {
+            let extensions = reference::solve(program, semantics).expect("Solving by brute force");
+            extensions.into_iter().collect()
+        };
+        const PROGRAM: &str = "arg(a). arg(b). att(a,b).";
+        let mut af = ArgumentationFramework::<S>::new(PROGRAM).expect("Creating AF");
+        assert_eq!(extensions_of(&mut af), expected(PROGRAM));
+        // The rules for a were grounded before c existed
+        af.update("+arg(c).").expect("Adding c");
+        af.update("+att(c,a).").expect("Adding the attack");
+        assert_eq!(
+            extensions_of(&mut af),
+            expected("arg(a). arg(b). arg(c). att(a,b). att(c,a).")
+        );
+    }
+    check::<StronglyAdmissible>(Reference::StronglyAdmissible);
+    check::<ResolutionGrounded>(Reference::ResolutionGrounded);
+}
+
+#[test]
+fn disabling_arguments_that_are_not_optional() {
+    let mut af =
+        ArgumentationFramework::<Complete>::new("arg(a). arg(b). arg(c). att(a,b). att(b,c).")
+            .expect("Creating AF");
+    af.update("-arg(b):att(c,a).")
+        .expect_err("Disabling a missing attack");
+    assert!(!af.arguments()[1].optional);
+    af.update("-arg(a).").expect("Disabling a");
+    assert_eq!(extensions_of(&mut af), set![ext!("b")]);
+    assert!(af.arguments()[0].optional);
+    af.update("+arg(a).").expect("Enabling a");
+    assert_eq!(extensions_of(&mut af), set![ext!("a", "c")]);
+    af.undo(2).expect("Undoing");
+    assert_eq!(extensions_of(&mut af), set![ext!("a", "c")]);
+}
+
+#[test]
+fn patches_are_validated_against_the_current_framework() {
+    let mut af = ArgumentationFramework::<Admissible>::new(
+        "arg(a). arg(b). att(a,b). att(b,a). opt(att(b,a)). opt(arg(b)).",
+    )
+    .expect("Creating AF");
+    let validate = |af: &ArgumentationFramework<Admissible>, line: &str| {
+        let patches = Patch::parse_line(line).expect("Parsing");
+        af.validate_patch(&patches[0]).expect("Validating")
+    };
+    assert_eq!(validate(&af, "+arg(b)."), PatchValidity::Applicable);
+    assert_eq!(validate(&af, "-arg(b)."), PatchValidity::Unchanged);
+    assert_eq!(validate(&af, "-arg(a)."), PatchValidity::Applicable);
+    assert_eq!(validate(&af, "+arg(c)."), PatchValidity::Applicable);
+    assert_eq!(validate(&af, "+att(a,b)."), PatchValidity::Unchanged);
+    let Err(Error::Logic(reason)) = af.update("-att(a,b).") else {
+        panic!("Disabling an attack that is not optional");
+    };
+    assert_eq!(validate(&af, "-att(a,b)."), PatchValidity::Invalid(reason));
+    af.update("+att(a,b).")
+        .expect("Enabling an attack that is not optional");
+    assert_eq!(af.history().len(), 1);
+    assert_eq!(
+        validate(&af, "+att(c,a)."),
+        PatchValidity::Invalid("Unknown argument \"c\", did you mean \"a\" or \"b\"?".to_owned())
+    );
+    af.update("+arg(b):att(b,a).").expect("Updating");
+    assert_eq!(validate(&af, "+att(b,a)."), PatchValidity::Unchanged);
+    assert_eq!(validate(&af, "-att(b,a)."), PatchValidity::Applicable);
+}
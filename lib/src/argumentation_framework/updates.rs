@@ -0,0 +1,87 @@
+//! Reading and writing update lines in APXM or TGFM format
+use std::{io::BufRead, thread, time::Duration};
+
+use fallible_iterator::FallibleIterator;
+
+use crate::{Error, Result};
+
+/// Interval in which a followed reader is checked for new lines.
+pub const FOLLOW_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Format of update lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateFormat {
+    /// APXM, i.e. `+att(a1,a2).`
+    Apxm,
+    /// TGFM, i.e. `+a1 a2`
+    Tgfm,
+}
+
+/// Whether the update line is a comment, i.e. starts with `%` or `#`.
+pub fn is_comment(line: &str) -> bool {
+    line.trim_start().starts_with(['%', '#'])
+}
+
+/// How [`UpdateLines`] treats empty lines and the end of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Follow {
+    /// An empty line ends the updates, as does the end of the input
+    No,
+    /// Empty lines are skipped and reaching the end of the input waits for new lines to
+    /// be appended, like `tail -f`
+    File,
+    /// Empty lines are skipped and the end of the input ends the updates, e.g. once the
+    /// writer of a pipe is done
+    Stream,
+}
+
+/// Trimmed update lines of a reader, without comments.
+///
+/// Unless followed, an empty line ends the updates, as does the end of the input. If
+/// a file is followed, only complete lines are handed out, as the writer may not be
+/// done with the last one yet.
+pub struct UpdateLines<R> {
+    reader: R,
+    follow: Follow,
+    /// Content of the current line read so far
+    partial: String,
+    done: bool,
+}
+
+impl<R: BufRead> UpdateLines<R> {
+    pub fn new(reader: R, follow: Follow) -> Self {
+        UpdateLines {
+            reader,
+            follow,
+            partial: String::new(),
+            done: false,
+        }
+    }
+}
+
+impl<R: BufRead> FallibleIterator for UpdateLines<R> {
+    type Item = String;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<String>> {
+        while !self.done {
+            let read = self.reader.read_line(&mut self.partial)?;
+            if self.follow == Follow::File && !self.partial.ends_with('\n') {
+                thread::sleep(FOLLOW_INTERVAL);
+                continue;
+            }
+            log::trace!("Found line: {:?}", self.partial);
+            let line = std::mem::take(&mut self.partial).trim().to_owned();
+            if line.is_empty() {
+                // The end of the input is an empty line as well
+                self.done = self.follow == Follow::No || read == 0;
+            } else if is_comment(&line) {
+                log::trace!("Skipping comment: {line:?}");
+            } else {
+                log::info!("Found update line: {line:?}");
+                return Ok(Some(line));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,113 @@
+//! Checks of parsed frameworks beyond their syntax
+use std::collections::{HashMap, HashSet};
+
+use crate::framework::ParserError;
+
+use super::{symbols, UndeclaredArguments};
+
+/// Find duplicate declarations, conflicting optionality and undeclared arguments.
+///
+/// All issues are returned in the order of the declarations. Arguments are reported
+/// before attacks.
+///
+/// # Example
+///
+/// ```
+/// # use lib::{argumentation_framework::{parse_initial_file, validation}, framework::ParserError};
+/// let (args, attacks) = parse_initial_file("a\na?\n#\na b\n").unwrap();
+/// let issues = validation::declaration_issues(&args, &attacks);
+/// assert!(matches!(issues[0], ParserError::ConflictingOptionality { .. }));
+/// assert!(matches!(&issues[1], ParserError::UndeclaredArgument { arg_id, .. } if arg_id == "b"));
+/// ```
+pub fn declaration_issues(
+    args: &[symbols::Argument],
+    attacks: &[symbols::Attack],
+) -> Vec<ParserError> {
+    let mut issues = vec![];
+    let mut declared = HashMap::with_capacity(args.len());
+    for arg in args {
+        match declared.insert(arg.id.as_str(), arg.optional) {
+            None => {}
+            Some(optional) if optional != arg.optional => {
+                issues.push(ParserError::ConflictingOptionality {
+                    element: arg.to_string(),
+                })
+            }
+            Some(_) => issues.push(ParserError::DuplicateArgument {
+                arg_id: arg.id.clone(),
+            }),
+        }
+    }
+    let mut declared_attacks = HashMap::with_capacity(attacks.len());
+    for attack in attacks {
+        let key = (attack.from.as_str(), attack.to.as_str());
+        match declared_attacks.insert(key, attack.optional) {
+            None => {}
+            Some(optional) if optional != attack.optional => {
+                issues.push(ParserError::ConflictingOptionality {
+                    element: attack.to_string(),
+                })
+            }
+            Some(_) => issues.push(ParserError::DuplicateAttack {
+                from: attack.from.clone(),
+                to: attack.to.clone(),
+            }),
+        }
+        let mut endpoints = vec![&attack.from];
+        if attack.to != attack.from {
+            endpoints.push(&attack.to);
+        }
+        for id in endpoints {
+            if !declared.contains_key(id.as_str()) {
+                issues.push(ParserError::UndeclaredArgument {
+                    arg_id: id.clone(),
+                    from: attack.from.clone(),
+                    to: attack.to.clone(),
+                });
+            }
+        }
+    }
+    issues
+}
+
+/// Handle attacks whose endpoints were never declared as arguments.
+///
+/// Depending on the policy, the first such attack is rejected, all of them are dropped
+/// or the missing arguments are declared as non-optional arguments.
+pub fn resolve_undeclared(
+    args: &mut Vec<symbols::Argument>,
+    attacks: &mut Vec<symbols::Attack>,
+    policy: UndeclaredArguments,
+) -> Result<(), ParserError> {
+    let mut declared: HashSet<String> = args.iter().map(|arg| arg.id.clone()).collect();
+    let mut result = Ok(());
+    attacks.retain(|attack| {
+        let mut keep = true;
+        for id in [&attack.from, &attack.to] {
+            if declared.contains(id) || result.is_err() {
+                continue;
+            }
+            match policy {
+                UndeclaredArguments::Reject => {
+                    result = Err(ParserError::UndeclaredArgument {
+                        arg_id: id.clone(),
+                        from: attack.from.clone(),
+                        to: attack.to.clone(),
+                    })
+                }
+                UndeclaredArguments::Ignore => {
+                    log::warn!("Ignoring {attack}, the argument {id} is not declared");
+                    keep = false;
+                    break;
+                }
+                UndeclaredArguments::Declare => {
+                    log::info!("Declaring the argument {id} used by {attack}");
+                    declared.insert(id.clone());
+                    args.push(symbols::Argument::new(id.clone(), false));
+                }
+            }
+        }
+        keep
+    });
+    result
+}
@@ -0,0 +1,173 @@
+//! Known relations between the semantics, checked on concrete frameworks
+//!
+//! Every semantics is solved on its own, so a violated relation points to a bug in one
+//! of the encodings, the decomposition or the solver setup. Used as test oracle and by
+//! `dasp verify` as sanity check of an installation.
+//!
+//! # Example
+//! ```
+//! use lib::argumentation_framework::{parse_initial_file, verification, FrameworkConfig};
+//!
+//! let (args, attacks) = parse_initial_file("arg(a). arg(b). att(a,b). att(b,a).").unwrap();
+//! let violations = verification::verify(&args, &attacks, &FrameworkConfig::default())
+//!     .expect("Solving");
+//! assert!(violations.is_empty());
+//! ```
+use std::{collections::BTreeSet, fmt};
+
+use fallible_iterator::FallibleIterator;
+
+use super::{
+    semantics::ArgumentationFrameworkSemantic, symbols, ArgumentationFramework, Extension,
+    FrameworkConfig,
+};
+use crate::{
+    semantics::{Admissible, Complete, ConflictFree, Ground, Stable},
+    Framework, GenericExtension, Result,
+};
+
+/// A relation that holds between the extensions of two semantics on every framework.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    /// Every admissible extension is conflict-free
+    AdmissibleIsConflictFree,
+    /// Every complete extension is admissible
+    CompleteIsAdmissible,
+    /// Every stable extension is complete
+    StableIsComplete,
+    /// There is exactly one grounded extension
+    GroundedIsUnique,
+    /// The grounded extension is complete
+    GroundedIsComplete,
+    /// The grounded extension is contained in every complete extension
+    GroundedInComplete,
+}
+
+impl Relation {
+    /// All relations, in the order they are checked.
+    pub const ALL: [Relation; 6] = [
+        Relation::AdmissibleIsConflictFree,
+        Relation::CompleteIsAdmissible,
+        Relation::StableIsComplete,
+        Relation::GroundedIsUnique,
+        Relation::GroundedIsComplete,
+        Relation::GroundedInComplete,
+    ];
+}
+
+impl fmt::Display for Relation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Relation::AdmissibleIsConflictFree => "every admissible extension is conflict-free",
+            Relation::CompleteIsAdmissible => "every complete extension is admissible",
+            Relation::StableIsComplete => "every stable extension is complete",
+            Relation::GroundedIsUnique => "there is exactly one grounded extension",
+            Relation::GroundedIsComplete => "the grounded extension is complete",
+            Relation::GroundedInComplete => {
+                "the grounded extension is contained in every complete extension"
+            }
+        };
+        f.write_str(description)
+    }
+}
+
+/// A relation that does not hold on a framework.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub relation: Relation,
+    /// Extension contradicting the relation, `None` if it is missing an extension
+    pub witness: Option<Extension>,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "violated: {}", self.relation)?;
+        if let Some(witness) = &self.witness {
+            write!(f, ", counterexample {}", witness.format())?;
+        }
+        Ok(())
+    }
+}
+
+/// Check all [`Relation`]s on the framework, returning the violated ones.
+///
+/// None of the elements should be optional, as optional elements stay disabled.
+pub fn verify(
+    arguments: &[symbols::Argument],
+    attacks: &[symbols::Attack],
+    config: &FrameworkConfig,
+) -> Result<Vec<Violation>> {
+    // Relations are about all extensions, solve without limits or shortcuts
+    let config = FrameworkConfig {
+        models: None,
+        decompose: true,
+        cache: false,
+        ..config.clone()
+    };
+    let extensions = Extensions {
+        conflict_free: extensions::<ConflictFree>(arguments, attacks, &config)?,
+        admissible: extensions::<Admissible>(arguments, attacks, &config)?,
+        complete: extensions::<Complete>(arguments, attacks, &config)?,
+        stable: extensions::<Stable>(arguments, attacks, &config)?,
+        grounded: extensions::<Ground>(arguments, attacks, &config)?,
+    };
+    let violations = Relation::ALL
+        .into_iter()
+        .filter_map(|relation| {
+            extensions
+                .counterexample(relation)
+                .map(|witness| Violation { relation, witness })
+        })
+        .collect();
+    Ok(violations)
+}
+
+/// Extensions of the framework under every semantics.
+struct Extensions {
+    conflict_free: BTreeSet<Extension>,
+    admissible: BTreeSet<Extension>,
+    complete: BTreeSet<Extension>,
+    stable: BTreeSet<Extension>,
+    grounded: BTreeSet<Extension>,
+}
+
+impl Extensions {
+    /// Find an extension contradicting the relation, `Some(None)` if one is missing.
+    fn counterexample(&self, relation: Relation) -> Option<Option<Extension>> {
+        let not_in = |sub: &BTreeSet<Extension>, sup: &BTreeSet<Extension>| {
+            sub.iter().find(|ext| !sup.contains(ext)).cloned().map(Some)
+        };
+        match relation {
+            Relation::AdmissibleIsConflictFree => not_in(&self.admissible, &self.conflict_free),
+            Relation::CompleteIsAdmissible => not_in(&self.complete, &self.admissible),
+            Relation::StableIsComplete => not_in(&self.stable, &self.complete),
+            Relation::GroundedIsUnique => match self.grounded.len() {
+                0 => Some(None),
+                1 => None,
+                _ => self.grounded.iter().nth(1).cloned().map(Some),
+            },
+            Relation::GroundedIsComplete => not_in(&self.grounded, &self.complete),
+            Relation::GroundedInComplete => self.grounded.iter().find_map(|grounded| {
+                self.complete
+                    .iter()
+                    .find(|complete| !grounded.is_subset(complete))
+                    .cloned()
+                    .map(Some)
+            }),
+        }
+    }
+}
+
+fn extensions<S: ArgumentationFrameworkSemantic>(
+    arguments: &[symbols::Argument],
+    attacks: &[symbols::Attack],
+    config: &FrameworkConfig,
+) -> Result<BTreeSet<Extension>> {
+    let mut af = ArgumentationFramework::<S>::from_elements(
+        arguments.to_vec(),
+        attacks.to_vec(),
+        config.clone(),
+    )?;
+    let extensions = af.enumerate_extensions()?.by_ref().collect()?;
+    Ok(extensions)
+}
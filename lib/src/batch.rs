@@ -0,0 +1,123 @@
+//! Solving many instances in parallel
+//!
+//! Every instance is loaded and solved on one of a bounded number of workers, results
+//! are handed out in the order in which the instances finish.
+//!
+//! # Example
+//! ```
+//! use lib::{
+//!     argumentation_framework::FrameworkConfig,
+//!     batch::{self, Instance},
+//!     semantics, Framework,
+//! };
+//!
+//! let instances = vec![
+//!     Instance::content("chain", "arg(a). arg(b). att(a,b)."),
+//!     Instance::content("cycle", "arg(a). arg(b). att(a,b). att(b,a)."),
+//! ];
+//! let workers = 2.try_into().unwrap();
+//! let results = batch::solve::<semantics::Admissible, _, _>(
+//!     instances,
+//!     workers,
+//!     FrameworkConfig::default(),
+//!     |af| af.count_extensions(),
+//! )
+//! .expect("Starting workers");
+//! let mut counts: Vec<_> = results
+//!     .map(|solved| (solved.instance.name(), solved.result.unwrap()))
+//!     .collect();
+//! counts.sort();
+//! assert_eq!(counts, vec![(String::from("chain"), 2), (String::from("cycle"), 3)]);
+//! ```
+use std::{num::NonZeroUsize, path::PathBuf, sync::mpsc, sync::Arc};
+
+use crate::{
+    argumentation_framework::{
+        semantics::ArgumentationFrameworkSemantic, ArgumentationFramework, FrameworkConfig,
+    },
+    Error, Result,
+};
+
+/// An instance to solve, given as initial file in APX or TGF format.
+#[derive(Debug, Clone)]
+pub enum Instance {
+    /// Read the initial file from this path
+    Path(PathBuf),
+    /// Content of an initial file, with a name to identify it
+    Content { name: String, input: String },
+}
+
+/// The result of solving an [`Instance`].
+#[derive(Debug)]
+pub struct Solved<T> {
+    pub instance: Instance,
+    /// Result of the task, or the error loading the instance
+    pub result: Result<T>,
+}
+
+impl Instance {
+    pub fn content(name: impl Into<String>, input: impl Into<String>) -> Self {
+        Instance::Content {
+            name: name.into(),
+            input: input.into(),
+        }
+    }
+    /// Name of the instance, i.e. the path for files.
+    pub fn name(&self) -> String {
+        match self {
+            Instance::Path(path) => path.display().to_string(),
+            Instance::Content { name, .. } => name.clone(),
+        }
+    }
+    fn load<S: ArgumentationFrameworkSemantic>(
+        &self,
+        config: FrameworkConfig,
+    ) -> Result<ArgumentationFramework<S>> {
+        match self {
+            Instance::Path(path) => {
+                let input = std::fs::read_to_string(path)?;
+                ArgumentationFramework::new_with_config(&input, config)
+            }
+            Instance::Content { input, .. } => {
+                ArgumentationFramework::new_with_config(input, config)
+            }
+        }
+    }
+}
+
+/// Solve the task on all instances, using at most `workers` threads.
+///
+/// Every instance gets its own framework using the given configuration. Note that
+/// [`FrameworkConfig::threads`] applies per instance, so it is usually best set to one.
+/// The returned iterator yields the results as soon as they are available and ends
+/// once all instances are solved.
+pub fn solve<S, T, F>(
+    instances: impl IntoIterator<Item = Instance>,
+    workers: NonZeroUsize,
+    config: FrameworkConfig,
+    task: F,
+) -> Result<impl Iterator<Item = Solved<T>>>
+where
+    S: ArgumentationFrameworkSemantic + 'static,
+    T: Send + 'static,
+    F: Fn(&mut ArgumentationFramework<S>) -> Result<T> + Send + Sync + 'static,
+{
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(workers.get())
+        .thread_name(|idx| format!("batch-{idx}"))
+        .build()
+        .map_err(|why| Error::Logic(format!("Could not start the workers: {why}")))?;
+    let (sender, receiver) = mpsc::channel();
+    let task = Arc::new(task);
+    for instance in instances {
+        let (sender, task, config) = (sender.clone(), Arc::clone(&task), config.clone());
+        pool.spawn(move || {
+            log::debug!("Solving {}", instance.name());
+            let result = instance.load::<S>(config).and_then(|mut af| task(&mut af));
+            // The receiver is gone if the caller is not interested anymore
+            sender.send(Solved { instance, result }).ok();
+        });
+    }
+    // Dropping the pool lets the workers finish the spawned instances and terminate
+    Ok(receiver.into_iter())
+}
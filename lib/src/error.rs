@@ -6,12 +6,15 @@ pub type Result<T = (), E = Error> = ::std::result::Result<T, E>;
 pub enum Error {
     #[error("IO Error: {0}")]
     Io(#[from] ::std::io::Error),
+    #[cfg(feature = "clingo")]
     #[error("Clingo Error: {0}")]
     Clingo(#[from] ::clingo::ClingoError),
     #[error("parser error")]
     Parser(#[from] crate::framework::ParserError),
     #[error("logic error: {0}")]
     Logic(String),
-    #[error("bug: clingo backend not initialized")]
+    #[error("clingo backend not initialized, reinitialize the framework")]
     ClingoNotInitialized,
+    #[error("solve limit exceeded")]
+    LimitExceeded,
 }
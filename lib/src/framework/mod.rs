@@ -15,23 +15,39 @@ pub enum ParserError {
         "Error while parsing file: Expected {expected:?}, but found {found:?}: ({position:?}: {text})"
     )]
     UnexpectedToken {
-        found: Box<dyn ::std::fmt::Debug>,
-        expected: Vec<Box<dyn ::std::fmt::Debug>>,
+        found: Box<dyn ::std::fmt::Debug + Send + Sync>,
+        expected: Vec<Box<dyn ::std::fmt::Debug + Send + Sync>>,
         position: std::ops::Range<usize>,
         text: String,
     },
     #[error("Unexpected end of input while parsing: Expected {expected:?}")]
     UnexpectedEndOfInput {
-        expected: Vec<Box<dyn ::std::fmt::Debug>>,
+        expected: Vec<Box<dyn ::std::fmt::Debug + Send + Sync>>,
     },
     #[error("Argument {arg_id:?} is configured as optional but does not exist")]
     OptionalArgumentNotFound { arg_id: String },
     #[error("Attack from {from:?} to {to:?} is configured as optional but does not exist")]
     OptionalAttackNotFound { from: String, to: String },
+    #[error("Argument {arg_id:?} is declared more than once")]
+    DuplicateArgument { arg_id: String },
+    #[error("Attack from {from:?} to {to:?} is declared more than once")]
+    DuplicateAttack { from: String, to: String },
+    #[error("{element} is declared both optional and not optional")]
+    ConflictingOptionality { element: String },
+    #[error("Attack from {from:?} to {to:?} uses the undeclared argument {arg_id:?}")]
+    UndeclaredArgument {
+        arg_id: String,
+        from: String,
+        to: String,
+    },
+    #[error("{element} is marked optional, which is not part of the ICCMA formats")]
+    NotIccmaCompliant { element: String },
+    #[error("Invalid timestamp {text:?}, expected a number like in `@1234: +arg(a).`")]
+    InvalidTimestamp { text: String },
 }
 
 /// A generic extension.
-pub trait GenericExtension {
+pub trait GenericExtension: PartialEq {
     /// Argument type used by the extension.
     type Arg;
     /// Check whether the given argument is contained in this extension.
@@ -39,6 +55,86 @@ pub trait GenericExtension {
     /// Format the extension.
     /// The return-value should comply the ICCMA specification for extension output
     fn format(&self) -> String;
+    /// Format the extension as an ICCMA'23 witness line, i.e. `w a1 a2`.
+    fn format_witness(&self) -> String;
+    /// Number of arguments in this extension.
+    fn len(&self) -> usize;
+    /// Arguments contained in this or the other extension.
+    fn union(&self, other: &Self) -> Self;
+    /// Arguments contained in both this and the other extension.
+    fn intersection(&self, other: &Self) -> Self;
+    /// Whether this extension contains no arguments.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Size of the extension to look for, see [`Framework::optimal_extension`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    /// An extension with as many arguments as possible
+    Maximum,
+    /// An extension with as few arguments as possible
+    Minimum,
+}
+
+impl Objective {
+    /// Whether an extension of the given size is better than the best one so far.
+    pub fn improves(self, size: usize, best: usize) -> bool {
+        match self {
+            Objective::Maximum => size > best,
+            Objective::Minimum => size < best,
+        }
+    }
+}
+
+/// Find an optimal extension by enumerating all of them.
+///
+/// Of all optimal extensions, the first one enumerated is returned.
+pub fn optimal_by_enumeration<F: Framework>(
+    af: &mut F,
+    objective: Objective,
+) -> Result<Option<F::Extension>> {
+    let mut best: Option<F::Extension> = None;
+    af.enumerate_extensions()?.by_ref().for_each(|ext| {
+        if best
+            .as_ref()
+            .is_none_or(|best| objective.improves(ext.len(), best.len()))
+        {
+            best = Some(ext);
+        }
+        Ok(())
+    })?;
+    Ok(best)
+}
+
+/// Compute the union of all extensions by enumerating them, `None` if there is none.
+pub fn union_by_enumeration<F: Framework>(af: &mut F) -> Result<Option<F::Extension>> {
+    af.enumerate_extensions()?
+        .by_ref()
+        .fold(None, |union: Option<F::Extension>, ext| {
+            Ok(Some(match union {
+                Some(union) => union.union(&ext),
+                None => ext,
+            }))
+        })
+}
+
+/// Compute the intersection of all extensions by enumerating them, `None` if there is none.
+pub fn intersection_by_enumeration<F: Framework>(af: &mut F) -> Result<Option<F::Extension>> {
+    af.enumerate_extensions()?
+        .by_ref()
+        .fold(None, |intersection: Option<F::Extension>, ext| {
+            Ok(Some(match intersection {
+                Some(intersection) => intersection.intersection(&ext),
+                None => ext,
+            }))
+        })
+}
+
+/// Check whether the candidate is an extension by enumerating all of them.
+pub fn verify_by_enumeration<F: Framework>(af: &mut F, candidate: &F::Extension) -> Result<bool> {
+    af.enumerate_extensions()?.any(|ext| Ok(ext == *candidate))
 }
 
 /// A general framework for argumentation
@@ -57,7 +153,17 @@ where
     /// All other extension methods are derived, but may be overriden if necessary.
     fn enumerate_extensions(&mut self) -> Result<IterGuard<'_, Self>>;
     /// Update the framework with the given line from standard input.
+    ///
+    /// Implementations may defer the actual work until the next query, so that
+    /// consecutive updates are coalesced.
     fn update(&mut self, update_line: &str) -> Result<()>;
+    /// Update the framework with all given lines before the next query.
+    ///
+    /// Implementations may apply the lines as one batch, with a single pass over the
+    /// affected elements.
+    fn update_many(&mut self, update_lines: &[&str]) -> Result<()> {
+        update_lines.iter().try_for_each(|line| self.update(line))
+    }
     /// Drop the extension iter.
     ///
     /// May be used to recycle the iterator if necessary.
@@ -72,6 +178,26 @@ where
     fn sample_extension(&mut self) -> Result<Option<Self::Extension>> {
         self.enumerate_extensions()?.next()
     }
+    /// Check whether the given set of arguments is an extension of the current framework.
+    fn verify_extension(&mut self, candidate: &Self::Extension) -> Result<bool> {
+        verify_by_enumeration(self, candidate)
+    }
+    /// Check whether there is any extension.
+    fn has_extension(&mut self) -> Result<bool> {
+        Ok(self.sample_extension()?.is_some())
+    }
+    /// Return an extension of maximum or minimum size, `None` if there is none.
+    fn optimal_extension(&mut self, objective: Objective) -> Result<Option<Self::Extension>> {
+        optimal_by_enumeration(self, objective)
+    }
+    /// Return the arguments contained in some extension, `None` if there is no extension.
+    fn credulously_accepted_arguments(&mut self) -> Result<Option<Self::Extension>> {
+        union_by_enumeration(self)
+    }
+    /// Return the arguments contained in every extension, `None` if there is no extension.
+    fn skeptically_accepted_arguments(&mut self) -> Result<Option<Self::Extension>> {
+        intersection_by_enumeration(self)
+    }
     /// Check the given argument for credulous acceptance.
     fn is_credulous_accepted(
         &mut self,
@@ -0,0 +1,111 @@
+//! Fetching and caching benchmark instances
+//!
+//! Instances are stored as lzma compressed archives named `<name>.<format>.lzma`, like
+//! the ICCMA instance sets. Missing archives are downloaded from a mirror and all
+//! archives are decompressed on first use, so that repeated runs work offline.
+//!
+//! Configured via environment variables:
+//! - `DASP_INSTANCE_CACHE`: Directory holding the archives and decompressed instances,
+//!   defaults to `dasp-instances` in the temporary directory.
+//! - `DASP_INSTANCE_MIRROR`: URL of a directory serving the archives. Without it,
+//!   only instances already in the cache are available.
+use std::{
+    env, fs,
+    io::{BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::{Error, Result};
+
+/// Directory of cached instances.
+#[derive(Debug, Clone)]
+pub struct InstanceCache {
+    dir: PathBuf,
+    mirror: Option<String>,
+}
+
+impl InstanceCache {
+    /// Cache configured by the environment, see the [module documentation](self).
+    pub fn from_env() -> Result<Self> {
+        let dir = env::var_os("DASP_INSTANCE_CACHE")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| env::temp_dir().join("dasp-instances"));
+        let mirror = env::var("DASP_INSTANCE_MIRROR").ok();
+        Self::new(dir, mirror)
+    }
+    /// Cache in `dir`, downloading missing archives from `mirror`, if any.
+    pub fn new(dir: impl Into<PathBuf>, mirror: Option<String>) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(InstanceCache { dir, mirror })
+    }
+    /// Path of the decompressed instance, fetching and decompressing it if necessary.
+    ///
+    /// The `format` is the file extension, e.g. `tgf` or `apx`.
+    pub fn get(&self, name: &str, format: &str) -> Result<PathBuf> {
+        let file_name = format!("{name}.{format}");
+        let path = self.dir.join(&file_name);
+        if path.exists() {
+            return Ok(path);
+        }
+        let archive = self.dir.join(format!("{file_name}.lzma"));
+        if !archive.exists() {
+            self.download(&format!("{file_name}.lzma"), &archive)?;
+        }
+        decompress(&archive, &path)?;
+        Ok(path)
+    }
+    /// Content of the decompressed instance, see [`InstanceCache::get`].
+    pub fn read(&self, name: &str, format: &str) -> Result<String> {
+        Ok(fs::read_to_string(self.get(name, format)?)?)
+    }
+    fn download(&self, file_name: &str, target: &Path) -> Result {
+        let Some(mirror) = &self.mirror else {
+            return Err(Error::Logic(format!(
+                "{file_name} is not cached in {} and DASP_INSTANCE_MIRROR is not set",
+                self.dir.display()
+            )));
+        };
+        let url = format!("{}/{file_name}", mirror.trim_end_matches('/'));
+        log::info!("Downloading {url}");
+        // Download next to the target, so that interrupted downloads are never used
+        let partial = partial(target);
+        let status = Command::new("curl")
+            .args([
+                "--fail",
+                "--silent",
+                "--show-error",
+                "--location",
+                "--output",
+            ])
+            .arg(&partial)
+            .arg(&url)
+            .status()?;
+        if !status.success() {
+            fs::remove_file(&partial).ok();
+            return Err(Error::Logic(format!("Downloading {url} failed: {status}")));
+        }
+        fs::rename(partial, target)?;
+        Ok(())
+    }
+}
+
+fn decompress(archive: &Path, target: &Path) -> Result {
+    log::debug!("Decompressing {}", archive.display());
+    let partial = partial(target);
+    let mut reader = BufReader::new(fs::File::open(archive)?);
+    let mut writer = BufWriter::new(fs::File::create(&partial)?);
+    lzma_rs::lzma_decompress(&mut reader, &mut writer)
+        .map_err(|why| Error::Logic(format!("Decompressing {}: {why}", archive.display())))?;
+    writer.flush()?;
+    fs::rename(partial, target)?;
+    Ok(())
+}
+
+/// Path to write to before moving the complete file to `path`.
+fn partial(path: &Path) -> PathBuf {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    PathBuf::from(partial)
+}
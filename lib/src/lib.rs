@@ -1,13 +1,16 @@
-#![feature(try_find)]
 pub mod argumentation_framework;
+#[cfg(feature = "clingo")]
+pub mod batch;
 mod error;
 pub mod framework;
+#[cfg(feature = "instances")]
+pub mod instances;
 pub mod semantics;
-#[cfg(test)]
+#[cfg(all(test, feature = "clingo"))]
 mod tests;
 
 pub use error::{Error, Result};
-pub use framework::{Framework, GenericExtension};
+pub use framework::{Framework, GenericExtension, Objective};
 
 /// Try setting up logging for unit tests
 #[cfg(test)]
@@ -43,9 +46,7 @@ mod macros {
     /// Macro to easily construct an extension
     macro_rules! ext {
         () => {
-            crate::argumentation_framework::Extension {
-                atoms: crate::macros::set!()
-            }
+            crate::argumentation_framework::Extension::EMPTY
         };
         ($($arg:literal),*) => {
             [
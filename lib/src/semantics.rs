@@ -13,7 +13,12 @@ macro_rules! semantics {
 }
 
 semantics!(Admissible);
+semantics!(Cf2);
 semantics!(Complete);
 semantics!(ConflictFree);
+semantics!(Custom);
 semantics!(Ground);
+semantics!(Preferred);
+semantics!(ResolutionGrounded);
 semantics!(Stable);
+semantics!(StronglyAdmissible);
@@ -0,0 +1,225 @@
+//! WebAssembly bindings for running DASP client-side
+//!
+//! libclingo does not compile to wasm, so frameworks are solved by the brute-force
+//! [`reference`] solver of the lib, which is built without its `clingo` feature here.
+//! This is only feasible for small frameworks, like the ones of educational tools.
+//!
+//! Build the package for the browser with `wasm-pack build wasm --target web`.
+use std::collections::HashSet;
+
+use js_sys::Array;
+use lib::{
+    argumentation_framework::{
+        parse_initial_file,
+        reference::{self, ReferenceSemantics, MAX_ARGUMENTS},
+        symbols, Extension, Patch,
+    },
+    Error, GenericExtension, Result,
+};
+use wasm_bindgen::prelude::*;
+
+/// Semantics to solve a framework under.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Semantics {
+    ConflictFree,
+    Admissible,
+    Complete,
+    Grounded,
+    Stable,
+    Preferred,
+    Cf2,
+    StronglyAdmissible,
+    ResolutionGrounded,
+}
+
+impl From<Semantics> for ReferenceSemantics {
+    fn from(semantics: Semantics) -> Self {
+        match semantics {
+            Semantics::ConflictFree => ReferenceSemantics::ConflictFree,
+            Semantics::Admissible => ReferenceSemantics::Admissible,
+            Semantics::Complete => ReferenceSemantics::Complete,
+            Semantics::Grounded => ReferenceSemantics::Grounded,
+            Semantics::Stable => ReferenceSemantics::Stable,
+            Semantics::Preferred => ReferenceSemantics::Preferred,
+            Semantics::Cf2 => ReferenceSemantics::Cf2,
+            Semantics::StronglyAdmissible => ReferenceSemantics::StronglyAdmissible,
+            Semantics::ResolutionGrounded => ReferenceSemantics::ResolutionGrounded,
+        }
+    }
+}
+
+/// A framework in APX or TGF format under some semantics.
+///
+/// Like in the solver backend, optional elements start disabled and only they can be
+/// enabled or disabled by updates.
+#[wasm_bindgen]
+pub struct Framework {
+    arguments: Vec<symbols::Argument>,
+    attacks: Vec<symbols::Attack>,
+    /// Enabled optional elements, formatted as APX atoms
+    enabled: HashSet<String>,
+    semantics: Semantics,
+}
+
+#[wasm_bindgen]
+impl Framework {
+    /// Parse the content of an initial file.
+    #[wasm_bindgen(constructor)]
+    pub fn new(input: &str, semantics: Semantics) -> Result<Framework, JsError> {
+        Ok(Self::parse(input, semantics)?)
+    }
+
+    /// Apply an update line in APXM or TGFM format, i.e. `+att(a,b).`
+    pub fn update(&mut self, line: &str) -> Result<(), JsError> {
+        Ok(self.apply(line)?)
+    }
+
+    /// All extensions, each as array of argument ids.
+    pub fn extensions(&self) -> Result<Array, JsError> {
+        Ok(self.solve()?.iter().map(to_array).collect())
+    }
+
+    /// Number of extensions.
+    pub fn count(&self) -> Result<usize, JsError> {
+        Ok(self.solve()?.len())
+    }
+
+    /// Whether the argument is contained in some extension.
+    pub fn credulous(&self, argument: &str) -> Result<bool, JsError> {
+        let argument = symbols::Argument::new(argument, false);
+        Ok(self.solve()?.iter().any(|ext| ext.contains(&argument)))
+    }
+
+    /// Whether the argument is contained in all extensions.
+    pub fn skeptical(&self, argument: &str) -> Result<bool, JsError> {
+        let argument = symbols::Argument::new(argument, false);
+        Ok(self.solve()?.iter().all(|ext| ext.contains(&argument)))
+    }
+}
+
+impl Framework {
+    fn parse(input: &str, semantics: Semantics) -> Result<Self> {
+        let (arguments, attacks) = parse_initial_file(input)?;
+        if arguments.len() > MAX_ARGUMENTS {
+            return Err(Error::Logic(format!(
+                "Only frameworks with up to {MAX_ARGUMENTS} arguments are supported, found {}",
+                arguments.len()
+            )));
+        }
+        Ok(Framework {
+            arguments,
+            attacks,
+            enabled: HashSet::new(),
+            semantics,
+        })
+    }
+
+    /// Apply all patches of the line, or none if any targets an element that is not optional.
+    fn apply(&mut self, line: &str) -> Result {
+        let patches = Patch::parse_line(line)?;
+        let assignments = patches
+            .iter()
+            .map(|patch| self.resolve(patch))
+            .collect::<Result<Vec<_>>>()?;
+        for (element, enable) in assignments {
+            if enable {
+                self.enabled.insert(element);
+            } else {
+                self.enabled.remove(&element);
+            }
+        }
+        Ok(())
+    }
+
+    /// Find the optional element changed by the patch and its new state.
+    fn resolve(&self, patch: &Patch) -> Result<(String, bool)> {
+        let (kind, element, optional, enable) = match patch {
+            Patch::EnableArgument(argument) | Patch::DisableArgument(argument) => (
+                "argument",
+                argument.to_string(),
+                self.arguments
+                    .iter()
+                    .any(|arg| arg.id == argument.id && arg.optional),
+                matches!(patch, Patch::EnableArgument(_)),
+            ),
+            Patch::EnableAttack(attack) | Patch::DisableAttack(attack) => (
+                "attack",
+                attack.to_string(),
+                self.attacks
+                    .iter()
+                    .any(|att| att.from == attack.from && att.to == attack.to && att.optional),
+                matches!(patch, Patch::EnableAttack(_)),
+            ),
+        };
+        if !optional {
+            return Err(Error::Logic(format!(
+                "The {kind} {element} was not defined as optional and cannot be {} now",
+                if enable { "enabled" } else { "disabled" }
+            )));
+        }
+        Ok((element, enable))
+    }
+
+    /// Extensions of the arguments and attacks currently part of the framework.
+    fn solve(&self) -> Result<Vec<Extension>> {
+        let is_active =
+            |optional: bool, element: String| !optional || self.enabled.contains(&element);
+        let arguments: Vec<_> = self
+            .arguments
+            .iter()
+            .filter(|arg| is_active(arg.optional, arg.to_string()))
+            .cloned()
+            .collect();
+        let attacks: Vec<_> = self
+            .attacks
+            .iter()
+            .filter(|att| is_active(att.optional, att.to_string()))
+            .cloned()
+            .collect();
+        reference::extensions(&arguments, &attacks, self.semantics.into())
+    }
+}
+
+/// Parse the framework and return all its extensions, each as array of argument ids.
+#[wasm_bindgen]
+pub fn solve(input: &str, semantics: Semantics) -> Result<Array, JsError> {
+    Framework::new(input, semantics)?.extensions()
+}
+
+fn to_array(extension: &Extension) -> JsValue {
+    extension
+        .arguments()
+        .map(|arg| JsValue::from_str(&arg.id))
+        .collect::<Array>()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(extensions: Vec<Extension>) -> Vec<Vec<String>> {
+        extensions
+            .iter()
+            .map(|ext| ext.arguments().map(|arg| arg.id.clone()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn updates_toggle_optional_elements() {
+        let mut af = Framework::parse("a\nb\nc?\n#\na b\nc a\n", Semantics::Stable).unwrap();
+        assert_eq!(ids(af.solve().unwrap()), [["a"]]);
+        af.apply("+arg(c).").unwrap();
+        assert_eq!(ids(af.solve().unwrap()), [["b", "c"]]);
+        af.apply("-arg(c).").unwrap();
+        assert_eq!(ids(af.solve().unwrap()), [["a"]]);
+    }
+
+    #[test]
+    fn updates_are_all_or_nothing() {
+        let mut af = Framework::parse("a\nb?\n#\na b\n", Semantics::Grounded).unwrap();
+        assert!(af.apply("+arg(b):att(a,b).").is_err());
+        assert!(af.enabled.is_empty());
+    }
+}